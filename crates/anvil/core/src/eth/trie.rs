@@ -1,7 +1,12 @@
 //! Utility functions for Ethereum adapted from <https://github.com/rust-blockchain/ethereum/blob/755dffaa4903fbec1269f50cde9863cf86269a14/src/util.rs>
 
-use alloy_primitives::{fixed_bytes, B256};
-use alloy_trie::{HashBuilder, Nibbles};
+use alloy_primitives::{fixed_bytes, keccak256, Bytes, B256};
+use alloy_rlp::Decodable;
+use alloy_trie::{
+    nodes::TrieNode,
+    proof::{self, ProofVerificationError},
+    HashBuilder, Nibbles,
+};
 use std::collections::BTreeMap;
 
 /// The KECCAK of the RLP encoding of empty data.
@@ -28,3 +33,90 @@ pub fn ordered_trie_root<I, V>(input: I) -> B256
 
     builder.root()
 }
+
+/// A Merkle-Patricia proof which doesn't prove the expected value.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+#[error("proof node {node} is invalid: {source}")]
+pub struct ProofError {
+    /// The index of the first proof node which failed verification.
+    pub node: usize,
+    pub source: Box<ProofVerificationError>,
+}
+
+/// Verifies a proof of the value at `key` of a secure trie with the given root, such as the state
+/// or a storage trie, where the path of a key is its hash.
+///
+/// The expected value is `None` if the proof shows that the key is absent from the trie.
+pub fn verify_proof(
+    root: B256,
+    key: impl AsRef<[u8]>,
+    expected_value: Option<Vec<u8>>,
+    nodes: &[Bytes],
+) -> Result<(), ProofError> {
+    let path = Nibbles::unpack(keccak256(key));
+    proof::verify_proof(root, path, expected_value, nodes).map_err(|source| {
+        let node = match &source {
+            ProofVerificationError::RootMismatch { .. } => 0,
+            // Either a node which doesn't hash to the reference held by its parent, or the value
+            // at the end of the proof.
+            ProofVerificationError::ValueMismatch { got, .. } => got
+                .as_ref()
+                .and_then(|got| nodes.iter().position(|node| node == got))
+                .unwrap_or(nodes.len().saturating_sub(1)),
+            ProofVerificationError::UnexpectedEmptyRoot | ProofVerificationError::Rlp(_) => nodes
+                .iter()
+                .position(|node| {
+                    !matches!(
+                        TrieNode::decode(&mut &node[..]),
+                        Ok(TrieNode::Branch(_) | TrieNode::Extension(_) | TrieNode::Leaf(_))
+                    )
+                })
+                .unwrap_or_default(),
+        };
+        ProofError { node, source: Box::new(source) }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_trie::proof::ProofRetainer;
+
+    #[test]
+    fn verifies_proofs() {
+        let keys = [B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3)];
+        let mut leaves = keys
+            .iter()
+            .map(|key| (Nibbles::unpack(keccak256(key)), alloy_rlp::encode(key)))
+            .collect::<Vec<_>>();
+        leaves.sort();
+
+        let target = Nibbles::unpack(keccak256(keys[0]));
+        let mut builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::new(vec![target.clone()]));
+        for (path, value) in &leaves {
+            builder.add_leaf(path.clone(), value);
+        }
+        let root = builder.root();
+        let mut nodes = builder
+            .take_proof_nodes()
+            .matching_nodes_sorted(&target)
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect::<Vec<_>>();
+        assert!(nodes.len() > 1);
+
+        let value = alloy_rlp::encode(keys[0]);
+        assert_eq!(verify_proof(root, keys[0], Some(value.clone()), &nodes), Ok(()));
+        assert_eq!(verify_proof(root, keys[0], None, &nodes).unwrap_err().node, nodes.len() - 1);
+        assert_eq!(
+            verify_proof(B256::ZERO, keys[0], Some(value.clone()), &nodes).unwrap_err().node,
+            0
+        );
+
+        let mut node = nodes[1].to_vec();
+        *node.last_mut().unwrap() ^= 1;
+        nodes[1] = node.into();
+        assert_eq!(verify_proof(root, keys[0], Some(value), &nodes).unwrap_err().node, 1);
+    }
+}
@@ -88,7 +88,7 @@
 use futures::channel::{mpsc::Receiver, oneshot};
 use parking_lot::RwLock;
 use revm::primitives::Bytecode;
-use std::{future::Future, sync::Arc, time::Duration};
+use std::{fmt, future::Future, sync::Arc, time::Duration};
 
 /// The client version: `anvil/v{major}.{minor}.{patch}`
 pub const CLIENT_VERSION: &str = concat!("anvil/v", env!("CARGO_PKG_VERSION"));
@@ -128,6 +128,12 @@ pub struct EthApi {
     instance_id: Arc<RwLock<B256>>,
 }
 
+impl fmt::Debug for EthApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EthApi").finish_non_exhaustive()
+    }
+}
+
 impl EthApi {
     /// Creates a new instance
     #[allow(clippy::too_many_arguments)]
@@ -1178,20 +1184,7 @@ pub async fn estimate_gas(
     /// Handler for ETH RPC call: `eth_getTransactionByHash`
     pub async fn transaction_by_hash(&self, hash: B256) -> Result<Option<AnyRpcTransaction>> {
         node_info!("eth_getTransactionByHash");
-        let mut tx = self.pool.get_transaction(hash).map(|pending| {
-            let from = *pending.sender();
-            let mut tx = transaction_build(
-                Some(*pending.hash()),
-                pending.transaction,
-                None,
-                None,
-                Some(self.backend.base_fee()),
-            );
-            // we set the from field here explicitly to the set sender of the pending transaction,
-            // in case the transaction is impersonated.
-            tx.from = from;
-            tx
-        });
+        let mut tx = self.pool_transaction_by_hash(hash);
         if tx.is_none() {
             tx = self.backend.transaction_by_hash(hash).await?
         }
@@ -2769,6 +2762,24 @@ pub fn new_ready_transactions(&self) -> Receiver<TxHash> {
         self.pool.add_ready_listener()
     }
 
+    /// Returns the transaction with the given hash if it's in the pool
+    pub fn pool_transaction_by_hash(&self, hash: B256) -> Option<AnyRpcTransaction> {
+        self.pool.get_transaction(hash).map(|pending| {
+            let from = *pending.sender();
+            let mut tx = transaction_build(
+                Some(*pending.hash()),
+                pending.transaction,
+                None,
+                None,
+                Some(self.backend.base_fee()),
+            );
+            // we set the from field here explicitly to the set sender of the pending transaction,
+            // in case the transaction is impersonated.
+            tx.from = from;
+            tx
+        })
+    }
+
     /// Returns a new accessor for certain storage elements
     pub fn storage_info(&self) -> StorageInfo {
         StorageInfo::new(Arc::clone(&self.backend))
@@ -1,7 +1,8 @@
 use crate::{
     eth::{backend::notifications::NewBlockNotifications, error::to_rpc_result},
-    StorageInfo,
+    EthApi, StorageInfo,
 };
+use alloy_network::AnyRpcTransaction;
 use alloy_primitives::{TxHash, B256};
 use alloy_rpc_types::{pubsub::SubscriptionResult, FilteredParams, Log, Transaction};
 use anvil_core::eth::{block::Block, subscription::SubscriptionId, transaction::TypedReceipt};
@@ -86,6 +87,7 @@ pub enum EthSubscription {
     Logs(Box<LogsSubscription>),
     Header(NewBlockNotifications, StorageInfo, SubscriptionId),
     PendingTransactions(Receiver<TxHash>, SubscriptionId),
+    FullPendingTransactions(Receiver<TxHash>, EthApi, SubscriptionId),
 }
 
 impl EthSubscription {
@@ -120,6 +122,21 @@ fn poll_response(&mut self, cx: &mut Context<'_>) -> Poll<Option<EthSubscription
                     });
                 Poll::Ready(res)
             }
+            Self::FullPendingTransactions(tx, api, id) => {
+                // skip transactions that were already mined or dropped from the pool
+                loop {
+                    let Some(hash) = ready!(tx.poll_next_unpin(cx)) else {
+                        return Poll::Ready(None);
+                    };
+                    if let Some(tx) = api.pool_transaction_by_hash(hash) {
+                        let result = to_rpc_result(
+                            SubscriptionResult::<AnyRpcTransaction>::FullTransaction(Box::new(tx)),
+                        );
+                        let params = EthSubscriptionParams { subscription: id.clone(), result };
+                        return Poll::Ready(Some(EthSubscriptionResponse::new(params)));
+                    }
+                }
+            }
         }
     }
 }
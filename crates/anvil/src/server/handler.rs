@@ -59,9 +59,12 @@ async fn on_pub_sub(&self, pubsub: EthPubSub, cx: PubSubContext<Self>) -> Respon
                 ResponseResult::Success(canceled.into())
             }
             EthPubSub::EthSubscribe(kind, params) => {
-                let filter = match *params {
-                    Params::None => None,
-                    Params::Logs(filter) => Some(*filter),
+                let (filter, full) = match *params {
+                    Params::None => (None, false),
+                    Params::Logs(filter) => (Some(*filter), false),
+                    Params::Bool(full) if kind == SubscriptionKind::NewPendingTransactions => {
+                        (None, full)
+                    }
                     Params::Bool(_) => {
                         return ResponseResult::Error(RpcError::invalid_params(
                             "Expected params for logs subscription",
@@ -91,10 +94,16 @@ async fn on_pub_sub(&self, pubsub: EthPubSub, cx: PubSubContext<Self>) -> Respon
                     }
                     SubscriptionKind::NewPendingTransactions => {
                         trace!(target: "rpc::ws", "received pending transactions subscription");
-                        EthSubscription::PendingTransactions(
-                            self.api.new_ready_transactions(),
-                            id.clone(),
-                        )
+                        let transactions = self.api.new_ready_transactions();
+                        if full {
+                            EthSubscription::FullPendingTransactions(
+                                transactions,
+                                self.api.clone(),
+                                id.clone(),
+                            )
+                        } else {
+                            EthSubscription::PendingTransactions(transactions, id.clone())
+                        }
                     }
                     SubscriptionKind::Syncing => {
                         return RpcError::internal_error_with("Not implemented").into()
@@ -28,6 +28,25 @@ async fn test_sub_new_heads() {
     assert_eq!(block_numbers, vec![1, 2, 3]);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sub_full_pending_transactions() {
+    let (_api, handle) = spawn(NodeConfig::test().with_no_mining(true)).await;
+
+    let provider = connect_pubsub(&handle.ws_endpoint()).await;
+    let accounts: Vec<_> = handle.dev_wallets().collect();
+    let from = accounts[0].address();
+    let to = accounts[1].address();
+
+    let mut txs = provider.subscribe_full_pending_transactions().await.unwrap().into_stream();
+
+    let tx = TransactionRequest::default().from(from).to(to).value(U256::from(1337));
+    let hash = *provider.send_transaction(tx).await.unwrap().tx_hash();
+
+    let tx = txs.next().await.unwrap();
+    assert_eq!(*tx.inner.tx_hash(), hash);
+    assert_eq!(tx.from, from);
+}
+
 sol!(
     #[sol(rpc)]
     EmitLogs,
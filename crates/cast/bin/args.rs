@@ -1,9 +1,11 @@
 use crate::cmd::{
-    access_list::AccessListArgs, artifact::ArtifactArgs, bind::BindArgs, call::CallArgs,
-    constructor_args::ConstructorArgsArgs, create2::Create2Args, creation_code::CreationCodeArgs,
-    estimate::EstimateArgs, find_block::FindBlockArgs, interface::InterfaceArgs, logs::LogsArgs,
-    mktx::MakeTxArgs, rpc::RpcArgs, run::RunArgs, send::SendTxArgs, storage::StorageArgs,
-    wallet::WalletSubcommands,
+    access_list::AccessListArgs, artifact::ArtifactArgs, bind::BindArgs, bisect::BisectArgs,
+    call::CallArgs, constructor_args::ConstructorArgsArgs, create2::Create2Args,
+    creation_code::CreationCodeArgs, decompile::DecompileArgs, estimate::EstimateArgs,
+    find_block::FindBlockArgs, find_slot::FindSlotArgs, interface::InterfaceArgs, logs::LogsArgs,
+    mempool::MempoolArgs, mktx::MakeTxArgs, rpc::RpcArgs, run::RunArgs, safe::SafeSubcommands,
+    send::SendTxArgs, storage::StorageArgs, trace_diff::TraceDiffArgs,
+    verify_proof::VerifyProofArgs, wallet::WalletSubcommands,
 };
 use alloy_primitives::{Address, B256, U256};
 use alloy_rpc_types::BlockId;
@@ -41,607 +43,320 @@ pub struct Cast {
 
 #[derive(Subcommand)]
 pub enum CastSubcommand {
-    /// Prints the maximum value of the given integer type.
-    #[command(visible_aliases = &["--max-int", "maxi"])]
-    MaxInt {
-        /// The integer type to get the maximum value of.
-        #[arg(default_value = "int256")]
-        r#type: String,
-    },
+    #[command(flatten)]
+    Convert(ConvertSubcommand),
 
-    /// Prints the minimum value of the given integer type.
-    #[command(visible_aliases = &["--min-int", "mini"])]
-    MinInt {
-        /// The integer type to get the minimum value of.
-        #[arg(default_value = "int256")]
-        r#type: String,
+    /// Create an access list for a transaction.
+    #[command(visible_aliases = &["ac", "acl"])]
+    AccessList(AccessListArgs),
+    /// Get logs by signature or topic.
+    #[command(visible_alias = "l")]
+    Logs(LogsArgs),
+    /// Watch pending transactions in the mempool and decode their calldata.
+    #[command(visible_alias = "mp")]
+    Mempool(MempoolArgs),
+    /// Get information about a block.
+    #[command(visible_alias = "bl")]
+    Block {
+        /// The block height to query at.
+        ///
+        /// Can also be the tags earliest, finalized, safe, latest, or pending.
+        block: Option<BlockId>,
+
+        /// If specified, only get the given field of the block.
+        #[arg(long, short)]
+        field: Option<String>,
+
+        #[arg(long, env = "CAST_FULL_BLOCK")]
+        full: bool,
+
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Prints the maximum value of the given integer type.
-    #[command(visible_aliases = &["--max-uint", "maxu"])]
-    MaxUint {
-        /// The unsigned integer type to get the maximum value of.
-        #[arg(default_value = "uint256")]
-        r#type: String,
+    /// Get the latest block number.
+    #[command(visible_alias = "bn")]
+    BlockNumber {
+        /// The hash or tag to query. If not specified, the latest number is returned.
+        block: Option<BlockId>,
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Prints the zero address.
-    #[command(visible_aliases = &["--address-zero", "az"])]
-    AddressZero,
+    /// Perform a call on an account without publishing a transaction.
+    #[command(visible_alias = "c")]
+    Call(CallArgs),
 
-    /// Prints the zero hash.
-    #[command(visible_aliases = &["--hash-zero", "hz"])]
-    HashZero,
+    /// ABI-encode a function with arguments.
+    #[command(name = "calldata", visible_alias = "cd")]
+    CalldataEncode {
+        /// The function signature in the format `<name>(<in-types>)(<out-types>)`
+        sig: String,
 
-    /// Convert UTF8 text to hex.
-    #[command(
-        visible_aliases = &[
-        "--from-ascii",
-        "--from-utf8",
-        "from-ascii",
-        "fu",
-        "fa"]
-    )]
-    FromUtf8 {
-        /// The text to convert.
-        text: Option<String>,
+        /// The arguments to encode.
+        #[arg(allow_hyphen_values = true)]
+        args: Vec<String>,
     },
 
-    /// Concatenate hex strings.
-    #[command(visible_aliases = &["--concat-hex", "ch"])]
-    ConcatHex {
-        /// The data to concatenate.
-        data: Vec<String>,
+    /// Get the symbolic name of the current chain.
+    Chain {
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Convert binary data into hex data.
-    #[command(visible_aliases = &["--from-bin", "from-binx", "fb"])]
-    FromBin,
+    /// Get the Ethereum chain ID.
+    #[command(visible_aliases = &["ci", "cid"])]
+    ChainId {
+        #[command(flatten)]
+        rpc: RpcOpts,
+    },
 
-    /// Normalize the input to lowercase, 0x-prefixed hex.
-    ///
-    /// The input can be:
-    /// - mixed case hex with or without 0x prefix
-    /// - 0x prefixed hex, concatenated with a ':'
-    /// - an absolute path to file
-    /// - @tag, where the tag is defined in an environment variable
-    #[command(visible_aliases = &["--to-hexdata", "thd", "2hd"])]
-    ToHexdata {
-        /// The input to normalize.
-        input: Option<String>,
+    /// Get the current client version.
+    #[command(visible_alias = "cl")]
+    Client {
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Convert an address to a checksummed format (EIP-55).
-    #[command(
-        visible_aliases = &["--to-checksum-address",
-        "--to-checksum",
-        "to-checksum",
-        "ta",
-        "2a"]
-    )]
-    ToCheckSumAddress {
-        /// The address to convert.
+    /// Compute the contract address from a given nonce and deployer address.
+    #[command(visible_alias = "ca")]
+    ComputeAddress {
+        /// The deployer address.
         address: Option<Address>,
-    },
 
-    /// Convert hex data to an ASCII string.
-    #[command(visible_aliases = &["--to-ascii", "tas", "2as"])]
-    ToAscii {
-        /// The hex data to convert.
-        hexdata: Option<String>,
+        /// The nonce of the deployer address.
+        #[arg(long)]
+        nonce: Option<u64>,
+
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Convert hex data to a utf-8 string.
-    #[command(visible_aliases = &["--to-utf8", "tu8", "2u8"])]
-    ToUtf8 {
-        /// The hex data to convert.
-        hexdata: Option<String>,
+    /// Disassembles a hex-encoded bytecode into a human-readable representation.
+    #[command(visible_alias = "da")]
+    Disassemble {
+        /// The hex-encoded bytecode.
+        bytecode: Option<String>,
     },
 
-    /// Convert a fixed point number into an integer.
-    #[command(visible_aliases = &["--from-fix", "ff"])]
-    FromFixedPoint {
-        /// The number of decimals to use.
-        decimals: Option<String>,
+    /// Decompiles a runtime bytecode into pseudo-Solidity or annotated assembly.
+    ///
+    /// Recovers the control flow of the bytecode, splits its dispatcher into one function per
+    /// selector and identifies storage slot accesses. Function signatures are resolved through
+    /// the local signature cache and https://openchain.xyz.
+    #[command(visible_alias = "dc")]
+    Decompile(DecompileArgs),
 
-        /// The value to convert.
-        #[arg(allow_hyphen_values = true)]
-        value: Option<String>,
-    },
+    /// Build and sign a transaction.
+    #[command(name = "mktx", visible_alias = "m")]
+    MakeTx(MakeTxArgs),
 
-    /// Right-pads hex data to 32 bytes.
-    #[command(visible_aliases = &["--to-bytes32", "tb", "2b"])]
-    ToBytes32 {
-        /// The hex data to convert.
-        bytes: Option<String>,
-    },
+    /// Calculate the ENS namehash of a name.
+    #[command(visible_aliases = &["na", "nh"])]
+    Namehash { name: Option<String> },
 
-    /// Convert an integer into a fixed point number.
-    #[command(visible_aliases = &["--to-fix", "tf", "2f"])]
-    ToFixedPoint {
-        /// The number of decimals to use.
-        decimals: Option<String>,
+    /// Get information about a transaction.
+    #[command(visible_alias = "t")]
+    Tx {
+        /// The transaction hash.
+        tx_hash: String,
 
-        /// The value to convert.
-        #[arg(allow_hyphen_values = true)]
-        value: Option<String>,
-    },
+        /// If specified, only get the given field of the transaction. If "raw", the RLP encoded
+        /// transaction will be printed.
+        field: Option<String>,
 
-    /// Convert a number to a hex-encoded uint256.
-    #[command(name = "to-uint256", visible_aliases = &["--to-uint256", "tu", "2u"])]
-    ToUint256 {
-        /// The value to convert.
-        value: Option<String>,
-    },
+        /// Print the raw RLP encoded transaction.
+        #[arg(long, conflicts_with = "field")]
+        raw: bool,
 
-    /// Convert a number to a hex-encoded int256.
-    #[command(name = "to-int256", visible_aliases = &["--to-int256", "ti", "2i"])]
-    ToInt256 {
-        /// The value to convert.
-        value: Option<String>,
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Perform a left shifting operation
-    #[command(name = "shl")]
-    LeftShift {
-        /// The value to shift.
-        value: String,
+    /// Get the transaction receipt for a transaction.
+    #[command(visible_alias = "re")]
+    Receipt {
+        /// The transaction hash.
+        tx_hash: String,
 
-        /// The number of bits to shift.
-        bits: String,
+        /// If specified, only get the given field of the transaction.
+        field: Option<String>,
 
-        /// The input base.
-        #[arg(long)]
-        base_in: Option<String>,
+        /// The number of confirmations until the receipt is fetched
+        #[arg(long, default_value = "1")]
+        confirmations: u64,
 
-        /// The output base.
-        #[arg(long, default_value = "16")]
-        base_out: String,
+        /// Exit immediately if the transaction was not found.
+        #[arg(id = "async", long = "async", env = "CAST_ASYNC", alias = "cast-async")]
+        cast_async: bool,
+
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Perform a right shifting operation
-    #[command(name = "shr")]
-    RightShift {
-        /// The value to shift.
-        value: String,
+    /// Sign and publish a transaction.
+    #[command(name = "send", visible_alias = "s")]
+    SendTx(SendTxArgs),
 
-        /// The number of bits to shift.
-        bits: String,
+    /// Publish a raw transaction to the network.
+    #[command(name = "publish", visible_alias = "p")]
+    PublishTx {
+        /// The raw transaction
+        raw_tx: String,
 
-        /// The input base,
-        #[arg(long)]
-        base_in: Option<String>,
+        /// Only print the transaction hash and exit immediately.
+        #[arg(id = "async", long = "async", env = "CAST_ASYNC", alias = "cast-async")]
+        cast_async: bool,
 
-        /// The output base,
-        #[arg(long, default_value = "16")]
-        base_out: String,
+        #[command(flatten)]
+        rpc: RpcOpts,
     },
 
-    /// Convert an ETH amount into another unit (ether, gwei or wei).
-    ///
-    /// Examples:
-    /// - 1ether wei
-    /// - "1 ether" wei
-    /// - 1ether
-    /// - 1 gwei
-    /// - 1gwei ether
-    #[command(visible_aliases = &["--to-unit", "tun", "2un"])]
-    ToUnit {
-        /// The value to convert.
-        value: Option<String>,
-
-        /// The unit to convert to (ether, gwei, wei).
-        #[arg(default_value = "wei")]
-        unit: String,
-    },
+    /// Estimate the gas cost of a transaction.
+    #[command(visible_alias = "e")]
+    Estimate(EstimateArgs),
 
-    /// Convert a number from decimal to smallest unit with arbitrary decimals.
+    /// Decode ABI-encoded input data.
     ///
-    /// Examples:
-    /// - 1.0 6    (for USDC, result: 1000000)
-    /// - 2.5 12   (for 12 decimals token, result: 2500000000000)
-    /// - 1.23 3   (for 3 decimals token, result: 1230)
-    #[command(visible_aliases = &["--parse-units", "pun"])]
-    ParseUnits {
-        /// The value to convert.
-        value: Option<String>,
+    /// Similar to `abi-decode --input`, but function selector MUST be prefixed in `calldata`
+    /// string
+    #[command(visible_aliases = &["calldata-decode", "--calldata-decode", "cdd"])]
+    DecodeCalldata {
+        /// The function signature in the format `<name>(<in-types>)(<out-types>)`.
+        sig: String,
 
-        /// The unit to convert to.
-        #[arg(default_value = "18")]
-        unit: u8,
+        /// The ABI-encoded calldata.
+        calldata: String,
     },
 
-    /// Format a number from smallest unit to decimal with arbitrary decimals.
+    /// Decode ABI-encoded string.
     ///
-    /// Examples:
-    /// - 1000000 6       (for USDC, result: 1.0)
-    /// - 2500000000000 12 (for 12 decimals, result: 2.5)
-    /// - 1230 3          (for 3 decimals, result: 1.23)
-    #[command(visible_aliases = &["--format-units", "fun"])]
-    FormatUnits {
-        /// The value to format.
-        value: Option<String>,
-
-        /// The unit to format to.
-        #[arg(default_value = "18")]
-        unit: u8,
+    /// Similar to `calldata-decode --input`, but the function argument is a `string`
+    #[command(visible_aliases = &["string-decode", "--string-decode", "sd"])]
+    DecodeString {
+        /// The ABI-encoded string.
+        data: String,
     },
 
-    /// Convert an ETH amount to wei.
-    ///
-    /// Consider using --to-unit.
-    #[command(visible_aliases = &["--to-wei", "tw", "2w"])]
-    ToWei {
-        /// The value to convert.
-        #[arg(allow_hyphen_values = true)]
-        value: Option<String>,
-
-        /// The unit to convert from (ether, gwei, wei).
-        #[arg(default_value = "eth")]
-        unit: String,
+    /// Decode event data.
+    #[command(visible_aliases = &["event-decode", "--event-decode", "ed"])]
+    DecodeEvent {
+        /// The event signature. If none provided then tries to decode from local cache or `https://api.openchain.xyz`.
+        #[arg(long, visible_alias = "event-sig")]
+        sig: Option<String>,
+        /// The event data to decode.
+        data: String,
     },
 
-    /// Convert wei into an ETH amount.
-    ///
-    /// Consider using --to-unit.
-    #[command(visible_aliases = &["--from-wei", "fw"])]
-    FromWei {
-        /// The value to convert.
-        #[arg(allow_hyphen_values = true)]
-        value: Option<String>,
-
-        /// The unit to convert from (ether, gwei, wei).
-        #[arg(default_value = "eth")]
-        unit: String,
+    /// Decode custom error data.
+    #[command(visible_aliases = &["error-decode", "--error-decode", "erd"])]
+    DecodeError {
+        /// The error signature. If none provided then tries to decode from local cache or `https://api.openchain.xyz`.
+        #[arg(long, visible_alias = "error-sig")]
+        sig: Option<String>,
+        /// The error data to decode.
+        data: String,
     },
 
-    /// RLP encodes hex data, or an array of hex data.
+    /// Decode ABI-encoded input or output data.
     ///
-    /// Accepts a hex-encoded string, or an array of hex-encoded strings.
-    /// Can be arbitrarily recursive.
+    /// Defaults to decoding output data. To decode input data pass --input.
     ///
-    /// Examples:
-    /// - `cast to-rlp "[]"` -> `0xc0`
-    /// - `cast to-rlp "0x22"` -> `0x22`
-    /// - `cast to-rlp "[\"0x61\"]"` -> `0xc161`
-    /// - `cast to-rlp "[\"0xf1\", \"f2\"]"` -> `0xc481f181f2`
-    #[command(visible_aliases = &["--to-rlp"])]
-    ToRlp {
-        /// The value to convert.
-        ///
-        /// This is a hex-encoded string, or an array of hex-encoded strings.
-        /// Can be arbitrarily recursive.
-        value: Option<String>,
+    /// When passing `--input`, function selector must NOT be prefixed in `calldata` string
+    #[command(name = "decode-abi", visible_aliases = &["abi-decode", "--abi-decode", "ad"])]
+    DecodeAbi {
+        /// The function signature in the format `<name>(<in-types>)(<out-types>)`.
+        sig: String,
+
+        /// The ABI-encoded calldata.
+        calldata: String,
+
+        /// Whether to decode the input or output data.
+        #[arg(long, short, help_heading = "Decode input data instead of output data")]
+        input: bool,
     },
 
-    /// Decodes RLP hex-encoded data.
-    #[command(visible_aliases = &["--from-rlp"])]
-    FromRlp {
-        /// The RLP hex-encoded data.
-        value: Option<String>,
+    /// ABI encode the given function argument, excluding the selector.
+    #[command(visible_alias = "ae")]
+    AbiEncode {
+        /// The function signature.
+        sig: String,
 
-        /// Decode the RLP data as int
-        #[arg(long, alias = "int")]
-        as_int: bool,
+        /// Whether to use packed encoding.
+        #[arg(long)]
+        packed: bool,
+
+        /// The arguments of the function.
+        #[arg(allow_hyphen_values = true)]
+        args: Vec<String>,
     },
 
-    /// Converts a number of one base to another
-    #[command(visible_aliases = &["--to-hex", "th", "2h"])]
-    ToHex(ToBaseArgs),
+    /// Compute the storage slot for an entry in a mapping.
+    #[command(visible_alias = "in")]
+    Index {
+        /// The mapping key type.
+        key_type: String,
 
-    /// Converts a number of one base to decimal
-    #[command(visible_aliases = &["--to-dec", "td", "2d"])]
-    ToDec(ToBaseArgs),
+        /// The mapping key.
+        key: String,
 
-    /// Converts a number of one base to another
-    #[command(
-        visible_aliases = &["--to-base",
-        "--to-radix",
-        "to-radix",
-        "tr",
-        "2r"]
-    )]
-    ToBase {
-        #[command(flatten)]
-        base: ToBaseArgs,
+        /// The storage slot of the mapping.
+        slot_number: String,
+    },
 
-        /// The output base.
-        #[arg(value_name = "BASE")]
-        base_out: Option<String>,
+    /// Find the storage slot holding a token balance or allowance.
+    ///
+    /// Traces the call, changes the storage slots it reads until its result changes, and recovers
+    /// the slot of the mapping and whether it is laid out by Solidity or Vyper.
+    #[command(visible_alias = "fsl")]
+    FindSlot(FindSlotArgs),
+
+    /// Compute storage slots as specified by `ERC-7201: Namespaced Storage Layout`.
+    #[command(name = "index-erc7201", alias = "index-erc-7201", visible_aliases = &["index7201", "in7201"])]
+    IndexErc7201 {
+        /// The arbitrary identifier.
+        id: Option<String>,
+        /// The formula ID. Currently the only supported formula is `erc7201`.
+        #[arg(long, default_value = "erc7201")]
+        formula_id: String,
     },
-    /// Create an access list for a transaction.
-    #[command(visible_aliases = &["ac", "acl"])]
-    AccessList(AccessListArgs),
-    /// Get logs by signature or topic.
-    #[command(visible_alias = "l")]
-    Logs(LogsArgs),
-    /// Get information about a block.
-    #[command(visible_alias = "bl")]
-    Block {
+
+    /// Fetch the EIP-1967 implementation for a contract
+    /// Can read from the implementation slot or the beacon slot.
+    #[command(visible_alias = "impl")]
+    Implementation {
         /// The block height to query at.
         ///
         /// Can also be the tags earliest, finalized, safe, latest, or pending.
+        #[arg(long, short = 'B')]
         block: Option<BlockId>,
 
-        /// If specified, only get the given field of the block.
-        #[arg(long, short)]
-        field: Option<String>,
+        /// Fetch the implementation from the beacon slot.
+        ///
+        /// If not specified, the implementation slot is used.
+        #[arg(long)]
+        beacon: bool,
 
-        #[arg(long, env = "CAST_FULL_BLOCK")]
-        full: bool,
+        /// The address for which the implementation will be fetched.
+        #[arg(value_parser = NameOrAddress::from_str)]
+        who: NameOrAddress,
 
         #[command(flatten)]
         rpc: RpcOpts,
     },
 
-    /// Get the latest block number.
-    #[command(visible_alias = "bn")]
-    BlockNumber {
-        /// The hash or tag to query. If not specified, the latest number is returned.
+    /// Fetch the EIP-1967 admin account
+    #[command(visible_alias = "adm")]
+    Admin {
+        /// The block height to query at.
+        ///
+        /// Can also be the tags earliest, finalized, safe, latest, or pending.
+        #[arg(long, short = 'B')]
         block: Option<BlockId>,
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Perform a call on an account without publishing a transaction.
-    #[command(visible_alias = "c")]
-    Call(CallArgs),
-
-    /// ABI-encode a function with arguments.
-    #[command(name = "calldata", visible_alias = "cd")]
-    CalldataEncode {
-        /// The function signature in the format `<name>(<in-types>)(<out-types>)`
-        sig: String,
 
-        /// The arguments to encode.
-        #[arg(allow_hyphen_values = true)]
-        args: Vec<String>,
-    },
-
-    /// Get the symbolic name of the current chain.
-    Chain {
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Get the Ethereum chain ID.
-    #[command(visible_aliases = &["ci", "cid"])]
-    ChainId {
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Get the current client version.
-    #[command(visible_alias = "cl")]
-    Client {
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Compute the contract address from a given nonce and deployer address.
-    #[command(visible_alias = "ca")]
-    ComputeAddress {
-        /// The deployer address.
-        address: Option<Address>,
-
-        /// The nonce of the deployer address.
-        #[arg(long)]
-        nonce: Option<u64>,
-
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Disassembles a hex-encoded bytecode into a human-readable representation.
-    #[command(visible_alias = "da")]
-    Disassemble {
-        /// The hex-encoded bytecode.
-        bytecode: Option<String>,
-    },
-
-    /// Build and sign a transaction.
-    #[command(name = "mktx", visible_alias = "m")]
-    MakeTx(MakeTxArgs),
-
-    /// Calculate the ENS namehash of a name.
-    #[command(visible_aliases = &["na", "nh"])]
-    Namehash { name: Option<String> },
-
-    /// Get information about a transaction.
-    #[command(visible_alias = "t")]
-    Tx {
-        /// The transaction hash.
-        tx_hash: String,
-
-        /// If specified, only get the given field of the transaction. If "raw", the RLP encoded
-        /// transaction will be printed.
-        field: Option<String>,
-
-        /// Print the raw RLP encoded transaction.
-        #[arg(long, conflicts_with = "field")]
-        raw: bool,
-
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Get the transaction receipt for a transaction.
-    #[command(visible_alias = "re")]
-    Receipt {
-        /// The transaction hash.
-        tx_hash: String,
-
-        /// If specified, only get the given field of the transaction.
-        field: Option<String>,
-
-        /// The number of confirmations until the receipt is fetched
-        #[arg(long, default_value = "1")]
-        confirmations: u64,
-
-        /// Exit immediately if the transaction was not found.
-        #[arg(id = "async", long = "async", env = "CAST_ASYNC", alias = "cast-async")]
-        cast_async: bool,
-
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Sign and publish a transaction.
-    #[command(name = "send", visible_alias = "s")]
-    SendTx(SendTxArgs),
-
-    /// Publish a raw transaction to the network.
-    #[command(name = "publish", visible_alias = "p")]
-    PublishTx {
-        /// The raw transaction
-        raw_tx: String,
-
-        /// Only print the transaction hash and exit immediately.
-        #[arg(id = "async", long = "async", env = "CAST_ASYNC", alias = "cast-async")]
-        cast_async: bool,
-
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Estimate the gas cost of a transaction.
-    #[command(visible_alias = "e")]
-    Estimate(EstimateArgs),
-
-    /// Decode ABI-encoded input data.
-    ///
-    /// Similar to `abi-decode --input`, but function selector MUST be prefixed in `calldata`
-    /// string
-    #[command(visible_aliases = &["calldata-decode", "--calldata-decode", "cdd"])]
-    DecodeCalldata {
-        /// The function signature in the format `<name>(<in-types>)(<out-types>)`.
-        sig: String,
-
-        /// The ABI-encoded calldata.
-        calldata: String,
-    },
-
-    /// Decode ABI-encoded string.
-    ///
-    /// Similar to `calldata-decode --input`, but the function argument is a `string`
-    #[command(visible_aliases = &["string-decode", "--string-decode", "sd"])]
-    DecodeString {
-        /// The ABI-encoded string.
-        data: String,
-    },
-
-    /// Decode event data.
-    #[command(visible_aliases = &["event-decode", "--event-decode", "ed"])]
-    DecodeEvent {
-        /// The event signature. If none provided then tries to decode from local cache or `https://api.openchain.xyz`.
-        #[arg(long, visible_alias = "event-sig")]
-        sig: Option<String>,
-        /// The event data to decode.
-        data: String,
-    },
-
-    /// Decode custom error data.
-    #[command(visible_aliases = &["error-decode", "--error-decode", "erd"])]
-    DecodeError {
-        /// The error signature. If none provided then tries to decode from local cache or `https://api.openchain.xyz`.
-        #[arg(long, visible_alias = "error-sig")]
-        sig: Option<String>,
-        /// The error data to decode.
-        data: String,
-    },
-
-    /// Decode ABI-encoded input or output data.
-    ///
-    /// Defaults to decoding output data. To decode input data pass --input.
-    ///
-    /// When passing `--input`, function selector must NOT be prefixed in `calldata` string
-    #[command(name = "decode-abi", visible_aliases = &["abi-decode", "--abi-decode", "ad"])]
-    DecodeAbi {
-        /// The function signature in the format `<name>(<in-types>)(<out-types>)`.
-        sig: String,
-
-        /// The ABI-encoded calldata.
-        calldata: String,
-
-        /// Whether to decode the input or output data.
-        #[arg(long, short, help_heading = "Decode input data instead of output data")]
-        input: bool,
-    },
-
-    /// ABI encode the given function argument, excluding the selector.
-    #[command(visible_alias = "ae")]
-    AbiEncode {
-        /// The function signature.
-        sig: String,
-
-        /// Whether to use packed encoding.
-        #[arg(long)]
-        packed: bool,
-
-        /// The arguments of the function.
-        #[arg(allow_hyphen_values = true)]
-        args: Vec<String>,
-    },
-
-    /// Compute the storage slot for an entry in a mapping.
-    #[command(visible_alias = "in")]
-    Index {
-        /// The mapping key type.
-        key_type: String,
-
-        /// The mapping key.
-        key: String,
-
-        /// The storage slot of the mapping.
-        slot_number: String,
-    },
-
-    /// Compute storage slots as specified by `ERC-7201: Namespaced Storage Layout`.
-    #[command(name = "index-erc7201", alias = "index-erc-7201", visible_aliases = &["index7201", "in7201"])]
-    IndexErc7201 {
-        /// The arbitrary identifier.
-        id: Option<String>,
-        /// The formula ID. Currently the only supported formula is `erc7201`.
-        #[arg(long, default_value = "erc7201")]
-        formula_id: String,
-    },
-
-    /// Fetch the EIP-1967 implementation for a contract
-    /// Can read from the implementation slot or the beacon slot.
-    #[command(visible_alias = "impl")]
-    Implementation {
-        /// The block height to query at.
-        ///
-        /// Can also be the tags earliest, finalized, safe, latest, or pending.
-        #[arg(long, short = 'B')]
-        block: Option<BlockId>,
-
-        /// Fetch the implementation from the beacon slot.
-        ///
-        /// If not specified, the implementation slot is used.
-        #[arg(long)]
-        beacon: bool,
-
-        /// The address for which the implementation will be fetched.
-        #[arg(value_parser = NameOrAddress::from_str)]
-        who: NameOrAddress,
-
-        #[command(flatten)]
-        rpc: RpcOpts,
-    },
-
-    /// Fetch the EIP-1967 admin account
-    #[command(visible_alias = "adm")]
-    Admin {
-        /// The block height to query at.
-        ///
-        /// Can also be the tags earliest, finalized, safe, latest, or pending.
-        #[arg(long, short = 'B')]
-        block: Option<BlockId>,
-
-        /// The address from which the admin account will be fetched.
-        #[arg(value_parser = NameOrAddress::from_str)]
-        who: NameOrAddress,
+        /// The address from which the admin account will be fetched.
+        #[arg(value_parser = NameOrAddress::from_str)]
+        who: NameOrAddress,
 
         #[command(flatten)]
         rpc: RpcOpts,
@@ -863,10 +578,19 @@ pub enum CastSubcommand {
         #[arg(long, short = 'B')]
         block: Option<BlockId>,
 
+        /// Verify the account and storage proofs against the state root of the block locally,
+        /// instead of printing them.
+        #[arg(long)]
+        verify: bool,
+
         #[command(flatten)]
         rpc: RpcOpts,
     },
 
+    /// Verify an `eth_getProof` response against a state root locally.
+    #[command(visible_alias = "vpr")]
+    VerifyProof(VerifyProofArgs),
+
     /// Get the nonce for an account.
     #[command(visible_alias = "n")]
     Nonce {
@@ -951,6 +675,14 @@ pub enum CastSubcommand {
         command: WalletSubcommands,
     },
 
+    /// Safe multisig transaction utilities.
+    ///
+    /// Builds, hashes and signs Safe transactions offline.
+    Safe {
+        #[command(subcommand)]
+        command: SafeSubcommands,
+    },
+
     /// Download a contract creation code from Etherscan and RPC.
     #[command(visible_alias = "cc")]
     CreationCode(CreationCodeArgs),
@@ -991,6 +723,13 @@ pub enum CastSubcommand {
     #[command(visible_alias = "f")]
     FindBlock(FindBlockArgs),
 
+    /// Find the first block where the result of a call changes or matches a predicate.
+    ///
+    /// Binary-searches the blocks between `--from` and `--to`, then prints the transactions of the
+    /// found block which touched the contract.
+    #[command(visible_alias = "bs")]
+    Bisect(BisectArgs),
+
     /// Generate shell completions script.
     #[command(visible_alias = "com")]
     Completions {
@@ -1006,6 +745,13 @@ pub enum CastSubcommand {
     #[command(visible_alias = "r")]
     Run(RunArgs),
 
+    /// Executes two transactions, or the same call at two blocks, and diffs their decoded traces.
+    ///
+    /// Subcalls are aligned by their target and function, and their arguments, return values,
+    /// emitted events and gas usage are compared.
+    #[command(visible_alias = "trd")]
+    TraceDiff(TraceDiffArgs),
+
     /// Perform a raw JSON-RPC request.
     #[command(visible_alias = "rp")]
     Rpc(RpcArgs),
@@ -1050,6 +796,322 @@ pub enum CastSubcommand {
     DecodeEof { eof: Option<String> },
 }
 
+/// The constants and conversions of `cast`, flattened into [`CastSubcommand`].
+///
+/// They are kept in their own enum so that the derived parser of each enum fits in the stack of a
+/// test thread in debug builds.
+#[derive(Subcommand)]
+pub enum ConvertSubcommand {
+    /// Prints the maximum value of the given integer type.
+    #[command(visible_aliases = &["--max-int", "maxi"])]
+    MaxInt {
+        /// The integer type to get the maximum value of.
+        #[arg(default_value = "int256")]
+        r#type: String,
+    },
+
+    /// Prints the minimum value of the given integer type.
+    #[command(visible_aliases = &["--min-int", "mini"])]
+    MinInt {
+        /// The integer type to get the minimum value of.
+        #[arg(default_value = "int256")]
+        r#type: String,
+    },
+
+    /// Prints the maximum value of the given integer type.
+    #[command(visible_aliases = &["--max-uint", "maxu"])]
+    MaxUint {
+        /// The unsigned integer type to get the maximum value of.
+        #[arg(default_value = "uint256")]
+        r#type: String,
+    },
+
+    /// Prints the zero address.
+    #[command(visible_aliases = &["--address-zero", "az"])]
+    AddressZero,
+
+    /// Prints the zero hash.
+    #[command(visible_aliases = &["--hash-zero", "hz"])]
+    HashZero,
+
+    /// Convert UTF8 text to hex.
+    #[command(
+        visible_aliases = &[
+        "--from-ascii",
+        "--from-utf8",
+        "from-ascii",
+        "fu",
+        "fa"]
+    )]
+    FromUtf8 {
+        /// The text to convert.
+        text: Option<String>,
+    },
+
+    /// Concatenate hex strings.
+    #[command(visible_aliases = &["--concat-hex", "ch"])]
+    ConcatHex {
+        /// The data to concatenate.
+        data: Vec<String>,
+    },
+
+    /// Convert binary data into hex data.
+    #[command(visible_aliases = &["--from-bin", "from-binx", "fb"])]
+    FromBin,
+
+    /// Normalize the input to lowercase, 0x-prefixed hex.
+    ///
+    /// The input can be:
+    /// - mixed case hex with or without 0x prefix
+    /// - 0x prefixed hex, concatenated with a ':'
+    /// - an absolute path to file
+    /// - @tag, where the tag is defined in an environment variable
+    #[command(visible_aliases = &["--to-hexdata", "thd", "2hd"])]
+    ToHexdata {
+        /// The input to normalize.
+        input: Option<String>,
+    },
+
+    /// Convert an address to a checksummed format (EIP-55).
+    #[command(
+        visible_aliases = &["--to-checksum-address",
+        "--to-checksum",
+        "to-checksum",
+        "ta",
+        "2a"]
+    )]
+    ToCheckSumAddress {
+        /// The address to convert.
+        address: Option<Address>,
+    },
+
+    /// Convert hex data to an ASCII string.
+    #[command(visible_aliases = &["--to-ascii", "tas", "2as"])]
+    ToAscii {
+        /// The hex data to convert.
+        hexdata: Option<String>,
+    },
+
+    /// Convert hex data to a utf-8 string.
+    #[command(visible_aliases = &["--to-utf8", "tu8", "2u8"])]
+    ToUtf8 {
+        /// The hex data to convert.
+        hexdata: Option<String>,
+    },
+
+    /// Convert a fixed point number into an integer.
+    #[command(visible_aliases = &["--from-fix", "ff"])]
+    FromFixedPoint {
+        /// The number of decimals to use.
+        decimals: Option<String>,
+
+        /// The value to convert.
+        #[arg(allow_hyphen_values = true)]
+        value: Option<String>,
+    },
+
+    /// Right-pads hex data to 32 bytes.
+    #[command(visible_aliases = &["--to-bytes32", "tb", "2b"])]
+    ToBytes32 {
+        /// The hex data to convert.
+        bytes: Option<String>,
+    },
+
+    /// Convert an integer into a fixed point number.
+    #[command(visible_aliases = &["--to-fix", "tf", "2f"])]
+    ToFixedPoint {
+        /// The number of decimals to use.
+        decimals: Option<String>,
+
+        /// The value to convert.
+        #[arg(allow_hyphen_values = true)]
+        value: Option<String>,
+    },
+
+    /// Convert a number to a hex-encoded uint256.
+    #[command(name = "to-uint256", visible_aliases = &["--to-uint256", "tu", "2u"])]
+    ToUint256 {
+        /// The value to convert.
+        value: Option<String>,
+    },
+
+    /// Convert a number to a hex-encoded int256.
+    #[command(name = "to-int256", visible_aliases = &["--to-int256", "ti", "2i"])]
+    ToInt256 {
+        /// The value to convert.
+        value: Option<String>,
+    },
+
+    /// Perform a left shifting operation
+    #[command(name = "shl")]
+    LeftShift {
+        /// The value to shift.
+        value: String,
+
+        /// The number of bits to shift.
+        bits: String,
+
+        /// The input base.
+        #[arg(long)]
+        base_in: Option<String>,
+
+        /// The output base.
+        #[arg(long, default_value = "16")]
+        base_out: String,
+    },
+
+    /// Perform a right shifting operation
+    #[command(name = "shr")]
+    RightShift {
+        /// The value to shift.
+        value: String,
+
+        /// The number of bits to shift.
+        bits: String,
+
+        /// The input base,
+        #[arg(long)]
+        base_in: Option<String>,
+
+        /// The output base,
+        #[arg(long, default_value = "16")]
+        base_out: String,
+    },
+
+    /// Convert an ETH amount into another unit (ether, gwei or wei).
+    ///
+    /// Examples:
+    /// - 1ether wei
+    /// - "1 ether" wei
+    /// - 1ether
+    /// - 1 gwei
+    /// - 1gwei ether
+    #[command(visible_aliases = &["--to-unit", "tun", "2un"])]
+    ToUnit {
+        /// The value to convert.
+        value: Option<String>,
+
+        /// The unit to convert to (ether, gwei, wei).
+        #[arg(default_value = "wei")]
+        unit: String,
+    },
+
+    /// Convert a number from decimal to smallest unit with arbitrary decimals.
+    ///
+    /// Examples:
+    /// - 1.0 6    (for USDC, result: 1000000)
+    /// - 2.5 12   (for 12 decimals token, result: 2500000000000)
+    /// - 1.23 3   (for 3 decimals token, result: 1230)
+    #[command(visible_aliases = &["--parse-units", "pun"])]
+    ParseUnits {
+        /// The value to convert.
+        value: Option<String>,
+
+        /// The unit to convert to.
+        #[arg(default_value = "18")]
+        unit: u8,
+    },
+
+    /// Format a number from smallest unit to decimal with arbitrary decimals.
+    ///
+    /// Examples:
+    /// - 1000000 6       (for USDC, result: 1.0)
+    /// - 2500000000000 12 (for 12 decimals, result: 2.5)
+    /// - 1230 3          (for 3 decimals, result: 1.23)
+    #[command(visible_aliases = &["--format-units", "fun"])]
+    FormatUnits {
+        /// The value to format.
+        value: Option<String>,
+
+        /// The unit to format to.
+        #[arg(default_value = "18")]
+        unit: u8,
+    },
+
+    /// Convert an ETH amount to wei.
+    ///
+    /// Consider using --to-unit.
+    #[command(visible_aliases = &["--to-wei", "tw", "2w"])]
+    ToWei {
+        /// The value to convert.
+        #[arg(allow_hyphen_values = true)]
+        value: Option<String>,
+
+        /// The unit to convert from (ether, gwei, wei).
+        #[arg(default_value = "eth")]
+        unit: String,
+    },
+
+    /// Convert wei into an ETH amount.
+    ///
+    /// Consider using --to-unit.
+    #[command(visible_aliases = &["--from-wei", "fw"])]
+    FromWei {
+        /// The value to convert.
+        #[arg(allow_hyphen_values = true)]
+        value: Option<String>,
+
+        /// The unit to convert from (ether, gwei, wei).
+        #[arg(default_value = "eth")]
+        unit: String,
+    },
+
+    /// RLP encodes hex data, or an array of hex data.
+    ///
+    /// Accepts a hex-encoded string, or an array of hex-encoded strings.
+    /// Can be arbitrarily recursive.
+    ///
+    /// Examples:
+    /// - `cast to-rlp "[]"` -> `0xc0`
+    /// - `cast to-rlp "0x22"` -> `0x22`
+    /// - `cast to-rlp "[\"0x61\"]"` -> `0xc161`
+    /// - `cast to-rlp "[\"0xf1\", \"f2\"]"` -> `0xc481f181f2`
+    #[command(visible_aliases = &["--to-rlp"])]
+    ToRlp {
+        /// The value to convert.
+        ///
+        /// This is a hex-encoded string, or an array of hex-encoded strings.
+        /// Can be arbitrarily recursive.
+        value: Option<String>,
+    },
+
+    /// Decodes RLP hex-encoded data.
+    #[command(visible_aliases = &["--from-rlp"])]
+    FromRlp {
+        /// The RLP hex-encoded data.
+        value: Option<String>,
+
+        /// Decode the RLP data as int
+        #[arg(long, alias = "int")]
+        as_int: bool,
+    },
+
+    /// Converts a number of one base to another
+    #[command(visible_aliases = &["--to-hex", "th", "2h"])]
+    ToHex(ToBaseArgs),
+
+    /// Converts a number of one base to decimal
+    #[command(visible_aliases = &["--to-dec", "td", "2d"])]
+    ToDec(ToBaseArgs),
+
+    /// Converts a number of one base to another
+    #[command(
+        visible_aliases = &["--to-base",
+        "--to-radix",
+        "to-radix",
+        "tr",
+        "2r"]
+    )]
+    ToBase {
+        #[command(flatten)]
+        base: ToBaseArgs,
+
+        /// The output base.
+        #[arg(value_name = "BASE")]
+        base_out: Option<String>,
+    },
+}
+
 /// CLI arguments for `cast --to-base`.
 #[derive(Debug, Parser)]
 pub struct ToBaseArgs {
@@ -0,0 +1,298 @@
+use alloy_consensus::Transaction;
+use alloy_network::{AnyNetwork, TransactionBuilder, TransactionResponse};
+use alloy_primitives::{Address, TxHash, I256, U256};
+use alloy_provider::{ext::TraceApi, Provider};
+use alloy_rpc_types::{
+    trace::parity::{Action, TraceOutput},
+    BlockId, BlockTransactionsKind, TransactionRequest,
+};
+use alloy_serde::WithOtherFields;
+use alloy_transport::{Transport, TransportError};
+use cast::Cast;
+use clap::Parser;
+use eyre::Result;
+use foundry_cli::{opts::RpcOpts, utils};
+use foundry_common::{
+    abi::{encode_function_args, get_func},
+    ens::NameOrAddress,
+    shell,
+};
+use foundry_config::Config;
+use serde::Serialize;
+use std::{cmp::Ordering, str::FromStr};
+
+/// CLI arguments for `cast bisect`.
+#[derive(Clone, Debug, Parser)]
+pub struct BisectArgs {
+    /// The address of the contract to call.
+    #[arg(value_parser = NameOrAddress::from_str)]
+    to: NameOrAddress,
+
+    /// The signature of the view function to call, with its return types.
+    ///
+    /// Example: "balanceOf(address)(uint256)"
+    sig: String,
+
+    /// The arguments of the function to call.
+    #[arg(allow_negative_numbers = true)]
+    args: Vec<String>,
+
+    /// The block to start searching from.
+    #[arg(long, value_name = "BLOCK")]
+    from: u64,
+
+    /// The block to stop searching at.
+    ///
+    /// Defaults to the latest block.
+    #[arg(long = "to", id = "to_block", value_name = "BLOCK")]
+    to_block: Option<u64>,
+
+    /// Search for the first block where the first returned value matches this predicate, instead
+    /// of the first block where the returned values change.
+    ///
+    /// The predicate is an operator among `==`, `!=`, `<`, `<=`, `>` and `>=`, followed by a
+    /// value. Numbers are compared numerically, other values as strings.
+    ///
+    /// Example: ">= 1000000"
+    #[arg(long, value_name = "EXPR", allow_hyphen_values = true)]
+    until: Option<Predicate>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+/// The result of a bisection.
+#[derive(Debug, Serialize)]
+struct BisectReport {
+    /// The first block where the call result changed or matched the predicate.
+    block: u64,
+    /// The result of the call at the previous block.
+    before: String,
+    /// The result of the call at `block`.
+    after: String,
+    /// The transactions of `block` which touched the contract.
+    transactions: Vec<TxHash>,
+}
+
+impl BisectArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { to, sig, args, from, to_block, until, rpc } = self;
+
+        let config = Config::from(&rpc);
+        let provider = utils::get_provider(&config)?;
+        let address = to.resolve(&provider).await?;
+
+        let func = get_func(&sig)?;
+        let input = encode_function_args(&func, &args)?;
+        let tx =
+            WithOtherFields::new(TransactionRequest::default().with_to(address).with_input(input));
+
+        let latest = provider.get_block_number().await?;
+        let to_block = to_block.unwrap_or(latest).min(latest);
+        if from >= to_block {
+            eyre::bail!("--from block {from} must be lower than --to block {to_block}");
+        }
+
+        let cast = Cast::new(provider.clone());
+        let call = |block: u64| {
+            let (cast, tx, func) = (&cast, &tx, &func);
+            async move {
+                match cast.call(tx, Some(func), Some(BlockId::number(block))).await {
+                    Ok(result) => Ok(result),
+                    // A revert is a result of the call, e.g. before the contract is initialized.
+                    Err(err) => revert_message(&err).ok_or(err),
+                }
+            }
+        };
+
+        let first = call(from).await?;
+        let initial = first.clone();
+        let matches = |value: &str| match &until {
+            Some(predicate) => predicate.matches(value),
+            None => Ok(value != initial),
+        };
+        if matches(&first)? {
+            eyre::bail!("the call result already matches at block {from}: {first}");
+        }
+        let last = call(to_block).await?;
+        if !matches(&last)? {
+            eyre::bail!("the call result doesn't change until block {to_block}: {last}");
+        }
+
+        // Invariant: the result doesn't match at `low` and matches at `high`.
+        let (mut low, mut high) = (from, to_block);
+        let (mut before, mut after) = (first, last);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            let value = call(mid).await?;
+            if matches(&value)? {
+                (high, after) = (mid, value);
+            } else {
+                (low, before) = (mid, value);
+            }
+        }
+
+        let transactions = touching_transactions(&provider, high, address).await?;
+        let report = BisectReport { block: high, before, after, transactions };
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string_pretty(&report)?)?;
+            return Ok(());
+        }
+
+        sh_println!("Block {}", report.block)?;
+        sh_println!("Before: {}", report.before)?;
+        sh_println!("After: {}", report.after)?;
+        if report.transactions.is_empty() {
+            sh_println!("No transaction of the block touched {address}")?;
+        } else {
+            sh_println!("Transactions touching {address}:")?;
+            for tx in report.transactions {
+                sh_println!("{tx}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the message of a call error if the call reverted.
+fn revert_message(err: &eyre::Report) -> Option<String> {
+    let payload = err.downcast_ref::<TransportError>()?.as_error_resp()?;
+    // Nodes return the error code 3 for reverts with data, but not all of them for reverts
+    // without data.
+    (payload.code == 3 || payload.message.contains("revert")).then(|| payload.message.to_string())
+}
+
+/// Returns the transactions of `block` which called or created `address`, including through
+/// internal calls.
+///
+/// Internal calls are found with `trace_block`. If the node doesn't support it, only the
+/// transactions sent to `address` are returned.
+async fn touching_transactions<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+    provider: &P,
+    block: u64,
+    address: Address,
+) -> Result<Vec<TxHash>> {
+    let mut transactions = Vec::<TxHash>::new();
+    match provider.trace_block(BlockId::number(block)).await {
+        Ok(traces) => {
+            for trace in traces {
+                let touched = match &trace.trace.action {
+                    Action::Call(call) => call.to == address || call.from == address,
+                    Action::Create(create) => {
+                        create.from == address ||
+                            matches!(
+                                &trace.trace.result,
+                                Some(TraceOutput::Create(output)) if output.address == address
+                            )
+                    }
+                    Action::Selfdestruct(selfdestruct) => selfdestruct.address == address,
+                    Action::Reward(_) => false,
+                };
+                if let Some(hash) = trace.transaction_hash.filter(|_| touched) {
+                    if !transactions.contains(&hash) {
+                        transactions.push(hash);
+                    }
+                }
+            }
+        }
+        Err(err) => {
+            sh_warn!("trace_block is not supported, only listing direct calls: {err}")?;
+            let block = provider
+                .get_block(block.into(), BlockTransactionsKind::Full)
+                .await?
+                .ok_or_else(|| eyre::eyre!("block {block} not found"))?;
+            for tx in block.transactions.txns() {
+                if tx.to() == Some(address) {
+                    transactions.push(tx.tx_hash());
+                }
+            }
+        }
+    }
+    Ok(transactions)
+}
+
+/// A predicate on the first value returned by a call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Predicate {
+    op: &'static str,
+    value: String,
+}
+
+impl FromStr for Predicate {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let op = ["==", "!=", "<=", ">=", "<", ">"]
+            .into_iter()
+            .find(|op| s.starts_with(op))
+            .ok_or_else(|| eyre::eyre!("predicate must start with ==, !=, <, <=, > or >=: {s}"))?;
+        let value = s[op.len()..].trim();
+        if value.is_empty() {
+            eyre::bail!("missing value in predicate: {s}");
+        }
+        Ok(Self { op, value: value.to_string() })
+    }
+}
+
+impl Predicate {
+    /// Returns whether the first value of a formatted call result matches the predicate.
+    fn matches(&self, result: &str) -> Result<bool> {
+        // Numbers are formatted with their scientific notation, e.g. `1000000 [1e6]`.
+        let actual = result.lines().next().unwrap_or_default();
+        let actual = actual.split(" [").next().unwrap_or_default().trim();
+
+        let ordering = if let (Ok(a), Ok(b)) = (U256::from_str(actual), U256::from_str(&self.value))
+        {
+            a.cmp(&b)
+        } else if let (Ok(a), Ok(b)) = (I256::from_str(actual), I256::from_str(&self.value)) {
+            a.cmp(&b)
+        } else {
+            match self.op {
+                "==" => return Ok(actual.eq_ignore_ascii_case(&self.value)),
+                "!=" => return Ok(!actual.eq_ignore_ascii_case(&self.value)),
+                _ => eyre::bail!("cannot compare non-numeric value `{actual}` with {}", self.op),
+            }
+        };
+
+        Ok(match self.op {
+            "==" => ordering == Ordering::Equal,
+            "!=" => ordering != Ordering::Equal,
+            "<" => ordering == Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            _ => ordering != Ordering::Less,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_match_predicates() {
+        let predicate: Predicate = ">= 1000000".parse().unwrap();
+        assert!(predicate.matches("1000000 [1e6]").unwrap());
+        assert!(!predicate.matches("999999 [9.999e5]").unwrap());
+
+        let predicate: Predicate = "<-1".parse().unwrap();
+        assert!(predicate.matches("-5").unwrap());
+        assert!(!predicate.matches("0").unwrap());
+
+        let predicate: Predicate = "== 0x000000000000000000000000000000000000dEaD".parse().unwrap();
+        assert!(predicate.matches("0x000000000000000000000000000000000000dEaD").unwrap());
+
+        let predicate: Predicate = "!=true".parse().unwrap();
+        assert!(predicate.matches("false").unwrap());
+        assert!(!predicate.matches("true").unwrap());
+
+        let predicate: Predicate = "> 1".parse().unwrap();
+        assert!(predicate.matches("true").is_err());
+
+        assert!("1000".parse::<Predicate>().is_err());
+        assert!(">=".parse::<Predicate>().is_err());
+    }
+}
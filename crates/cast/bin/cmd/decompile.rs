@@ -0,0 +1,76 @@
+use alloy_primitives::{hex, map::HashMap, Address};
+use alloy_rpc_types::BlockId;
+use cast::{decompile::Decompiler, traces::identifier::SignaturesIdentifier, Cast};
+use clap::Parser;
+use eyre::Result;
+use foundry_cli::{opts::RpcOpts, utils};
+use foundry_common::stdin;
+use foundry_config::Config;
+use std::str::FromStr;
+
+/// CLI arguments for `cast decompile`.
+#[derive(Clone, Debug, Parser)]
+pub struct DecompileArgs {
+    /// The hex-encoded runtime bytecode, or the address of a contract whose code will be fetched.
+    target: Option<String>,
+
+    /// The block height to query at, if an address is given.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[arg(long, short = 'B')]
+    block: Option<BlockId>,
+
+    /// Output the reachable code as assembly annotated with jump targets, selectors and storage
+    /// slots, instead of pseudo-Solidity.
+    #[arg(long)]
+    asm: bool,
+
+    /// Only resolve function signatures from the local signature cache.
+    #[arg(long)]
+    offline: bool,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+impl DecompileArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { target, block, asm, offline, rpc } = self;
+
+        let target = stdin::unwrap_line(target)?;
+        let code = match Address::from_str(&target) {
+            Ok(address) => {
+                let config = Config::from(&rpc);
+                let provider = utils::get_provider(&config)?;
+                let code = Cast::new(provider).code(address, block, false).await?;
+                hex::decode(code)?
+            }
+            Err(_) => hex::decode(target)?,
+        };
+        if code.is_empty() {
+            eyre::bail!("no bytecode to decompile");
+        }
+
+        let decompiler = Decompiler::new(&code)?;
+
+        let selectors = decompiler.selectors().collect::<Vec<_>>();
+        let identified = SignaturesIdentifier::new(Config::foundry_cache_dir(), offline)?
+            .write()
+            .await
+            .identify_functions(&selectors)
+            .await;
+        let signatures = selectors
+            .into_iter()
+            .zip(identified)
+            .filter_map(|(selector, func)| Some((selector, func?.signature())))
+            .collect::<HashMap<_, _>>();
+
+        if asm {
+            sh_println!("{}", decompiler.to_asm(&signatures))?;
+        } else {
+            sh_print!("{}", decompiler.to_solidity(&signatures))?;
+        }
+
+        Ok(())
+    }
+}
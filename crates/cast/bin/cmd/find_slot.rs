@@ -0,0 +1,182 @@
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, BlockNumberOrTag, BlockTransactionsKind};
+use alloy_sol_types::{sol, SolCall};
+use cast::{
+    mapping_slot::{keccak_preimages, storage_reads, MappingSlot},
+    revm::DatabaseRef,
+};
+use clap::{Parser, ValueEnum};
+use eyre::{OptionExt, Result};
+use foundry_cli::{opts::RpcOpts, utils};
+use foundry_common::{ens::NameOrAddress, shell};
+use foundry_config::{figment::Figment, Config};
+use foundry_evm::{
+    executors::{RawCallResult, TracingExecutor},
+    opts::EvmOpts,
+    traces::TraceMode,
+};
+use serde::Serialize;
+use std::str::FromStr;
+
+sol! {
+    interface IToken {
+        function balanceOf(address owner) external view returns (uint256);
+        function allowance(address owner, address spender) external view returns (uint256);
+    }
+}
+
+/// The value written to candidate slots to check whether they hold the result of the call.
+const PERTURBATION: U256 = U256::from_limbs([0x1337, 0, 0, 0]);
+
+/// A token function reading a mapping.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TokenFunction {
+    /// `balanceOf(owner)`
+    #[value(name = "balanceOf", alias = "balance-of")]
+    BalanceOf,
+    /// `allowance(owner, spender)`
+    #[value(name = "allowance")]
+    Allowance,
+}
+
+/// CLI arguments for `cast find-slot`.
+#[derive(Clone, Debug, Parser)]
+pub struct FindSlotArgs {
+    /// The address of the token.
+    #[arg(value_parser = NameOrAddress::from_str)]
+    token: NameOrAddress,
+
+    /// The function reading the slot.
+    function: TokenFunction,
+
+    /// The arguments of the function: the holder for `balanceOf`, the owner and the spender for
+    /// `allowance`.
+    #[arg(value_parser = NameOrAddress::from_str, num_args = 1..=2, required = true)]
+    accounts: Vec<NameOrAddress>,
+
+    /// The block height to query at.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending, or a block hash.
+    #[arg(long, short = 'B')]
+    block: Option<BlockId>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+/// The slot found by `cast find-slot`.
+#[derive(Debug, Serialize)]
+struct FoundSlot {
+    /// The account whose storage holds the value.
+    address: Address,
+    slot: B256,
+    /// The mapping the slot belongs to, if it could be recovered.
+    mapping: Option<MappingSlot>,
+}
+
+impl FindSlotArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { token, function, accounts, block, rpc } = self;
+
+        let figment = Into::<Figment>::into(&rpc);
+        let evm_opts = figment.extract::<EvmOpts>()?;
+        let mut config = Config::try_from(figment)?.sanitized();
+        let provider = utils::get_provider(&config)?;
+
+        let token = token.resolve(&provider).await?;
+        let mut keys = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            keys.push(account.resolve(&provider).await?);
+        }
+        let calldata: Bytes = match (function, keys.as_slice()) {
+            (TokenFunction::BalanceOf, &[owner]) => IToken::balanceOfCall { owner }.abi_encode(),
+            (TokenFunction::Allowance, &[owner, spender]) => {
+                IToken::allowanceCall { owner, spender }.abi_encode()
+            }
+            (TokenFunction::BalanceOf, _) => eyre::bail!("balanceOf takes a single holder"),
+            (TokenFunction::Allowance, _) => eyre::bail!("allowance takes an owner and a spender"),
+        }
+        .into();
+        let keys = keys.iter().map(Address::into_word).collect::<Vec<_>>();
+
+        // The fork is pinned to a block number, which tags and hashes are resolved to.
+        config.fork_block_number = match block {
+            Some(BlockId::Number(BlockNumberOrTag::Number(number))) => Some(number),
+            Some(block) => Some(
+                provider
+                    .get_block(block, BlockTransactionsKind::Hashes)
+                    .await?
+                    .ok_or_eyre(format!("block {block} not found"))?
+                    .header
+                    .number,
+            ),
+            None => config.fork_block_number,
+        };
+        let create2_deployer = evm_opts.create2_deployer;
+        let (env, fork, _, odyssey) = TracingExecutor::get_fork_material(&config, evm_opts).await?;
+        let mut executor =
+            TracingExecutor::new(env, fork, None, TraceMode::Debug, odyssey, create2_deployer);
+
+        let call = |executor: &TracingExecutor| -> Result<RawCallResult> {
+            executor.call_raw(Address::ZERO, token, calldata.clone(), U256::ZERO)
+        };
+        let result = call(&executor)?;
+        if result.reverted {
+            eyre::bail!("the call to {token} reverted: {}", result.result);
+        }
+        let arena = result.traces.as_ref().ok_or_else(|| eyre::eyre!("no traces were recorded"))?;
+        let reads = storage_reads(arena);
+        let preimages = keccak_preimages(arena);
+
+        // The value is usually read last, e.g. after the implementation slot of a proxy.
+        let mut found = None;
+        for read in reads.iter().rev() {
+            let slot = U256::from_be_bytes(read.slot.0);
+            let value = executor.backend().storage_ref(read.address, slot)?;
+            let perturbed =
+                if value == PERTURBATION { PERTURBATION + U256::from(1) } else { PERTURBATION };
+            executor.backend_mut().insert_account_storage(read.address, slot, perturbed)?;
+            let perturbed_result = call(&executor)?;
+            executor.backend_mut().insert_account_storage(read.address, slot, value)?;
+
+            if !perturbed_result.reverted && perturbed_result.result != result.result {
+                found = Some(FoundSlot {
+                    address: read.address,
+                    slot: read.slot,
+                    mapping: MappingSlot::resolve(read.slot, &keys, &preimages),
+                });
+                break;
+            }
+        }
+        let Some(found) = found else {
+            eyre::bail!(
+                "none of the {} storage slots read by the call holds its result",
+                reads.len()
+            );
+        };
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string_pretty(&found)?)?;
+            return Ok(());
+        }
+
+        sh_println!("Slot: {}", found.slot)?;
+        if found.address != token {
+            sh_println!("Storage of: {}", found.address)?;
+        }
+        match found.mapping {
+            Some(mapping) => {
+                let slot = U256::from_be_bytes(mapping.slot.0);
+                match u64::try_from(slot) {
+                    Ok(slot) => sh_println!("Mapping slot: {slot}")?,
+                    Err(_) => sh_println!("Mapping slot: {}", mapping.slot)?,
+                }
+                sh_println!("Layout: {}", mapping.layout)?;
+            }
+            None => sh_println!("The mapping slot could not be recovered")?,
+        }
+
+        Ok(())
+    }
+}
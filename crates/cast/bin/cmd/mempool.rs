@@ -0,0 +1,250 @@
+use alloy_consensus::Transaction;
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::Function;
+use alloy_network::{AnyNetwork, AnyRpcTransaction, TransactionResponse};
+use alloy_primitives::{map::HashSet, Address, Selector, B256};
+use alloy_provider::{ext::TxPoolApi, Provider, ProviderBuilder};
+use cast::traces::identifier::{SignaturesIdentifier, SingleSignaturesIdentifier};
+use clap::Parser;
+use eyre::Result;
+use foundry_cli::{opts::RpcOpts, utils};
+use foundry_common::{
+    abi::get_func, compile::ProjectCompiler, ens::NameOrAddress, fmt::format_tokens, shell,
+};
+use foundry_config::Config;
+use futures::StreamExt;
+use serde_json::json;
+use std::{collections::BTreeMap, str::FromStr, time::Duration};
+use tokio::signal::ctrl_c;
+
+/// CLI arguments for `cast mempool`.
+#[derive(Clone, Debug, Parser)]
+pub struct MempoolArgs {
+    /// Only show transactions sent to one of these addresses.
+    #[arg(long, value_parser = NameOrAddress::from_str)]
+    to: Vec<NameOrAddress>,
+
+    /// Only show transactions sent by one of these addresses.
+    #[arg(long, value_parser = NameOrAddress::from_str)]
+    from: Vec<NameOrAddress>,
+
+    /// Only show transactions calling one of these functions, given as selectors or signatures.
+    #[arg(long = "selector", value_name = "SELECTOR_OR_SIG", value_parser = parse_selector)]
+    selectors: Vec<Selector>,
+
+    /// Poll `txpool_content` instead of subscribing to pending transactions.
+    ///
+    /// Subscriptions require a WebSocket or IPC endpoint, and are used by default with them.
+    #[arg(long)]
+    poll: bool,
+
+    /// The interval between polls, in milliseconds.
+    #[arg(long, value_name = "MILLIS", default_value = "1000")]
+    interval: u64,
+
+    /// Exit after showing this many transactions.
+    #[arg(long, short = 'n', value_name = "COUNT")]
+    count: Option<usize>,
+
+    /// Decode calldata with the ABIs of the local project's artifacts, in addition to the
+    /// signature database.
+    #[arg(long)]
+    with_local_artifacts: bool,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+impl MempoolArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { to, from, selectors, poll, interval, count, with_local_artifacts, rpc } = self;
+
+        let config = Config::from(&rpc);
+        let provider = utils::get_provider(&config)?;
+
+        let mut filter = MempoolFilter { selectors, ..Default::default() };
+        for to in to {
+            filter.to.insert(to.resolve(&provider).await?);
+        }
+        for from in from {
+            filter.from.insert(from.resolve(&provider).await?);
+        }
+
+        let mut watcher = MempoolWatcher {
+            filter,
+            decoder: CalldataDecoder::new(&config, with_local_artifacts)?,
+            count,
+            shown: 0,
+        };
+
+        let url = config.get_rpc_url_or_localhost_http()?;
+        let is_pubsub = !url.starts_with("http");
+        if poll || !is_pubsub {
+            let interval = Duration::from_millis(interval);
+            let mut seen = HashSet::<B256>::default();
+            loop {
+                let content = provider.txpool_content().await?;
+                let pending = content.pending.values().flat_map(BTreeMap::values);
+                let mut still_pending = HashSet::<B256>::default();
+                for tx in pending {
+                    still_pending.insert(tx.tx_hash());
+                    if !seen.contains(&tx.tx_hash()) && watcher.show(tx).await? {
+                        return Ok(());
+                    }
+                }
+                seen = still_pending;
+
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = ctrl_c() => return Ok(()),
+                }
+            }
+        }
+
+        // Subscribe with the builtin transport, as in `cast logs --subscribe`.
+        let provider =
+            ProviderBuilder::<_, _, AnyNetwork>::default().on_builtin(url.as_ref()).await?;
+        let mut stream = provider.subscribe_full_pending_transactions().await?.into_stream();
+        loop {
+            tokio::select! {
+                tx = stream.next() => {
+                    let Some(tx) = tx else { break };
+                    if watcher.show(&tx).await? {
+                        break;
+                    }
+                }
+                _ = ctrl_c() => break,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_selector(s: &str) -> Result<Selector> {
+    match Selector::from_str(s) {
+        Ok(selector) => Ok(selector),
+        Err(_) => Ok(get_func(s)?.selector()),
+    }
+}
+
+/// The transactions shown by `cast mempool`. Empty sets match any transaction.
+#[derive(Debug, Default)]
+struct MempoolFilter {
+    to: HashSet<Address>,
+    from: HashSet<Address>,
+    selectors: Vec<Selector>,
+}
+
+impl MempoolFilter {
+    fn matches(&self, tx: &AnyRpcTransaction) -> bool {
+        (self.to.is_empty() || tx.to().is_some_and(|to| self.to.contains(&to))) &&
+            (self.from.is_empty() || self.from.contains(&tx.from())) &&
+            (self.selectors.is_empty() ||
+                tx.input().get(..4).is_some_and(|selector| {
+                    self.selectors.iter().any(|s| s.as_slice() == selector)
+                }))
+    }
+}
+
+/// Decodes calldata with the local artifacts, falling back to the signature database.
+struct CalldataDecoder {
+    functions: BTreeMap<Selector, Function>,
+    identifier: SingleSignaturesIdentifier,
+}
+
+impl CalldataDecoder {
+    fn new(config: &Config, with_local_artifacts: bool) -> Result<Self> {
+        let functions = if with_local_artifacts {
+            let project = config.project()?;
+            let output = ProjectCompiler::new().quiet(shell::is_json()).compile(&project)?;
+            output
+                .artifacts()
+                .filter_map(|(_, artifact)| artifact.abi.as_ref())
+                .flat_map(|abi| abi.functions())
+                .map(|func| (func.selector(), func.clone()))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        let identifier = SignaturesIdentifier::new(Config::foundry_cache_dir(), config.offline)?;
+        Ok(Self { functions, identifier })
+    }
+
+    async fn decode(&mut self, input: &[u8]) -> Option<(Function, Vec<DynSolValue>)> {
+        let selector = Selector::from_slice(input.get(..4)?);
+        let func = match self.functions.get(&selector) {
+            Some(func) => func.clone(),
+            None => {
+                let func =
+                    self.identifier.write().await.identify_function(selector.as_slice()).await?;
+                self.functions.insert(selector, func.clone());
+                func
+            }
+        };
+        let args = func.abi_decode_input(&input[4..], false).ok()?;
+        Some((func, args))
+    }
+}
+
+struct MempoolWatcher {
+    filter: MempoolFilter,
+    decoder: CalldataDecoder,
+    count: Option<usize>,
+    shown: usize,
+}
+
+impl MempoolWatcher {
+    /// Shows a pending transaction if it matches the filter, returning `true` once enough
+    /// transactions were shown.
+    async fn show(&mut self, tx: &AnyRpcTransaction) -> Result<bool> {
+        if !self.filter.matches(tx) {
+            return Ok(false);
+        }
+
+        let decoded = self.decoder.decode(tx.input()).await;
+        if shell::is_json() {
+            let (function, args) = match &decoded {
+                Some((func, args)) => {
+                    (Some(func.signature()), Some(format_tokens(args).collect::<Vec<_>>()))
+                }
+                None => (None, None),
+            };
+            sh_println!(
+                "{}",
+                json!({
+                    "hash": tx.tx_hash(),
+                    "from": tx.from(),
+                    "to": tx.to(),
+                    "nonce": tx.nonce(),
+                    "value": tx.value(),
+                    "input": tx.input(),
+                    "function": function,
+                    "args": args,
+                })
+            )?;
+        } else {
+            sh_println!("- hash: {}", tx.tx_hash())?;
+            sh_println!("  from: {}", tx.from())?;
+            match tx.to() {
+                Some(to) => sh_println!("  to: {to}")?,
+                None => sh_println!("  to: contract creation")?,
+            }
+            sh_println!("  nonce: {}", tx.nonce())?;
+            sh_println!("  value: {}", tx.value())?;
+            match decoded {
+                Some((func, args)) => {
+                    sh_println!("  function: {}", func.signature())?;
+                    for arg in format_tokens(&args) {
+                        sh_println!("    {arg}")?;
+                    }
+                }
+                None if !tx.input().is_empty() => sh_println!("  input: {}", tx.input())?,
+                None => {}
+            }
+        }
+
+        self.shown += 1;
+        Ok(self.count.is_some_and(|count| self.shown >= count))
+    }
+}
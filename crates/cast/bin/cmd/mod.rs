@@ -8,17 +8,24 @@
 pub mod access_list;
 pub mod artifact;
 pub mod bind;
+pub mod bisect;
 pub mod call;
 pub mod constructor_args;
 pub mod create2;
 pub mod creation_code;
+pub mod decompile;
 pub mod estimate;
 pub mod find_block;
+pub mod find_slot;
 pub mod interface;
 pub mod logs;
+pub mod mempool;
 pub mod mktx;
 pub mod rpc;
 pub mod run;
+pub mod safe;
 pub mod send;
 pub mod storage;
+pub mod trace_diff;
+pub mod verify_proof;
 pub mod wallet;
@@ -1,31 +1,46 @@
 use alloy_consensus::Transaction;
-use alloy_network::TransactionResponse;
-use alloy_primitives::U256;
+use alloy_network::{AnyRpcBlock, AnyRpcTransaction, TransactionResponse};
+use alloy_primitives::{map::HashMap, Address, Bytes, TxHash, U256};
 use alloy_provider::Provider;
 use alloy_rpc_types::BlockTransactions;
-use cast::revm::primitives::EnvWithHandlerCfg;
-use clap::Parser;
-use eyre::{Result, WrapErr};
+use cast::{
+    revm::{
+        primitives::{Env, EnvWithHandlerCfg},
+        DatabaseRef,
+    },
+    state_diff::StateDiff,
+};
+use clap::{Parser, ValueHint};
+use eyre::{OptionExt, Result, WrapErr};
+use foundry_block_explorers::Client;
 use foundry_cli::{
     opts::{EtherscanOpts, RpcOpts},
-    utils::{handle_traces, init_progress, TraceResult},
+    utils::{build_trace_decoder, handle_traces, init_progress, print_traces, TraceResult},
+};
+use foundry_common::{
+    compile::ProjectCompiler, is_known_system_sender, shell, SYSTEM_TRANSACTION_TYPE,
 };
-use foundry_common::{is_known_system_sender, shell, SYSTEM_TRANSACTION_TYPE};
-use foundry_compilers::artifacts::EvmVersion;
+use foundry_compilers::{artifacts::EvmVersion, Artifact};
 use foundry_config::{
     figment::{
         self,
         value::{Dict, Map},
         Figment, Metadata, Profile,
     },
-    Config,
+    Chain, Config,
 };
 use foundry_evm::{
     executors::{EvmError, TracingExecutor},
     opts::EvmOpts,
-    traces::{InternalTraceMode, TraceMode},
+    traces::{folded_stack_trace, DebugTraceIdentifier, InternalTraceMode, TraceKind, TraceMode},
     utils::configure_tx_env,
 };
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::storage::{add_storage_layout_output, fetch_etherscan_artifact};
 
 /// CLI arguments for `cast run`.
 #[derive(Clone, Debug, Parser)]
@@ -92,6 +107,29 @@ pub struct RunArgs {
     /// Use current project artifacts for trace decoding.
     #[arg(long, visible_alias = "la")]
     pub with_local_artifacts: bool,
+
+    /// Print the balance, nonce, code and storage changes of every account touched by the
+    /// transaction.
+    ///
+    /// Storage slots are decoded into variables with the storage layouts of the current project
+    /// artifacts if `--with-local-artifacts` is set, or of the contracts verified on Etherscan.
+    #[arg(long)]
+    pub state_diff: bool,
+
+    /// Write a gas flamegraph of the transaction to an SVG file. Implies `--decode-internal`.
+    ///
+    /// The width of each frame is the gas used by a call or an internal function, including its
+    /// callees. Internal functions are only shown if their source maps are available, from the
+    /// current project artifacts with `--with-local-artifacts` or from Etherscan.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath, conflicts_with = "debug")]
+    pub flamegraph: Option<PathBuf>,
+
+    /// Write the folded stacks of the transaction to a file. Implies `--decode-internal`.
+    ///
+    /// Each line is a stack of calls and internal functions followed by the gas used by its last
+    /// frame itself, excluding its callees.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath, conflicts_with = "debug")]
+    pub folded: Option<PathBuf>,
 }
 
 impl RunArgs {
@@ -100,7 +138,13 @@ impl RunArgs {
     /// This replays the entire block the transaction was mined in unless `quick` is set to true
     ///
     /// Note: This executes the transaction(s) as is: Cheatcodes are disabled
-    pub async fn run(self) -> Result<()> {
+    pub async fn run(mut self) -> Result<()> {
+        let profile = self.flamegraph.is_some() || self.folded.is_some();
+        // Internal functions are needed for a meaningful flamegraph.
+        if profile {
+            self.decode_internal = true;
+        }
+
         let figment = Into::<Figment>::into(&self.rpc).merge(&self);
         let evm_opts = figment.extract::<EvmOpts>()?;
         let mut config = Config::try_from(figment)?.sanitized();
@@ -146,21 +190,7 @@ pub async fn run(self) -> Result<()> {
         env.block.number = U256::from(tx_block_number);
 
         if let Some(block) = &block {
-            env.block.timestamp = U256::from(block.header.timestamp);
-            env.block.coinbase = block.header.beneficiary;
-            env.block.difficulty = block.header.difficulty;
-            env.block.prevrandao = Some(block.header.mix_hash.unwrap_or_default());
-            env.block.basefee = U256::from(block.header.base_fee_per_gas.unwrap_or_default());
-            env.block.gas_limit = U256::from(block.header.gas_limit);
-
-            // TODO: we need a smarter way to map the block to the corresponding evm_version for
-            // commonly used chains
-            if evm_version.is_none() {
-                // if the block has the excess_blob_gas field, we assume it's a Cancun block
-                if block.header.excess_blob_gas.is_some() {
-                    evm_version = Some(EvmVersion::Cancun);
-                }
-            }
+            configure_block_env(&mut env, block, &mut evm_version);
         }
 
         let trace_mode = TraceMode::Call
@@ -189,69 +219,50 @@ pub async fn run(self) -> Result<()> {
             }
 
             if let Some(block) = block {
-                let pb = init_progress(block.transactions.len() as u64, "tx");
-                pb.set_position(0);
-
                 let BlockTransactions::Full(ref txs) = block.transactions else {
                     return Err(eyre::eyre!("Could not get block txs"))
                 };
-
-                for (index, tx) in txs.iter().enumerate() {
-                    // System transactions such as on L2s don't contain any pricing info so
-                    // we skip them otherwise this would cause
-                    // reverts
-                    if is_known_system_sender(tx.from) ||
-                        tx.transaction_type() == Some(SYSTEM_TRANSACTION_TYPE)
-                    {
-                        pb.set_position((index + 1) as u64);
-                        continue;
-                    }
-                    if tx.tx_hash() == tx_hash {
-                        break;
-                    }
-
-                    configure_tx_env(&mut env, &tx.inner);
-
-                    if let Some(to) = Transaction::to(tx) {
-                        trace!(tx=?tx.tx_hash(),?to, "executing previous call transaction");
-                        executor.transact_with_env(env.clone()).wrap_err_with(|| {
-                            format!(
-                                "Failed to execute transaction: {:?} in block {}",
-                                tx.tx_hash(),
-                                env.block.number
-                            )
-                        })?;
-                    } else {
-                        trace!(tx=?tx.tx_hash(), "executing previous create transaction");
-                        if let Err(error) = executor.deploy_with_env(env.clone(), None) {
-                            match error {
-                                // Reverted transactions should be skipped
-                                EvmError::Execution(_) => (),
-                                error => {
-                                    return Err(error).wrap_err_with(|| {
-                                        format!(
-                                            "Failed to deploy transaction: {:?} in block {}",
-                                            tx.tx_hash(),
-                                            env.block.number
-                                        )
-                                    })
-                                }
-                            }
-                        }
-                    }
-
-                    pb.set_position((index + 1) as u64);
-                }
+                replay_previous_transactions(&mut executor, &mut env, txs, tx_hash)?;
             }
         }
 
         // Execute our transaction
+        let mut state_diff = None;
         let result = {
             executor.set_trace_printer(self.trace_printer);
 
             configure_tx_env(&mut env, &tx.inner);
 
-            if let Some(to) = Transaction::to(&tx) {
+            if self.state_diff {
+                // The transaction is not committed, so that its changes can be diffed against the
+                // state it was executed on.
+                trace!(tx=?tx.tx_hash(), "executing transaction for state diff");
+                let kind = if Transaction::to(&tx).is_some() {
+                    TraceKind::Execution
+                } else {
+                    TraceKind::Deployment
+                };
+                let raw = executor.call_with_env(env)?;
+                let diff = StateDiff::new(executor.backend(), &raw.state_changeset)?;
+                let codes = diff
+                    .0
+                    .keys()
+                    .filter_map(|address| {
+                        let info = &raw.state_changeset.get(address)?.info;
+                        let code = match &info.code {
+                            Some(code) => code.original_bytes(),
+                            None => executor
+                                .backend()
+                                .code_by_hash_ref(info.code_hash)
+                                .ok()?
+                                .original_bytes(),
+                        };
+                        (!code.is_empty()).then_some((*address, code))
+                    })
+                    .collect();
+                state_diff = Some((diff, codes));
+                TraceResult::from_raw(raw, kind)
+            } else if let Some(to) = Transaction::to(&tx) {
                 trace!(tx=?tx.tx_hash(), to=?to, "executing call transaction");
                 TraceResult::try_from(executor.transact_with_env(env))?
             } else {
@@ -260,21 +271,218 @@ pub async fn run(self) -> Result<()> {
             }
         };
 
-        handle_traces(
-            result,
-            &config,
-            chain,
-            self.label,
-            self.with_local_artifacts,
-            self.debug,
-            self.decode_internal,
-        )
-        .await?;
+        if profile {
+            let mut result = result;
+            let (mut decoder, sources) = build_trace_decoder(
+                result.traces.iter().flatten().map(|(_, arena)| arena),
+                &config,
+                chain,
+                &self.label,
+                self.with_local_artifacts,
+                true,
+            )
+            .await?;
+            decoder.debug_identifier = Some(DebugTraceIdentifier::new(sources));
+            // Also decodes the traces for the folded stacks.
+            print_traces(&mut result, &decoder, shell::verbosity() > 0, shell::verbosity() > 4)
+                .await?;
+
+            let arena = result
+                .traces
+                .as_ref()
+                .and_then(|traces| traces.first())
+                .map(|(_, arena)| arena)
+                .ok_or_eyre("no traces were recorded")?;
+            let folded = folded_stack_trace::build(arena);
+            if let Some(path) = &self.folded {
+                fs::write(path, folded.join("\n") + "\n")
+                    .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+                sh_println!("Folded stacks saved to {}", path.display())?;
+            }
+            if let Some(path) = &self.flamegraph {
+                write_flamegraph(&folded, path, &format!("flamegraph {tx_hash}"))?;
+                sh_println!("Flamegraph saved to {}", path.display())?;
+            }
+        } else {
+            handle_traces(
+                result,
+                &config,
+                chain,
+                self.label,
+                self.with_local_artifacts,
+                self.debug,
+                self.decode_internal,
+            )
+            .await?;
+        }
+
+        if let Some((mut state_diff, codes)) = state_diff {
+            decode_state_diff(&mut state_diff, &codes, &config, chain, self.with_local_artifacts)
+                .await?;
+            if shell::is_json() {
+                sh_println!("{}", serde_json::to_string(&state_diff)?)?;
+            } else {
+                sh_println!("State diff:")?;
+                sh_println!("{state_diff}")?;
+            }
+        }
 
         Ok(())
     }
 }
 
+/// Decodes the storage changes of `state_diff` with the storage layouts of the current project
+/// artifacts if `with_local_artifacts` is set, or of the contracts verified on Etherscan.
+///
+/// Contracts are matched against local artifacts by their deployed bytecode.
+async fn decode_state_diff(
+    state_diff: &mut StateDiff,
+    codes: &HashMap<Address, Bytes>,
+    config: &Config,
+    chain: Option<Chain>,
+    with_local_artifacts: bool,
+) -> Result<()> {
+    let accounts = state_diff
+        .0
+        .iter_mut()
+        .filter(|(address, diff)| !diff.storage.is_empty() && codes.contains_key(*address));
+
+    if with_local_artifacts {
+        let mut project = config.project()?;
+        add_storage_layout_output(&mut project);
+        let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+        for (address, diff) in accounts {
+            let artifact = output.artifact_ids().find(|(_, artifact)| {
+                artifact.get_deployed_bytecode_bytes().is_some_and(|code| *code == codes[address])
+            });
+            if let Some((id, artifact)) = artifact {
+                diff.contract = Some(id.name);
+                if let Some(layout) = &artifact.storage_layout {
+                    diff.decode_storage(layout);
+                }
+            }
+        }
+    } else if let Some(api_key) = config.get_etherscan_api_key(chain) {
+        let client = Client::new(chain.unwrap_or_default(), api_key)?;
+        for (address, diff) in accounts {
+            match fetch_etherscan_artifact(client.clone(), *address).await {
+                Ok((name, artifact)) => {
+                    diff.contract = Some(name);
+                    if let Some(layout) = &artifact.storage_layout {
+                        diff.decode_storage(layout);
+                    }
+                }
+                Err(err) => {
+                    sh_warn!("Could not decode the storage of {address}: {err}")?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Renders folded stacks as a gas flamegraph in an SVG file.
+fn write_flamegraph(folded: &[String], path: &Path, title: &str) -> Result<()> {
+    let file =
+        fs::File::create(path).wrap_err_with(|| format!("failed to create {}", path.display()))?;
+
+    let mut options = inferno::flamegraph::Options::default();
+    options.title = title.to_string();
+    options.count_name = "gas".to_string();
+    inferno::flamegraph::from_lines(
+        &mut options,
+        folded.iter().map(String::as_str),
+        io::BufWriter::new(file),
+    )
+    .wrap_err("failed to write svg")
+}
+
+/// Sets the block environment of `env` to the one of `block`.
+///
+/// If `evm_version` is not set, it is inferred from the block header.
+pub(crate) fn configure_block_env(
+    env: &mut Env,
+    block: &AnyRpcBlock,
+    evm_version: &mut Option<EvmVersion>,
+) {
+    env.block.timestamp = U256::from(block.header.timestamp);
+    env.block.coinbase = block.header.beneficiary;
+    env.block.difficulty = block.header.difficulty;
+    env.block.prevrandao = Some(block.header.mix_hash.unwrap_or_default());
+    env.block.basefee = U256::from(block.header.base_fee_per_gas.unwrap_or_default());
+    env.block.gas_limit = U256::from(block.header.gas_limit);
+
+    // TODO: we need a smarter way to map the block to the corresponding evm_version for
+    // commonly used chains
+    if evm_version.is_none() {
+        // if the block has the excess_blob_gas field, we assume it's a Cancun block
+        if block.header.excess_blob_gas.is_some() {
+            *evm_version = Some(EvmVersion::Cancun);
+        }
+    }
+}
+
+/// Executes and commits the transactions of a block which precede `tx_hash`, so that the state
+/// is the one right before `tx_hash` was executed.
+pub(crate) fn replay_previous_transactions(
+    executor: &mut TracingExecutor,
+    env: &mut EnvWithHandlerCfg,
+    txs: &[AnyRpcTransaction],
+    tx_hash: TxHash,
+) -> Result<()> {
+    let pb = init_progress(txs.len() as u64, "tx");
+    pb.set_position(0);
+
+    for (index, tx) in txs.iter().enumerate() {
+        // System transactions such as on L2s don't contain any pricing info so
+        // we skip them otherwise this would cause
+        // reverts
+        if is_known_system_sender(tx.from) || tx.transaction_type() == Some(SYSTEM_TRANSACTION_TYPE)
+        {
+            pb.set_position((index + 1) as u64);
+            continue;
+        }
+        if tx.tx_hash() == tx_hash {
+            break;
+        }
+
+        configure_tx_env(env, &tx.inner);
+
+        if let Some(to) = Transaction::to(tx) {
+            trace!(tx=?tx.tx_hash(),?to, "executing previous call transaction");
+            executor.transact_with_env(env.clone()).wrap_err_with(|| {
+                format!(
+                    "Failed to execute transaction: {:?} in block {}",
+                    tx.tx_hash(),
+                    env.block.number
+                )
+            })?;
+        } else {
+            trace!(tx=?tx.tx_hash(), "executing previous create transaction");
+            if let Err(error) = executor.deploy_with_env(env.clone(), None) {
+                match error {
+                    // Reverted transactions should be skipped
+                    EvmError::Execution(_) => (),
+                    error => {
+                        return Err(error).wrap_err_with(|| {
+                            format!(
+                                "Failed to deploy transaction: {:?} in block {}",
+                                tx.tx_hash(),
+                                env.block.number
+                            )
+                        })
+                    }
+                }
+            }
+        }
+
+        pb.set_position((index + 1) as u64);
+    }
+
+    Ok(())
+}
+
 impl figment::Provider for RunArgs {
     fn metadata(&self) -> Metadata {
         Metadata::named("RunArgs")
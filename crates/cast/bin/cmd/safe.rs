@@ -0,0 +1,212 @@
+use alloy_chains::Chain;
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_signer::Signer;
+use alloy_sol_types::Eip712Domain;
+use cast::safe::{
+    combine_signatures, encode_signature, multi_send_calldata, MultiSendTx, Operation, SafeTx,
+};
+use clap::Parser;
+use eyre::Result;
+use foundry_cli::utils::parse_ether_value;
+use foundry_common::{sh_println, shell};
+use foundry_wallets::WalletOpts;
+use semver::Version;
+use serde_json::json;
+use std::str::FromStr;
+
+/// CLI arguments for `cast safe`.
+#[derive(Debug, Parser)]
+#[allow(clippy::large_enum_variant)]
+pub enum SafeSubcommands {
+    /// Compute the `safeTxHash` of a Safe transaction, which is signed by the owners.
+    #[command(visible_alias = "h")]
+    Hash {
+        #[command(flatten)]
+        tx: SafeTxArgs,
+    },
+
+    /// Sign a Safe transaction with the EIP-712 domain of the Safe.
+    #[command(visible_alias = "s")]
+    Sign {
+        #[command(flatten)]
+        tx: SafeTxArgs,
+
+        #[command(flatten)]
+        wallet: WalletOpts,
+    },
+
+    /// Combine the owner signatures of a Safe transaction and encode the `execTransaction`
+    /// calldata executing it.
+    ///
+    /// Signatures are sorted by owner, as required by the Safe.
+    #[command(visible_alias = "ec")]
+    ExecCalldata {
+        #[command(flatten)]
+        tx: SafeTxArgs,
+
+        /// An owner signature of the transaction, as returned by `cast safe sign`.
+        ///
+        /// Signatures made with `eth_sign` (`v` + 4) and pre-approved hashes (`v` = 1) are also
+        /// accepted.
+        #[arg(
+            long = "signature",
+            value_name = "SIGNATURE",
+            value_parser = Bytes::from_str,
+            required = true
+        )]
+        signatures: Vec<Bytes>,
+    },
+
+    /// Encode the `multiSend` calldata of a batch of transactions.
+    ///
+    /// The Safe transaction must delegatecall a `MultiSend` or `MultiSendCallOnly` contract with
+    /// this calldata, e.g. with `--operation delegatecall`.
+    #[command(visible_alias = "ms")]
+    MultiSend {
+        /// The transactions of the batch, formatted as `TO[,VALUE[,DATA[,OPERATION]]]`.
+        ///
+        /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045,1ether,0x
+        #[arg(value_parser = parse_multi_send_tx, required = true)]
+        txs: Vec<MultiSendTx>,
+    },
+}
+
+/// The fields of a Safe transaction.
+#[derive(Clone, Debug, Parser)]
+pub struct SafeTxArgs {
+    /// The address of the Safe.
+    #[arg(long, value_name = "ADDRESS")]
+    safe: Address,
+
+    /// The chain of the Safe.
+    #[arg(long, alias = "chain-id")]
+    chain: Chain,
+
+    /// The nonce of the Safe transaction.
+    #[arg(long)]
+    nonce: u64,
+
+    /// The version of the Safe contract.
+    ///
+    /// Safes before version 1.3.0 don't include the chain in their EIP-712 domain.
+    #[arg(long, value_name = "VERSION", default_value = "1.3.0")]
+    safe_version: Version,
+
+    /// The destination of the transaction.
+    #[arg(long, value_name = "ADDRESS")]
+    to: Address,
+
+    /// Ether to send in the transaction.
+    ///
+    /// Either specified in wei, or as a string with a unit type.
+    ///
+    /// Examples: 1ether, 10gwei, 0.01ether
+    #[arg(long, value_parser = parse_ether_value)]
+    value: Option<U256>,
+
+    /// The calldata of the transaction.
+    #[arg(long, value_parser = Bytes::from_str)]
+    data: Option<Bytes>,
+
+    /// The operation of the transaction: `call` or `delegatecall`.
+    #[arg(long, value_parser = Operation::from_str, default_value = "call")]
+    operation: Operation,
+
+    /// The gas the Safe must forward to the transaction.
+    #[arg(long, default_value = "0")]
+    safe_tx_gas: U256,
+
+    /// The gas costs independent of the transaction execution, which are refunded.
+    #[arg(long, default_value = "0")]
+    base_gas: U256,
+
+    /// The gas price used for the refund.
+    #[arg(long, default_value = "0")]
+    gas_price: U256,
+
+    /// The token used for the refund, or ether if not set.
+    #[arg(long, value_name = "ADDRESS")]
+    gas_token: Option<Address>,
+
+    /// The receiver of the refund, or `tx.origin` if not set.
+    #[arg(long, value_name = "ADDRESS")]
+    refund_receiver: Option<Address>,
+}
+
+impl SafeTxArgs {
+    fn safe_tx(&self) -> SafeTx {
+        SafeTx {
+            to: self.to,
+            value: self.value.unwrap_or_default(),
+            data: self.data.clone().unwrap_or_default(),
+            operation: self.operation.into(),
+            safeTxGas: self.safe_tx_gas,
+            baseGas: self.base_gas,
+            gasPrice: self.gas_price,
+            gasToken: self.gas_token.unwrap_or_default(),
+            refundReceiver: self.refund_receiver.unwrap_or_default(),
+            nonce: U256::from(self.nonce),
+        }
+    }
+
+    fn domain(&self) -> Eip712Domain {
+        let legacy = self.safe_version < Version::new(1, 3, 0);
+        SafeTx::domain(self.safe, self.chain.id(), legacy)
+    }
+}
+
+impl SafeSubcommands {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Self::Hash { tx } => {
+                let hash = tx.safe_tx().hash(&tx.domain());
+                if shell::is_json() {
+                    let domain_separator = tx.domain().separator();
+                    sh_println!(
+                        "{}",
+                        json!({ "safeTxHash": hash, "domainSeparator": domain_separator })
+                    )?;
+                } else {
+                    sh_println!("{hash}")?;
+                }
+            }
+            Self::Sign { tx, wallet } => {
+                let signer = wallet.signer().await?;
+                let signature = signer.sign_typed_data(&tx.safe_tx(), &tx.domain()).await?;
+                let signature = encode_signature(&signature);
+                if shell::is_json() {
+                    sh_println!(
+                        "{}",
+                        json!({ "signer": signer.address(), "signature": signature })
+                    )?;
+                } else {
+                    sh_println!("{signature}")?;
+                }
+            }
+            Self::ExecCalldata { tx, signatures } => {
+                let safe_tx = tx.safe_tx();
+                let signatures = combine_signatures(safe_tx.hash(&tx.domain()), &signatures)?;
+                sh_println!("{}", safe_tx.exec_calldata(signatures))?;
+            }
+            Self::MultiSend { txs } => {
+                sh_println!("{}", multi_send_calldata(&txs))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_multi_send_tx(s: &str) -> Result<MultiSendTx> {
+    let mut parts = s.split(',').map(str::trim);
+    let to = parts.next().unwrap_or_default();
+    let tx = MultiSendTx {
+        to: to.parse().map_err(|_| eyre::eyre!("invalid address `{to}`"))?,
+        value: parts.next().map(parse_ether_value).transpose()?.unwrap_or_default(),
+        data: parts.next().map(Bytes::from_str).transpose()?.unwrap_or_default(),
+        operation: parts.next().map(Operation::from_str).transpose()?.unwrap_or_default(),
+    };
+    if parts.next().is_some() {
+        eyre::bail!("expected `TO[,VALUE[,DATA[,OPERATION]]]`, got `{s}`");
+    }
+    Ok(tx)
+}
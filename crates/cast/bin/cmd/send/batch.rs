@@ -0,0 +1,577 @@
+use crate::tx::CastTxBuilder;
+use alloy_network::{AnyNetwork, ReceiptResponse};
+use alloy_primitives::{Address, TxHash, U64};
+use alloy_provider::Provider;
+use alloy_rpc_types::TransactionRequest;
+use alloy_serde::WithOtherFields;
+use alloy_transport::Transport;
+use clap::{builder::RangedU64ValueParser, Parser};
+use eyre::{Result, WrapErr};
+use foundry_cli::{opts::TransactionOpts, utils::parse_ether_value};
+use foundry_common::{ens::NameOrAddress, fs, shell};
+use foundry_config::Config;
+use futures::{stream::FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// Options of `cast send --batch`.
+#[derive(Clone, Debug, Parser)]
+#[command(next_help_heading = "Batch options")]
+pub struct BatchOpts {
+    /// The maximum number of transactions waiting to be mined at once.
+    #[arg(
+        long,
+        default_value = "1",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        requires = "batch"
+    )]
+    pub concurrency: usize,
+
+    /// The journal recording the transactions sent, used to resume an interrupted batch.
+    ///
+    /// Defaults to the batch file with a `.journal.json` extension.
+    #[arg(long, value_name = "PATH", requires = "batch")]
+    pub journal: Option<PathBuf>,
+
+    /// The file the receipts of the batch are written to.
+    ///
+    /// Defaults to the batch file with a `.report.json` extension.
+    #[arg(long, value_name = "PATH", requires = "batch")]
+    pub report: Option<PathBuf>,
+
+    /// Replace a transaction with one paying higher fees if it is not mined after this many
+    /// seconds.
+    #[arg(long, value_name = "SECONDS", requires = "batch")]
+    pub bump_after: Option<u64>,
+
+    /// The percentage the fees are raised by when replacing a transaction.
+    #[arg(long, value_name = "PERCENT", default_value = "10", requires = "batch")]
+    pub bump_percent: u64,
+
+    /// The maximum number of times a transaction is replaced.
+    #[arg(long, default_value = "3", requires = "batch")]
+    pub max_bumps: usize,
+}
+
+/// A transaction of a batch file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRow {
+    /// The destination of the transaction.
+    pub to: String,
+    /// The signature of the function to call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sig: Option<String>,
+    /// The arguments of the function to call.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    /// Ether to send in the transaction, in wei or with a unit type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+/// A row of a CSV batch file, whose arguments are separated by whitespace.
+#[derive(Deserialize)]
+struct CsvRow {
+    to: String,
+    #[serde(default)]
+    sig: Option<String>,
+    #[serde(default)]
+    args: Option<String>,
+    #[serde(default)]
+    value: Option<String>,
+}
+
+impl From<CsvRow> for BatchRow {
+    fn from(row: CsvRow) -> Self {
+        Self {
+            to: row.to,
+            sig: row.sig,
+            args: row
+                .args
+                .iter()
+                .flat_map(|args| args.split_whitespace())
+                .map(Into::into)
+                .collect(),
+            value: row.value,
+        }
+    }
+}
+
+/// Reads the transactions of a CSV or JSON batch file.
+pub fn read_batch(path: &Path) -> Result<Vec<BatchRow>> {
+    let content = fs::read_to_string(path)?;
+    let rows = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&content)?,
+        Some("csv") => parse_csv(&content)?,
+        _ => {
+            eyre::bail!("unsupported batch file {}, expected a .csv or .json file", path.display())
+        }
+    };
+    for (index, row) in rows.iter().enumerate() {
+        row.validate().wrap_err_with(|| format!("invalid row {index} of the batch"))?;
+    }
+    Ok(rows)
+}
+
+fn parse_csv(content: &str) -> Result<Vec<BatchRow>> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(content.as_bytes());
+    reader
+        .deserialize::<CsvRow>()
+        .map(|row| Ok(BatchRow::from(row?)))
+        .collect::<Result<_>>()
+        .wrap_err("failed to parse the CSV batch file")
+}
+
+impl BatchRow {
+    fn validate(&self) -> Result<()> {
+        if self.to.is_empty() {
+            eyre::bail!("missing recipient");
+        }
+        if self.sig.is_none() && !self.args.is_empty() {
+            eyre::bail!("arguments given without a function signature");
+        }
+        if let Some(value) = &self.value {
+            parse_ether_value(value)?;
+        }
+        Ok(())
+    }
+}
+
+/// The receipt of a batch transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReceipt {
+    pub transaction_hash: TxHash,
+    pub status: bool,
+    pub block_number: u64,
+    pub gas_used: u64,
+    pub effective_gas_price: u128,
+}
+
+/// The progress of a batch, saved after every transaction sent or mined.
+#[derive(Debug, Serialize, Deserialize)]
+struct Journal {
+    chain: u64,
+    sender: Address,
+    /// The nonce of the first row of the batch.
+    nonce: u64,
+    entries: BTreeMap<usize, JournalEntry>,
+}
+
+/// A row of the batch which was sent.
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    row: BatchRow,
+    /// The last transaction sent for the row.
+    tx: WithOtherFields<TransactionRequest>,
+    /// The hashes of the transactions sent for the row, one more per fee bump.
+    hashes: Vec<TxHash>,
+    receipt: Option<BatchReceipt>,
+}
+
+impl Journal {
+    /// Loads the journal of an interrupted batch, checking that the rows it sent are unchanged.
+    fn load(path: &Path, chain: u64, sender: Address, rows: &[BatchRow]) -> Result<Self> {
+        let journal: Self = fs::read_json_file(path)?;
+        if journal.chain != chain || journal.sender != sender {
+            eyre::bail!(
+                "the journal {} was written by {} on chain {}",
+                path.display(),
+                journal.sender,
+                journal.chain
+            );
+        }
+        for (index, entry) in &journal.entries {
+            if rows.get(*index) != Some(&entry.row) {
+                eyre::bail!(
+                    "row {index} of the batch changed since it was sent, \
+                     restore it or remove the journal {}",
+                    path.display()
+                );
+            }
+        }
+        Ok(journal)
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        Ok(fs::write_pretty_json_file(path, self)?)
+    }
+}
+
+/// The status of a row in the report of a batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum RowStatus {
+    Success,
+    Reverted,
+    Pending,
+    Unsent,
+}
+
+#[derive(Serialize)]
+struct ReportEntry<'a> {
+    index: usize,
+    #[serde(flatten)]
+    row: &'a BatchRow,
+    nonce: u64,
+    status: RowStatus,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    hashes: &'a [TxHash],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipt: Option<&'a BatchReceipt>,
+}
+
+/// Sends the transactions of a batch file with sequential nonces, resuming from its journal if
+/// the batch was interrupted.
+pub struct BatchSender<'a> {
+    pub path: PathBuf,
+    pub tx: TransactionOpts,
+    pub config: &'a Config,
+    pub from: Address,
+    pub confirmations: u64,
+    /// The time a row may take to be mined, in seconds.
+    pub timeout: u64,
+    pub opts: BatchOpts,
+}
+
+impl BatchSender<'_> {
+    pub async fn send<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+        self,
+        provider: P,
+    ) -> Result<()> {
+        let Self { path, tx: tx_opts, config, from, confirmations, timeout, opts } = self;
+
+        let rows = read_batch(&path)?;
+        let journal_path =
+            opts.journal.clone().unwrap_or_else(|| path.with_extension("journal.json"));
+        let report_path = opts.report.clone().unwrap_or_else(|| path.with_extension("report.json"));
+
+        let chain = provider.get_chain_id().await?;
+        let nonce = tx_opts.nonce.map(|nonce| nonce.to());
+        let mut journal = if journal_path.exists() {
+            let journal = Journal::load(&journal_path, chain, from, &rows)?;
+            if nonce.is_some_and(|nonce| nonce != journal.nonce) {
+                eyre::bail!(
+                    "the batch was started at nonce {}, remove the journal {} to restart it",
+                    journal.nonce,
+                    journal_path.display()
+                );
+            }
+            sh_println!("Resuming the batch from {}", journal_path.display())?;
+            journal
+        } else {
+            let nonce = match nonce {
+                Some(nonce) => nonce,
+                None => provider.get_transaction_count(from).pending().await?,
+            };
+            Journal { chain, sender: from, nonce, entries: BTreeMap::new() }
+        };
+
+        let queue = (0..rows.len())
+            .filter(|index| journal.entries.get(index).is_none_or(|entry| entry.receipt.is_none()))
+            .collect::<Vec<_>>();
+        let mut queue = queue.into_iter();
+        let mut started = HashMap::new();
+        let mut in_flight = FuturesUnordered::new();
+        let mut result = Ok(());
+
+        loop {
+            while result.is_ok() && in_flight.len() < opts.concurrency {
+                let Some(index) = queue.next() else { break };
+                let nonce = journal.nonce + index as u64;
+                let sent = match journal.entries.get_mut(&index) {
+                    Some(entry) => resend_dropped(&provider, entry).await,
+                    None => {
+                        let tx = build_tx(&provider, &tx_opts, config, from, &rows[index], nonce);
+                        match tx.await {
+                            Ok(tx) => send_tx(&provider, tx.clone()).await.map(|hash| {
+                                let row = rows[index].clone();
+                                let entry =
+                                    JournalEntry { row, tx, hashes: vec![hash], receipt: None };
+                                journal.entries.insert(index, entry);
+                            }),
+                            Err(err) => Err(err),
+                        }
+                    }
+                };
+                if let Err(err) = sent.and_then(|()| journal.save(&journal_path)) {
+                    result = Err(err.wrap_err(format!("failed to send row {index} of the batch")));
+                    break;
+                }
+
+                let now = Instant::now();
+                started.insert(index, now);
+                let entry = &journal.entries[&index];
+                let deadline = next_deadline(&opts, entry, now, now + Duration::from_secs(timeout));
+                in_flight.push(wait_for_receipt(
+                    &provider,
+                    index,
+                    entry.hashes.clone(),
+                    confirmations,
+                    deadline,
+                ));
+            }
+
+            let Some((index, receipt)) = in_flight.next().await else { break };
+            let entry = journal.entries.get_mut(&index).expect("in-flight rows are journaled");
+            match receipt {
+                Ok(Some(receipt)) => {
+                    if !shell::is_json() {
+                        let status = if receipt.status { "Success" } else { "Reverted" };
+                        sh_println!(
+                            "[{status}] Row {index}, nonce {}: {:#x} (block {})",
+                            journal.nonce + index as u64,
+                            receipt.transaction_hash,
+                            receipt.block_number
+                        )?;
+                    }
+                    entry.receipt = Some(receipt);
+                }
+                Ok(None) => {
+                    let timeout_at = started[&index] + Duration::from_secs(timeout);
+                    let now = Instant::now();
+                    if now >= timeout_at {
+                        result = result.and(Err(eyre::eyre!(
+                            "row {index} of the batch was not mined after {timeout} seconds"
+                        )));
+                        continue;
+                    }
+
+                    // The deadline was the time to bump the fees of the transaction.
+                    bump_fees(&mut entry.tx, opts.bump_percent);
+                    match send_tx(&provider, entry.tx.clone()).await {
+                        Ok(hash) => {
+                            entry.hashes.push(hash);
+                            if !shell::is_json() {
+                                sh_println!("Replaced row {index} with {hash:#x}")?;
+                            }
+                        }
+                        Err(err) => {
+                            result = result.and(Err(
+                                err.wrap_err(format!("failed to replace row {index} of the batch"))
+                            ));
+                        }
+                    }
+                    let deadline = next_deadline(&opts, entry, now, timeout_at);
+                    in_flight.push(wait_for_receipt(
+                        &provider,
+                        index,
+                        entry.hashes.clone(),
+                        confirmations,
+                        deadline,
+                    ));
+                }
+                Err(err) => {
+                    result = result.and(Err(err));
+                    continue;
+                }
+            }
+            journal.save(&journal_path)?;
+        }
+
+        write_report(&journal, &rows, &report_path)?;
+        result
+    }
+}
+
+/// Builds the transaction of a row with the given nonce.
+async fn build_tx<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+    provider: &P,
+    tx_opts: &TransactionOpts,
+    config: &Config,
+    from: Address,
+    row: &BatchRow,
+    nonce: u64,
+) -> Result<WithOtherFields<TransactionRequest>> {
+    let mut tx_opts = tx_opts.clone();
+    tx_opts.nonce = Some(U64::from(nonce));
+    if let Some(value) = &row.value {
+        tx_opts.value = Some(parse_ether_value(value)?);
+    }
+    let to = NameOrAddress::from_str(&row.to)?;
+    let (tx, _) = CastTxBuilder::new(provider, tx_opts, config)
+        .await?
+        .with_to(Some(to))
+        .await?
+        .with_code_sig_and_args(None, row.sig.clone(), row.args.clone())
+        .await?
+        .build(from)
+        .await?;
+    Ok(tx)
+}
+
+async fn send_tx<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+    provider: &P,
+    tx: WithOtherFields<TransactionRequest>,
+) -> Result<TxHash> {
+    Ok(*provider.send_transaction(tx).await?.tx_hash())
+}
+
+/// Sends the last transaction of a row sent before the batch was interrupted again, if the node
+/// no longer knows it.
+async fn resend_dropped<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+    provider: &P,
+    entry: &mut JournalEntry,
+) -> Result<()> {
+    let hash = *entry.hashes.last().expect("journaled rows were sent");
+    if provider.get_transaction_by_hash(hash).await?.is_none() {
+        let hash = send_tx(provider, entry.tx.clone()).await?;
+        if !entry.hashes.contains(&hash) {
+            entry.hashes.push(hash);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the time to stop waiting for a row, which is either the time to bump its fees or the
+/// time it times out.
+fn next_deadline(
+    opts: &BatchOpts,
+    entry: &JournalEntry,
+    now: Instant,
+    timeout_at: Instant,
+) -> Instant {
+    let bumps = entry.hashes.len().saturating_sub(1);
+    match opts.bump_after {
+        Some(bump_after) if bumps < opts.max_bumps => {
+            timeout_at.min(now + Duration::from_secs(bump_after))
+        }
+        _ => timeout_at,
+    }
+}
+
+/// Polls the receipts of the transactions sent for a row until one of them is mined with the
+/// given number of confirmations, or the deadline is reached.
+async fn wait_for_receipt<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+    provider: &P,
+    index: usize,
+    hashes: Vec<TxHash>,
+    confirmations: u64,
+    deadline: Instant,
+) -> (usize, Result<Option<BatchReceipt>>) {
+    let receipt = async {
+        let interval = provider.client().poll_interval();
+        let receipt = 'poll: loop {
+            for hash in &hashes {
+                if let Some(receipt) = provider.get_transaction_receipt(*hash).await? {
+                    break 'poll receipt;
+                }
+            }
+            if Instant::now() >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(interval).await;
+        };
+
+        let block_number = receipt.block_number().unwrap_or_default();
+        while provider.get_block_number().await? + 1 < block_number + confirmations {
+            tokio::time::sleep(interval).await;
+        }
+
+        Ok(Some(BatchReceipt {
+            transaction_hash: receipt.transaction_hash(),
+            status: receipt.status(),
+            block_number,
+            gas_used: receipt.gas_used(),
+            effective_gas_price: receipt.effective_gas_price(),
+        }))
+    };
+    (index, receipt.await)
+}
+
+/// Raises the fees of a transaction by `percent`, rounding up so that the replacement is
+/// accepted by nodes requiring a minimum bump.
+fn bump_fees(tx: &mut TransactionRequest, percent: u64) {
+    let fees = [
+        &mut tx.gas_price,
+        &mut tx.max_fee_per_gas,
+        &mut tx.max_priority_fee_per_gas,
+        &mut tx.max_fee_per_blob_gas,
+    ];
+    for fee in fees.into_iter().flatten() {
+        *fee += (*fee * percent as u128).div_ceil(100);
+    }
+}
+
+fn write_report(journal: &Journal, rows: &[BatchRow], path: &Path) -> Result<()> {
+    let report = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let entry = journal.entries.get(&index);
+            let receipt = entry.and_then(|entry| entry.receipt.as_ref());
+            let status = match (entry, receipt) {
+                (_, Some(receipt)) if receipt.status => RowStatus::Success,
+                (_, Some(_)) => RowStatus::Reverted,
+                (Some(_), None) => RowStatus::Pending,
+                (None, None) => RowStatus::Unsent,
+            };
+            ReportEntry {
+                index,
+                row,
+                nonce: journal.nonce + index as u64,
+                status,
+                hashes: entry.map(|entry| entry.hashes.as_slice()).unwrap_or_default(),
+                receipt,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    fs::write_pretty_json_file(path, &report)?;
+    if shell::is_json() {
+        sh_println!("{}", serde_json::to_string(&report)?)?;
+    } else {
+        sh_println!("Wrote the report of the batch to {}", path.display())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv_batch() {
+        let rows = parse_csv(
+            "to,sig,args,value\n\
+             0x000000000000000000000000000000000000dEaD,,,1ether\n\
+             vitalik.eth,\"transfer(address,uint256)\",0x000000000000000000000000000000000000dEaD 100,\n",
+        )
+        .unwrap();
+        assert_eq!(
+            rows,
+            [
+                BatchRow {
+                    to: "0x000000000000000000000000000000000000dEaD".into(),
+                    sig: None,
+                    args: vec![],
+                    value: Some("1ether".into()),
+                },
+                BatchRow {
+                    to: "vitalik.eth".into(),
+                    sig: Some("transfer(address,uint256)".into()),
+                    args: vec!["0x000000000000000000000000000000000000dEaD".into(), "100".into()],
+                    value: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn bumps_fees() {
+        let mut tx = TransactionRequest {
+            max_fee_per_gas: Some(1_000_000_001),
+            max_priority_fee_per_gas: Some(0),
+            ..Default::default()
+        };
+        bump_fees(&mut tx, 10);
+        assert_eq!(tx.max_fee_per_gas, Some(1_100_000_002));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(0));
+        assert_eq!(tx.gas_price, None);
+    }
+}
@@ -16,6 +16,9 @@
 use foundry_config::Config;
 use std::{path::PathBuf, str::FromStr};
 
+mod batch;
+use batch::{BatchOpts, BatchSender};
+
 /// CLI arguments for `cast send`.
 #[derive(Debug, Parser)]
 pub struct SendTxArgs {
@@ -65,6 +68,25 @@ pub struct SendTxArgs {
         help_heading = "Transaction options"
     )]
     path: Option<PathBuf>,
+
+    /// Send the transactions of a CSV or JSON batch file with sequential nonces.
+    ///
+    /// CSV files have a header with the columns `to`, `sig`, `args` and `value`, where the
+    /// arguments are separated by whitespace. JSON files contain an array of objects with the
+    /// same fields, where `args` is an array.
+    ///
+    /// The transactions sent are recorded in a journal, so that running the same command again
+    /// resumes an interrupted batch.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["async", "path", "auth", "blob"],
+        help_heading = "Batch options"
+    )]
+    batch: Option<PathBuf>,
+
+    #[command(flatten)]
+    batch_opts: BatchOpts,
 }
 
 #[derive(Debug, Parser)]
@@ -98,6 +120,8 @@ pub async fn run(self) -> eyre::Result<()> {
             unlocked,
             path,
             timeout,
+            batch,
+            batch_opts,
         } = self;
 
         let blob_data = if let Some(path) = path { Some(std::fs::read(path)?) } else { None };
@@ -117,6 +141,32 @@ pub async fn run(self) -> eyre::Result<()> {
 
         let config = Config::from(&eth);
         let provider = utils::get_provider(&config)?;
+        let timeout = timeout.unwrap_or(config.transaction_timeout);
+
+        if let Some(path) = batch {
+            if to.is_some() || code.is_some() {
+                eyre::bail!("the transactions of a batch are read from the batch file");
+            }
+            let mut batch = BatchSender {
+                path,
+                tx,
+                config: &config,
+                from: config.sender,
+                confirmations,
+                timeout,
+                opts: batch_opts,
+            };
+            if unlocked {
+                return batch.send(provider).await;
+            }
+            let signer = eth.wallet.signer().await?;
+            batch.from = signer.address();
+            tx::validate_from_address(eth.wallet.from, batch.from)?;
+            let provider = ProviderBuilder::<_, _, AnyNetwork>::default()
+                .wallet(EthereumWallet::from(signer))
+                .on_provider(&provider);
+            return batch.send(provider).await;
+        }
 
         let builder = CastTxBuilder::new(&provider, tx, &config)
             .await?
@@ -126,8 +176,6 @@ pub async fn run(self) -> eyre::Result<()> {
             .await?
             .with_blob_data(blob_data)?;
 
-        let timeout = timeout.unwrap_or(config.transaction_timeout);
-
         // Case 1:
         // Default to sending via eth_sendTransaction if the --unlocked flag is passed.
         // This should be the only way this RPC method is used as it requires a local node
@@ -134,59 +134,70 @@ pub async fn run(self) -> Result<()> {
         let chain = utils::get_chain(config.chain, &provider).await?;
         let api_key = config.get_etherscan_api_key(Some(chain)).unwrap_or_default();
         let client = Client::new(chain, api_key)?;
-        let source = find_source(client, address).await?;
-        let metadata = source.items.first().unwrap();
-        if metadata.is_vyper() {
-            eyre::bail!("Contract at provided address is not a valid Solidity contract")
-        }
+        let (_, artifact) = fetch_etherscan_artifact(client, address).await?;
+
+        fetch_and_print_storage(provider, address, block, &artifact, !shell::is_json()).await
+    }
+}
+
+/// Compiles the verified sources of `address` fetched from Etherscan, and returns the name and the
+/// artifact of the deployed contract, with its storage layout.
+pub async fn fetch_etherscan_artifact(
+    client: Client,
+    address: Address,
+) -> Result<(String, ConfigurableContractArtifact)> {
+    let source = find_source(client, address).await?;
+    let metadata = source.items.first().unwrap();
+    if metadata.is_vyper() {
+        eyre::bail!("Contract at provided address is not a valid Solidity contract")
+    }
+
+    let version = metadata.compiler_version()?;
+    let auto_detect = version < MIN_SOLC;
+
+    // Create a new temp project
+    // TODO: Cache instead of using a temp directory: metadata from Etherscan won't change
+    let root = tempfile::tempdir()?;
+    let root_path = root.path();
+    let mut project = etherscan_project(metadata, root_path)?;
+    add_storage_layout_output(&mut project);
 
-        let version = metadata.compiler_version()?;
-        let auto_detect = version < MIN_SOLC;
-
-        // Create a new temp project
-        // TODO: Cache instead of using a temp directory: metadata from Etherscan won't change
-        let root = tempfile::tempdir()?;
-        let root_path = root.path();
-        let mut project = etherscan_project(metadata, root_path)?;
-        add_storage_layout_output(&mut project);
-
-        project.compiler = if auto_detect {
-            SolcCompiler::AutoDetect
-        } else {
-            SolcCompiler::Specific(Solc::find_or_install(&version)?)
-        };
-
-        // Compile
-        let mut out = ProjectCompiler::new().quiet(true).compile(&project)?;
-        let artifact = {
-            let (_, mut artifact) = out
-                .artifacts()
-                .find(|(name, _)| name == &metadata.contract_name)
-                .ok_or_else(|| eyre::eyre!("Could not find artifact"))?;
-
-            if is_storage_layout_empty(&artifact.storage_layout) && auto_detect {
-                // try recompiling with the minimum version
-                sh_warn!("The requested contract was compiled with {version} while the minimum version for storage layouts is {MIN_SOLC} and as a result the output may be empty.")?;
-                let solc = Solc::find_or_install(&MIN_SOLC)?;
-                project.compiler = SolcCompiler::Specific(solc);
-                if let Ok(output) = ProjectCompiler::new().quiet(true).compile(&project) {
-                    out = output;
-                    let (_, new_artifact) = out
-                        .artifacts()
-                        .find(|(name, _)| name == &metadata.contract_name)
-                        .ok_or_else(|| eyre::eyre!("Could not find artifact"))?;
-                    artifact = new_artifact;
-                }
+    project.compiler = if auto_detect {
+        SolcCompiler::AutoDetect
+    } else {
+        SolcCompiler::Specific(Solc::find_or_install(&version)?)
+    };
+
+    // Compile
+    let mut out = ProjectCompiler::new().quiet(true).compile(&project)?;
+    let artifact = {
+        let (_, mut artifact) = out
+            .artifacts()
+            .find(|(name, _)| name == &metadata.contract_name)
+            .ok_or_else(|| eyre::eyre!("Could not find artifact"))?;
+
+        if is_storage_layout_empty(&artifact.storage_layout) && auto_detect {
+            // try recompiling with the minimum version
+            sh_warn!("The requested contract was compiled with {version} while the minimum version for storage layouts is {MIN_SOLC} and as a result the output may be empty.")?;
+            let solc = Solc::find_or_install(&MIN_SOLC)?;
+            project.compiler = SolcCompiler::Specific(solc);
+            if let Ok(output) = ProjectCompiler::new().quiet(true).compile(&project) {
+                out = output;
+                let (_, new_artifact) = out
+                    .artifacts()
+                    .find(|(name, _)| name == &metadata.contract_name)
+                    .ok_or_else(|| eyre::eyre!("Could not find artifact"))?;
+                artifact = new_artifact;
             }
+        }
 
-            artifact
-        };
+        artifact.clone()
+    };
 
-        // Clear temp directory
-        root.close()?;
+    // Clear temp directory
+    root.close()?;
 
-        fetch_and_print_storage(provider, address, block, artifact, !shell::is_json()).await
-    }
+    Ok((metadata.contract_name.clone(), artifact))
 }
 
 /// Represents the value of a storage slot `eth_getStorageAt` call.
@@ -324,7 +335,9 @@ fn print_storage(layout: StorageLayout, values: Vec<StorageValue>, pretty: bool)
     Ok(())
 }
 
-fn add_storage_layout_output<C: Compiler<CompilerContract = Contract>>(project: &mut Project<C>) {
+pub fn add_storage_layout_output<C: Compiler<CompilerContract = Contract>>(
+    project: &mut Project<C>,
+) {
     project.artifacts.additional_values.storage_layout = true;
     project.update_output_selection(|selection| {
         selection.0.values_mut().for_each(|contract_selection| {
@@ -335,7 +348,7 @@ fn add_storage_layout_output<C: Compiler<CompilerContract = Contract>>(project:
     })
 }
 
-fn is_storage_layout_empty(storage_layout: &Option<StorageLayout>) -> bool {
+pub fn is_storage_layout_empty(storage_layout: &Option<StorageLayout>) -> bool {
     if let Some(ref s) = storage_layout {
         s.storage.is_empty()
     } else {
@@ -0,0 +1,291 @@
+use super::run::{configure_block_env, replay_previous_transactions};
+use alloy_consensus::Transaction;
+use alloy_network::{AnyNetwork, TransactionResponse};
+use alloy_primitives::{Address, Bytes, TxHash, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, BlockTransactions, BlockTransactionsKind};
+use alloy_transport::Transport;
+use cast::{
+    revm::primitives::EnvWithHandlerCfg,
+    trace_diff::{CallNode, TraceDiff},
+    traces::{decode_trace_arena, TraceKind},
+};
+use clap::Parser;
+use eyre::{OptionExt, Result, WrapErr};
+use foundry_cli::{
+    opts::{EtherscanOpts, RpcOpts},
+    utils::{self, build_trace_decoder, parse_ether_value, TraceResult},
+};
+use foundry_common::{
+    abi::{encode_function_args, get_func},
+    ens::NameOrAddress,
+    is_known_system_sender, shell, SYSTEM_TRANSACTION_TYPE,
+};
+use foundry_compilers::artifacts::EvmVersion;
+use foundry_config::{figment::Figment, Chain, Config};
+use foundry_evm::{
+    executors::TracingExecutor, opts::EvmOpts, traces::TraceMode, utils::configure_tx_env,
+};
+use std::str::FromStr;
+
+/// CLI arguments for `cast trace-diff`.
+#[derive(Clone, Debug, Parser)]
+pub struct TraceDiffArgs {
+    /// The hash of the first transaction, or the block of the first call if `--to` is set.
+    first: String,
+
+    /// The hash of the second transaction, or the block of the second call if `--to` is set.
+    second: String,
+
+    /// Compare the same call executed at two blocks instead of two transactions.
+    ///
+    /// The address of the contract to call.
+    #[arg(long, value_parser = NameOrAddress::from_str)]
+    to: Option<NameOrAddress>,
+
+    /// The signature of the function to call.
+    #[arg(long, requires = "to")]
+    sig: Option<String>,
+
+    /// The arguments of the function to call.
+    #[arg(long, requires = "sig", num_args = 1.., allow_negative_numbers = true)]
+    args: Vec<String>,
+
+    /// The sender of the call.
+    #[arg(long, requires = "to")]
+    from: Option<Address>,
+
+    /// Ether to send with the call.
+    ///
+    /// Either specified in wei, or as a string with a unit type.
+    ///
+    /// Examples: 1ether, 10gwei, 0.01ether
+    #[arg(long, requires = "to", value_parser = parse_ether_value)]
+    value: Option<U256>,
+
+    /// Executes the transactions only with the state from their previous block.
+    ///
+    /// May result in different results than the live execution!
+    #[arg(long, conflicts_with = "to")]
+    quick: bool,
+
+    /// Render the two traces side by side instead of as a single tree.
+    #[arg(long)]
+    side_by_side: bool,
+
+    /// Don't compare the gas used by the calls.
+    #[arg(long)]
+    ignore_gas: bool,
+
+    /// Label addresses in the traces.
+    ///
+    /// Example: 0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045:vitalik.eth
+    #[arg(long, short)]
+    label: Vec<String>,
+
+    /// Use current project artifacts for trace decoding.
+    #[arg(long, visible_alias = "la")]
+    with_local_artifacts: bool,
+
+    /// The EVM version to use.
+    ///
+    /// Overrides the version specified in the config.
+    #[arg(long)]
+    evm_version: Option<EvmVersion>,
+
+    #[command(flatten)]
+    etherscan: EtherscanOpts,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+impl TraceDiffArgs {
+    pub async fn run(self) -> Result<()> {
+        let figment = Into::<Figment>::into(&self.rpc).merge(&self.etherscan);
+        let evm_opts = figment.extract::<EvmOpts>()?;
+        let config = Config::try_from(figment)?.sanitized();
+        let provider = utils::get_provider(&config)?;
+
+        let (first, second, chain) = if let Some(to) = &self.to {
+            let to = to.resolve(&provider).await?;
+            let input = match &self.sig {
+                Some(sig) => encode_function_args(&get_func(sig)?, &self.args)?.into(),
+                None => Bytes::new(),
+            };
+            let mut results = Vec::with_capacity(2);
+            for block in [&self.first, &self.second] {
+                let block = block_number(&provider, block).await?;
+                results.push(
+                    self.execute_call(&config, evm_opts.clone(), block, to, input.clone()).await?,
+                );
+            }
+            let [(first, chain), (second, _)] = <[_; 2]>::try_from(results).unwrap();
+            (first, second, chain)
+        } else {
+            let (first, chain) =
+                self.replay(&config, evm_opts.clone(), &provider, &self.first).await?;
+            let (second, _) = self.replay(&config, evm_opts, &provider, &self.second).await?;
+            (first, second, chain)
+        };
+
+        let mut arenas = [first, second].map(|result| {
+            result.traces.and_then(|traces| traces.into_iter().next()).map(|(_, arena)| arena)
+        });
+        let [Some(first), Some(second)] = &mut arenas else {
+            eyre::bail!("no traces were recorded");
+        };
+
+        let (decoder, _) = build_trace_decoder(
+            [&*first, &*second],
+            &config,
+            chain,
+            &self.label,
+            self.with_local_artifacts,
+            false,
+        )
+        .await?;
+        decode_trace_arena(first, &decoder).await?;
+        decode_trace_arena(second, &decoder).await?;
+
+        let diff = TraceDiff::new(
+            &CallNode::from_arena(first),
+            &CallNode::from_arena(second),
+            !self.ignore_gas,
+        );
+
+        if shell::is_json() {
+            sh_println!("{}", serde_json::to_string(&diff)?)?;
+            return Ok(());
+        }
+
+        if diff.is_empty() {
+            sh_println!("The traces are identical.")?;
+        } else if self.side_by_side {
+            sh_print!("{}", diff.side_by_side())?;
+        } else {
+            sh_println!("--- {}\n+++ {}", self.first, self.second)?;
+            sh_print!("{}", diff.unified())?;
+        }
+
+        Ok(())
+    }
+
+    /// Executes the call at the state of `block`.
+    async fn execute_call(
+        &self,
+        config: &Config,
+        evm_opts: EvmOpts,
+        block: u64,
+        to: Address,
+        input: Bytes,
+    ) -> Result<(TraceResult, Option<Chain>)> {
+        let mut config = config.clone();
+        config.fork_block_number = Some(block);
+
+        let create2_deployer = evm_opts.create2_deployer;
+        let (mut env, fork, chain, odyssey) =
+            TracingExecutor::get_fork_material(&config, evm_opts).await?;
+
+        // modify settings that usually set in eth_call
+        env.cfg.disable_block_gas_limit = true;
+        env.block.gas_limit = U256::MAX;
+
+        let mut executor = TracingExecutor::new(
+            env,
+            fork,
+            self.evm_version,
+            TraceMode::Call,
+            odyssey,
+            create2_deployer,
+        );
+        let from = self.from.unwrap_or_default();
+        let raw = executor.transact_raw(from, to, input, self.value.unwrap_or_default())?;
+
+        Ok((TraceResult::from_raw(raw, TraceKind::Execution), chain))
+    }
+
+    /// Replays a transaction on the state of its block right before it was executed, or on the
+    /// state of the previous block if `--quick` is set.
+    async fn replay<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+        &self,
+        config: &Config,
+        evm_opts: EvmOpts,
+        provider: &P,
+        tx_hash: &str,
+    ) -> Result<(TraceResult, Option<Chain>)> {
+        let tx_hash = TxHash::from_str(tx_hash).wrap_err("invalid tx hash")?;
+        let tx = provider
+            .get_transaction_by_hash(tx_hash)
+            .await
+            .wrap_err_with(|| format!("tx not found: {tx_hash:?}"))?
+            .ok_or_else(|| eyre::eyre!("tx not found: {:?}", tx_hash))?;
+
+        if is_known_system_sender(tx.from) || tx.transaction_type() == Some(SYSTEM_TRANSACTION_TYPE)
+        {
+            eyre::bail!("{tx_hash:?} is a system transaction, which can't be replayed");
+        }
+
+        let tx_block_number =
+            tx.block_number.ok_or_else(|| eyre::eyre!("tx may still be pending: {:?}", tx_hash))?;
+        let block = provider.get_block(tx_block_number.into(), true.into()).await?;
+
+        // we need to fork off the parent block
+        let mut config = config.clone();
+        config.fork_block_number = Some(tx_block_number - 1);
+
+        let create2_deployer = evm_opts.create2_deployer;
+        let (mut env, fork, chain, odyssey) =
+            TracingExecutor::get_fork_material(&config, evm_opts).await?;
+
+        let mut evm_version = self.evm_version;
+        env.block.number = U256::from(tx_block_number);
+        if let Some(block) = &block {
+            configure_block_env(&mut env, block, &mut evm_version);
+        }
+
+        let mut executor = TracingExecutor::new(
+            env.clone(),
+            fork,
+            evm_version,
+            TraceMode::Call,
+            odyssey,
+            create2_deployer,
+        );
+        let mut env = EnvWithHandlerCfg::new_with_spec_id(Box::new(env), executor.spec_id());
+
+        if !self.quick {
+            if let Some(block) = block {
+                let BlockTransactions::Full(ref txs) = block.transactions else {
+                    eyre::bail!("could not get the transactions of block {tx_block_number}");
+                };
+                replay_previous_transactions(&mut executor, &mut env, txs, tx_hash)?;
+            }
+        }
+
+        configure_tx_env(&mut env, &tx.inner);
+        let result = if Transaction::to(&tx).is_some() {
+            TraceResult::try_from(executor.transact_with_env(env))?
+        } else {
+            TraceResult::try_from(executor.deploy_with_env(env, None))?
+        };
+
+        Ok((result, chain))
+    }
+}
+
+/// Resolves a block number or tag to a block number.
+async fn block_number<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+    provider: &P,
+    block: &str,
+) -> Result<u64> {
+    if let Ok(number) = block.parse() {
+        return Ok(number);
+    }
+    let id = BlockId::from_str(block).wrap_err_with(|| format!("invalid block: {block}"))?;
+    let block = provider
+        .get_block(id, BlockTransactionsKind::Hashes)
+        .await?
+        .ok_or_eyre(format!("block {block} not found"))?;
+    Ok(block.header.number)
+}
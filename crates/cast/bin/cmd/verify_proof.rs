@@ -0,0 +1,123 @@
+use alloy_network::AnyNetwork;
+use alloy_primitives::B256;
+use alloy_provider::Provider;
+use alloy_rpc_types::{BlockId, BlockTransactionsKind, EIP1186AccountProofResponse};
+use alloy_transport::Transport;
+use cast::proof::{ProofError, ProofVerification};
+use clap::Parser;
+use eyre::{OptionExt, Result};
+use foundry_cli::{opts::RpcOpts, utils};
+use foundry_common::{fs, shell, stdin};
+use foundry_config::Config;
+use serde_json::json;
+use std::path::Path;
+
+/// CLI arguments for `cast verify-proof`.
+#[derive(Clone, Debug, Parser)]
+pub struct VerifyProofArgs {
+    /// The `eth_getProof` response to verify, as printed by `cast proof`, or the path of a file
+    /// containing it.
+    ///
+    /// Read from stdin if not provided.
+    proof: Option<String>,
+
+    /// The state root to verify the proof against.
+    ///
+    /// Defaults to the state root of the block.
+    #[arg(long, value_name = "ROOT")]
+    state_root: Option<B256>,
+
+    /// The block whose state root the proof is verified against, the latest by default.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[arg(long, short = 'B', conflicts_with = "state_root")]
+    block: Option<BlockId>,
+
+    #[command(flatten)]
+    rpc: RpcOpts,
+}
+
+impl VerifyProofArgs {
+    pub async fn run(self) -> Result<()> {
+        let Self { proof, state_root, block, rpc } = self;
+
+        let proof = stdin::unwrap(proof, false)?;
+        let proof = proof.trim();
+        let proof: EIP1186AccountProofResponse = if proof.starts_with('{') {
+            serde_json::from_str(proof)?
+        } else {
+            fs::read_json_file(Path::new(proof))?
+        };
+
+        let state_root = match state_root {
+            Some(state_root) => state_root,
+            None => {
+                let config = Config::from(&rpc);
+                let provider = utils::get_provider(&config)?;
+                block_state_root(&provider, block.unwrap_or_default()).await?.1
+            }
+        };
+
+        print_verification(state_root, &proof)
+    }
+}
+
+/// Returns the number and the state root of a block.
+pub async fn block_state_root<P: Provider<T, AnyNetwork>, T: Transport + Clone>(
+    provider: &P,
+    block: BlockId,
+) -> Result<(u64, B256)> {
+    let block = provider
+        .get_block(block, BlockTransactionsKind::Hashes)
+        .await?
+        .ok_or_eyre(format!("block {block} not found"))?;
+    Ok((block.header.number, block.header.state_root))
+}
+
+/// Verifies the proofs of an `eth_getProof` response against a state root and prints which ones
+/// fail, and at which proof node.
+pub fn print_verification(state_root: B256, proof: &EIP1186AccountProofResponse) -> Result<()> {
+    let verification = ProofVerification::new(state_root, proof);
+
+    if shell::is_json() {
+        let result = |result: &Result<(), ProofError>| match result {
+            Ok(()) => json!({ "valid": true }),
+            Err(ProofError { node, source }) => {
+                json!({ "valid": false, "node": node, "error": source.to_string() })
+            }
+        };
+        let storage = verification
+            .storage
+            .iter()
+            .map(|(key, storage)| {
+                let mut storage = result(storage);
+                storage["key"] = json!(key);
+                storage
+            })
+            .collect::<Vec<_>>();
+        sh_println!(
+            "{}",
+            json!({
+                "address": proof.address,
+                "stateRoot": state_root,
+                "valid": verification.is_valid(),
+                "account": result(&verification.account),
+                "storage": storage,
+            })
+        )?;
+    } else {
+        let status = |result: &Result<(), ProofError>| match result {
+            Ok(()) => "valid".to_string(),
+            Err(err) => format!("invalid, {err}"),
+        };
+        sh_println!("Account {}: {}", proof.address, status(&verification.account))?;
+        for (key, storage) in &verification.storage {
+            sh_println!("Slot {key}: {}", status(storage))?;
+        }
+    }
+
+    if !verification.is_valid() {
+        eyre::bail!("the proof does not match the state root {state_root}");
+    }
+    Ok(())
+}
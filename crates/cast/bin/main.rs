@@ -29,8 +29,9 @@
 pub mod cmd;
 pub mod tx;
 
-use args::{Cast as CastArgs, CastSubcommand, ToBaseArgs};
+use args::{Cast as CastArgs, CastSubcommand, ConvertSubcommand, ToBaseArgs};
 use cast::traces::identifier::SignaturesIdentifier;
+use cmd::verify_proof;
 
 #[macro_use]
 extern crate foundry_common;
@@ -62,44 +63,44 @@ fn run() -> Result<()> {
 async fn main_args(args: CastArgs) -> Result<()> {
     match args.cmd {
         // Constants
-        CastSubcommand::MaxInt { r#type } => {
+        CastSubcommand::Convert(ConvertSubcommand::MaxInt { r#type }) => {
             sh_println!("{}", SimpleCast::max_int(&r#type)?)?;
         }
-        CastSubcommand::MinInt { r#type } => {
+        CastSubcommand::Convert(ConvertSubcommand::MinInt { r#type }) => {
             sh_println!("{}", SimpleCast::min_int(&r#type)?)?;
         }
-        CastSubcommand::MaxUint { r#type } => {
+        CastSubcommand::Convert(ConvertSubcommand::MaxUint { r#type }) => {
             sh_println!("{}", SimpleCast::max_int(&r#type)?)?;
         }
-        CastSubcommand::AddressZero => {
+        CastSubcommand::Convert(ConvertSubcommand::AddressZero) => {
             sh_println!("{:?}", Address::ZERO)?;
         }
-        CastSubcommand::HashZero => {
+        CastSubcommand::Convert(ConvertSubcommand::HashZero) => {
             sh_println!("{:?}", B256::ZERO)?;
         }
 
         // Conversions & transformations
-        CastSubcommand::FromUtf8 { text } => {
+        CastSubcommand::Convert(ConvertSubcommand::FromUtf8 { text }) => {
             let value = stdin::unwrap(text, false)?;
             sh_println!("{}", SimpleCast::from_utf8(&value))?
         }
-        CastSubcommand::ToAscii { hexdata } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToAscii { hexdata }) => {
             let value = stdin::unwrap(hexdata, false)?;
             sh_println!("{}", SimpleCast::to_ascii(value.trim())?)?
         }
-        CastSubcommand::ToUtf8 { hexdata } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToUtf8 { hexdata }) => {
             let value = stdin::unwrap(hexdata, false)?;
             sh_println!("{}", SimpleCast::to_utf8(&value)?)?
         }
-        CastSubcommand::FromFixedPoint { value, decimals } => {
+        CastSubcommand::Convert(ConvertSubcommand::FromFixedPoint { value, decimals }) => {
             let (value, decimals) = stdin::unwrap2(value, decimals)?;
             sh_println!("{}", SimpleCast::from_fixed_point(&value, &decimals)?)?
         }
-        CastSubcommand::ToFixedPoint { value, decimals } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToFixedPoint { value, decimals }) => {
             let (value, decimals) = stdin::unwrap2(value, decimals)?;
             sh_println!("{}", SimpleCast::to_fixed_point(&value, &decimals)?)?
         }
-        CastSubcommand::ConcatHex { data } => {
+        CastSubcommand::Convert(ConvertSubcommand::ConcatHex { data }) => {
             if data.is_empty() {
                 let s = stdin::read(true)?;
                 sh_println!("{}", SimpleCast::concat_hex(s.split_whitespace()))?
@@ -107,11 +108,11 @@ async fn main_args(args: CastArgs) -> Result<()> {
                 sh_println!("{}", SimpleCast::concat_hex(data))?
             }
         }
-        CastSubcommand::FromBin => {
+        CastSubcommand::Convert(ConvertSubcommand::FromBin) => {
             let hex = stdin::read_bytes(false)?;
             sh_println!("{}", hex::encode_prefixed(hex))?
         }
-        CastSubcommand::ToHexdata { input } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToHexdata { input }) => {
             let value = stdin::unwrap_line(input)?;
             let output = match value {
                 s if s.starts_with('@') => hex::encode(std::env::var(&s[1..])?),
@@ -120,59 +121,62 @@ async fn main_args(args: CastArgs) -> Result<()> {
             };
             sh_println!("0x{output}")?
         }
-        CastSubcommand::ToCheckSumAddress { address } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToCheckSumAddress { address }) => {
             let value = stdin::unwrap_line(address)?;
             sh_println!("{}", value.to_checksum(None))?
         }
-        CastSubcommand::ToUint256 { value } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToUint256 { value }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::to_uint256(&value)?)?
         }
-        CastSubcommand::ToInt256 { value } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToInt256 { value }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::to_int256(&value)?)?
         }
-        CastSubcommand::ToUnit { value, unit } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToUnit { value, unit }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::to_unit(&value, &unit)?)?
         }
-        CastSubcommand::ParseUnits { value, unit } => {
+        CastSubcommand::Convert(ConvertSubcommand::ParseUnits { value, unit }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::parse_units(&value, unit)?)?;
         }
-        CastSubcommand::FormatUnits { value, unit } => {
+        CastSubcommand::Convert(ConvertSubcommand::FormatUnits { value, unit }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::format_units(&value, unit)?)?;
         }
-        CastSubcommand::FromWei { value, unit } => {
+        CastSubcommand::Convert(ConvertSubcommand::FromWei { value, unit }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::from_wei(&value, &unit)?)?
         }
-        CastSubcommand::ToWei { value, unit } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToWei { value, unit }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::to_wei(&value, &unit)?)?
         }
-        CastSubcommand::FromRlp { value, as_int } => {
+        CastSubcommand::Convert(ConvertSubcommand::FromRlp { value, as_int }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::from_rlp(value, as_int)?)?
         }
-        CastSubcommand::ToRlp { value } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToRlp { value }) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::to_rlp(&value)?)?
         }
-        CastSubcommand::ToHex(ToBaseArgs { value, base_in }) => {
+        CastSubcommand::Convert(ConvertSubcommand::ToHex(ToBaseArgs { value, base_in })) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::to_base(&value, base_in.as_deref(), "hex")?)?
         }
-        CastSubcommand::ToDec(ToBaseArgs { value, base_in }) => {
+        CastSubcommand::Convert(ConvertSubcommand::ToDec(ToBaseArgs { value, base_in })) => {
             let value = stdin::unwrap_line(value)?;
             sh_println!("{}", SimpleCast::to_base(&value, base_in.as_deref(), "dec")?)?
         }
-        CastSubcommand::ToBase { base: ToBaseArgs { value, base_in }, base_out } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToBase {
+            base: ToBaseArgs { value, base_in },
+            base_out,
+        }) => {
             let (value, base_out) = stdin::unwrap2(value, base_out)?;
             sh_println!("{}", SimpleCast::to_base(&value, base_in.as_deref(), &base_out)?)?
         }
-        CastSubcommand::ToBytes32 { bytes } => {
+        CastSubcommand::Convert(ConvertSubcommand::ToBytes32 { bytes }) => {
             let value = stdin::unwrap_line(bytes)?;
             sh_println!("{}", SimpleCast::to_bytes32(&value)?)?
         }
@@ -388,6 +392,7 @@ async fn main_args(args: CastArgs) -> Result<()> {
             let bytecode = stdin::unwrap_line(bytecode)?;
             sh_println!("{}", SimpleCast::disassemble(&hex::decode(bytecode)?)?)?
         }
+        CastSubcommand::Decompile(cmd) => cmd.run().await?,
         CastSubcommand::Selectors { bytecode, resolve } => {
             let bytecode = stdin::unwrap_line(bytecode)?;
             let functions = SimpleCast::extract_functions(&bytecode)?;
@@ -412,6 +417,7 @@ async fn main_args(args: CastArgs) -> Result<()> {
             }
         }
         CastSubcommand::FindBlock(cmd) => cmd.run().await?,
+        CastSubcommand::Bisect(cmd) => cmd.run().await?,
         CastSubcommand::GasPrice { rpc } => {
             let config = Config::from(&rpc);
             let provider = utils::get_provider(&config)?;
@@ -420,6 +426,7 @@ async fn main_args(args: CastArgs) -> Result<()> {
         CastSubcommand::Index { key_type, key, slot_number } => {
             sh_println!("{}", SimpleCast::index(&key_type, &key, &slot_number)?)?;
         }
+        CastSubcommand::FindSlot(cmd) => cmd.run().await?,
         CastSubcommand::IndexErc7201 { id, formula_id } => {
             eyre::ensure!(formula_id == "erc7201", "unsupported formula ID: {formula_id}");
             let id = stdin::unwrap_line(id)?;
@@ -455,16 +462,28 @@ async fn main_args(args: CastArgs) -> Result<()> {
             let who = who.resolve(&provider).await?;
             sh_println!("{}", Cast::new(provider).storage_root(who, slots, block).await?)?;
         }
-        CastSubcommand::Proof { address, slots, rpc, block } => {
+        CastSubcommand::Proof { address, slots, rpc, block, verify } => {
             let config = Config::from(&rpc);
             let provider = utils::get_provider(&config)?;
             let address = address.resolve(&provider).await?;
-            let value = provider
-                .get_proof(address, slots.into_iter().collect())
-                .block_id(block.unwrap_or_default())
-                .await?;
-            sh_println!("{}", serde_json::to_string(&value)?)?;
+            let block = block.unwrap_or_default();
+            if verify {
+                // Pin the block so that the proof and the state root are from the same state.
+                let (number, state_root) = verify_proof::block_state_root(&provider, block).await?;
+                let value = provider
+                    .get_proof(address, slots.into_iter().collect())
+                    .block_id(number.into())
+                    .await?;
+                verify_proof::print_verification(state_root, &value)?;
+            } else {
+                let value = provider
+                    .get_proof(address, slots.into_iter().collect())
+                    .block_id(block)
+                    .await?;
+                sh_println!("{}", serde_json::to_string(&value)?)?;
+            }
         }
+        CastSubcommand::VerifyProof(cmd) => cmd.run().await?,
         CastSubcommand::Rpc(cmd) => cmd.run().await?,
         CastSubcommand::Storage(cmd) => cmd.run().await?,
 
@@ -497,6 +516,7 @@ async fn main_args(args: CastArgs) -> Result<()> {
             )?
         }
         CastSubcommand::Run(cmd) => cmd.run().await?,
+        CastSubcommand::TraceDiff(cmd) => cmd.run().await?,
         CastSubcommand::SendTx(cmd) => cmd.run().await?,
         CastSubcommand::Tx { tx_hash, field, raw, rpc } => {
             let config = Config::from(&rpc);
@@ -622,11 +642,21 @@ async fn main_args(args: CastArgs) -> Result<()> {
             let parsed_event = get_event(&event_string)?;
             sh_println!("{:?}", parsed_event.selector())?
         }
-        CastSubcommand::LeftShift { value, bits, base_in, base_out } => sh_println!(
+        CastSubcommand::Convert(ConvertSubcommand::LeftShift {
+            value,
+            bits,
+            base_in,
+            base_out,
+        }) => sh_println!(
             "{}",
             SimpleCast::left_shift(&value, &bits, base_in.as_deref(), &base_out)?
         )?,
-        CastSubcommand::RightShift { value, bits, base_in, base_out } => sh_println!(
+        CastSubcommand::Convert(ConvertSubcommand::RightShift {
+            value,
+            bits,
+            base_in,
+            base_out,
+        }) => sh_println!(
             "{}",
             SimpleCast::right_shift(&value, &bits, base_in.as_deref(), &base_out)?
         )?,
@@ -651,6 +681,7 @@ async fn main_args(args: CastArgs) -> Result<()> {
             cmd.run()?;
         }
         CastSubcommand::Wallet { command } => command.run().await?,
+        CastSubcommand::Safe { command } => command.run().await?,
         CastSubcommand::Completions { shell } => {
             generate(shell, &mut CastArgs::command(), "cast", &mut std::io::stdout())
         }
@@ -661,6 +692,7 @@ async fn main_args(args: CastArgs) -> Result<()> {
             &mut std::io::stdout(),
         ),
         CastSubcommand::Logs(cmd) => cmd.run().await?,
+        CastSubcommand::Mempool(cmd) => cmd.run().await?,
         CastSubcommand::DecodeTransaction { tx } => {
             let tx = stdin::unwrap_line(tx)?;
             let tx = SimpleCast::decode_raw_transaction(&tx)?;
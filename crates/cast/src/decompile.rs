@@ -0,0 +1,860 @@
+//! Control-flow recovery and decompilation of EVM bytecode.
+//!
+//! The bytecode is split into basic blocks, and jump targets are resolved by executing the blocks
+//! on a symbolic stack which only tracks constants pushed by the code itself. This is enough to
+//! resolve the jumps emitted by Solidity and Vyper, including returns from internal functions
+//! whose return address is passed on the stack.
+
+use crate::{
+    revm::interpreter::{opcode::*, OpCode},
+    utils::{decode_instructions, Instruction},
+};
+use alloy_json_abi::Function;
+use alloy_primitives::{
+    hex,
+    map::{HashMap, HashSet},
+    Selector, U256,
+};
+use eyre::Result;
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Write,
+    ops::Range,
+};
+
+/// The maximum number of distinct entry stacks a block is executed with.
+const MAX_CONTEXTS: usize = 32;
+
+/// The maximum number of values tracked on the symbolic stack.
+const MAX_STACK: usize = 32;
+
+/// Expressions longer than this are assigned to a variable in the pseudo-Solidity output.
+const MAX_EXPR_LEN: usize = 80;
+
+/// A symbolic stack, whose values are either constants or unknown.
+type Stack = Vec<Option<U256>>;
+
+/// A storage access.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageAccess {
+    /// Whether the access is an `SSTORE`.
+    pub write: bool,
+    /// The accessed slot, if it is the same constant on every path.
+    pub slot: Option<U256>,
+}
+
+/// The part of the program reachable from a given block.
+#[derive(Clone, Debug, Default)]
+pub struct Reachable {
+    /// The successors of every reachable block, by start program counter.
+    pub blocks: BTreeMap<usize, BTreeSet<usize>>,
+    /// The resolved targets of every reachable jump, by program counter.
+    pub jumps: BTreeMap<usize, BTreeSet<usize>>,
+    /// The program counters of the jumps whose target could not be resolved.
+    pub unresolved: BTreeSet<usize>,
+    /// The reachable storage accesses, by program counter.
+    pub storage: BTreeMap<usize, StorageAccess>,
+    /// The first stack each block was entered with.
+    entry_stacks: HashMap<usize, Stack>,
+}
+
+impl Reachable {
+    /// Returns whether the reachable code may modify state.
+    fn is_view(&self, decompiler: &Decompiler<'_>) -> bool {
+        self.blocks
+            .keys()
+            .flat_map(|pc| decompiler.block(*pc))
+            .all(|inst| !inst.op.is_some_and(|op| modifies_state(op.get())))
+    }
+}
+
+/// A function of the dispatcher.
+#[derive(Clone, Debug)]
+pub struct DispatchedFunction {
+    /// The selector of the function.
+    pub selector: Selector,
+    /// The program counter the dispatcher jumps to for this selector.
+    pub entry: usize,
+    /// The program counter of the `PUSH4` pushing the selector in the dispatcher.
+    pub selector_pc: usize,
+    /// The code reachable from the entry of the function.
+    pub body: Reachable,
+}
+
+/// Recovers the control flow of a runtime bytecode.
+pub struct Decompiler<'a> {
+    instructions: Vec<Instruction<'a>>,
+    /// The basic blocks, as ranges of instructions, by start program counter.
+    blocks: BTreeMap<usize, Range<usize>>,
+    jumpdests: HashSet<usize>,
+    program: Reachable,
+    functions: Vec<DispatchedFunction>,
+}
+
+impl<'a> Decompiler<'a> {
+    /// Builds the control-flow graph of `code` and splits its dispatcher into functions.
+    ///
+    /// Trailing bytes which don't form a complete instruction, such as a truncated metadata hash,
+    /// are ignored.
+    pub fn new(code: &'a [u8]) -> Result<Self> {
+        let instructions = decode_instructions(&code[..complete_len(code)])?;
+
+        let mut blocks = BTreeMap::new();
+        let mut jumpdests = HashSet::default();
+        let mut start = 0;
+        for (i, inst) in instructions.iter().enumerate() {
+            let op = inst.op.map(OpCode::get);
+            if op == Some(JUMPDEST) {
+                jumpdests.insert(inst.pc);
+                if start < i {
+                    blocks.insert(instructions[start].pc, start..i);
+                    start = i;
+                }
+            }
+            if op.is_none_or(is_block_end) {
+                blocks.insert(instructions[start].pc, start..i + 1);
+                start = i + 1;
+            }
+        }
+        if start < instructions.len() {
+            blocks.insert(instructions[start].pc, start..instructions.len());
+        }
+
+        let mut decompiler = Self {
+            instructions,
+            blocks,
+            jumpdests,
+            program: Reachable::default(),
+            functions: vec![],
+        };
+        decompiler.program = decompiler.explore(0, vec![]);
+        decompiler.functions = decompiler.dispatch();
+        Ok(decompiler)
+    }
+
+    /// Returns the code reachable from the start of the program.
+    pub fn program(&self) -> &Reachable {
+        &self.program
+    }
+
+    /// Returns the functions of the dispatcher, in dispatch order.
+    pub fn functions(&self) -> &[DispatchedFunction] {
+        &self.functions
+    }
+
+    /// Returns the instructions of the block starting at `pc`.
+    fn block(&self, pc: usize) -> &[Instruction<'a>] {
+        self.blocks.get(&pc).map_or(&[], |range| &self.instructions[range.clone()])
+    }
+
+    /// Returns the start of the block following the one starting at `pc`.
+    fn next_block(&self, pc: usize) -> Option<usize> {
+        let range = self.blocks.get(&pc)?;
+        self.instructions.get(range.end).map(|inst| inst.pc)
+    }
+
+    /// Executes the blocks reachable from `start` on a symbolic stack, starting with `stack`.
+    fn explore(&self, start: usize, stack: Stack) -> Reachable {
+        let mut reachable = Reachable::default();
+        let mut contexts: HashMap<usize, HashSet<Stack>> = HashMap::default();
+        let mut queue = VecDeque::from([(start, stack)]);
+
+        while let Some((pc, mut stack)) = queue.pop_front() {
+            let context = contexts.entry(pc).or_default();
+            if !self.blocks.contains_key(&pc) ||
+                context.len() >= MAX_CONTEXTS ||
+                !context.insert(stack.clone())
+            {
+                continue;
+            }
+            reachable.entry_stacks.entry(pc).or_insert_with(|| stack.clone());
+
+            let mut successors = vec![];
+            let mut falls_through = true;
+            for inst in self.block(pc) {
+                let Some(op) = inst.op else {
+                    falls_through = false;
+                    break;
+                };
+                match op.get() {
+                    op @ (JUMP | JUMPI) => {
+                        let target = pop(&mut stack);
+                        if op == JUMPI {
+                            pop(&mut stack);
+                        } else {
+                            falls_through = false;
+                        }
+                        match target
+                            .and_then(|target| usize::try_from(target).ok())
+                            .filter(|target| self.jumpdests.contains(target))
+                        {
+                            Some(target) => {
+                                reachable.jumps.entry(inst.pc).or_default().insert(target);
+                                successors.push(target);
+                            }
+                            None => {
+                                reachable.unresolved.insert(inst.pc);
+                            }
+                        }
+                    }
+                    op @ (SLOAD | SSTORE) => {
+                        let slot = pop(&mut stack);
+                        let access = StorageAccess { write: op == SSTORE, slot };
+                        reachable
+                            .storage
+                            .entry(inst.pc)
+                            .and_modify(|known| {
+                                if known.slot != slot {
+                                    known.slot = None;
+                                }
+                            })
+                            .or_insert(access);
+                        if op == SLOAD {
+                            push(&mut stack, None);
+                        } else {
+                            pop(&mut stack);
+                        }
+                    }
+                    op if is_block_end(op) => falls_through = false,
+                    op => step(&mut stack, op, inst.immediate),
+                }
+            }
+            if falls_through {
+                successors.extend(self.next_block(pc));
+            }
+
+            let block = reachable.blocks.entry(pc).or_default();
+            for successor in successors {
+                block.insert(successor);
+                queue.push_back((successor, stack.clone()));
+            }
+        }
+
+        reachable
+    }
+
+    /// Splits the dispatcher into functions, by matching the comparisons of the selector with
+    /// constants followed by a conditional jump.
+    fn dispatch(&self) -> Vec<DispatchedFunction> {
+        let mut functions = Vec::<DispatchedFunction>::new();
+        for pc in self.program.blocks.keys() {
+            let block = self.block(*pc);
+            for (i, inst) in block.iter().enumerate() {
+                if inst.op.map(OpCode::get) != Some(PUSH4) {
+                    continue;
+                }
+                // `PUSH4 selector [DUPn | SWAPn] EQ PUSHn entry JUMPI`
+                let Some(eq) = block[i + 1..]
+                    .iter()
+                    .take(2)
+                    .position(|inst| inst.op.map(OpCode::get) == Some(EQ))
+                    .map(|offset| i + 1 + offset)
+                else {
+                    continue;
+                };
+                if !block[i + 1..eq].iter().all(|inst| {
+                    matches!(inst.op.map(OpCode::get), Some(DUP1..=DUP16 | SWAP1..=SWAP16))
+                }) {
+                    continue;
+                }
+                let (Some(push), Some(jumpi)) = (block.get(eq + 1), block.get(eq + 2)) else {
+                    continue;
+                };
+                if !matches!(push.op.map(OpCode::get), Some(PUSH1..=PUSH32)) ||
+                    jumpi.op.map(OpCode::get) != Some(JUMPI)
+                {
+                    continue;
+                }
+
+                let selector = Selector::from_slice(inst.immediate);
+                let Some(entry) = self.program.jumps.get(&jumpi.pc).and_then(|targets| {
+                    let entry = U256::from_be_slice(push.immediate);
+                    targets.iter().copied().find(|target| U256::from(*target) == entry)
+                }) else {
+                    continue;
+                };
+                if functions.iter().any(|function| function.selector == selector) {
+                    continue;
+                }
+                let stack = self.program.entry_stacks.get(&entry).cloned().unwrap_or_default();
+                functions.push(DispatchedFunction {
+                    selector,
+                    entry,
+                    selector_pc: inst.pc,
+                    body: self.explore(entry, stack),
+                });
+            }
+        }
+        functions
+    }
+
+    /// Returns the selectors of the functions of the dispatcher.
+    pub fn selectors(&self) -> impl Iterator<Item = Selector> + '_ {
+        self.functions.iter().map(|function| function.selector)
+    }
+
+    /// Renders the program as pseudo-Solidity, with a function per selector of the dispatcher.
+    ///
+    /// `signatures` maps selectors to their resolved function signature.
+    pub fn to_solidity(&self, signatures: &HashMap<Selector, String>) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "// Decompiled from {} instructions: {} reachable basic blocks, {} functions.",
+            self.instructions.len(),
+            self.program.blocks.len(),
+            self.functions.len()
+        );
+        if !self.program.unresolved.is_empty() {
+            let _ = writeln!(
+                out,
+                "// Unresolved jumps at {}.",
+                self.program.unresolved.iter().map(|pc| label(*pc)).collect::<Vec<_>>().join(", ")
+            );
+        }
+        out.push_str("contract Decompiled {\n");
+
+        let mut slots = BTreeMap::<U256, BTreeSet<&str>>::new();
+        for access in self.program.storage.values() {
+            if let Some(slot) = access.slot {
+                slots.entry(slot).or_default().insert(if access.write { "write" } else { "read" });
+            }
+        }
+        if !slots.is_empty() {
+            out.push_str("    // Storage slots accessed with a constant key:\n");
+            for (slot, kinds) in &slots {
+                let kinds = kinds.iter().copied().collect::<Vec<_>>().join(", ");
+                let _ = writeln!(out, "    //   {slot:#x}: {kinds}");
+            }
+            out.push('\n');
+        }
+
+        for (i, function) in self.functions.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let signature = signatures.get(&function.selector);
+            let _ = writeln!(
+                out,
+                "    /// Selector {}, entry at {}.",
+                function.selector,
+                label(function.entry)
+            );
+            let header = signature
+                .and_then(|signature| Function::parse(signature).ok())
+                .map(|func| {
+                    let params = func
+                        .inputs
+                        .iter()
+                        .enumerate()
+                        .map(|(i, param)| format!("{} arg{i}", param.ty))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("{}({params})", func.name)
+                })
+                .unwrap_or_else(|| format!("func_{}()", function.selector));
+            let mutability = if function.body.is_view(self) { " view" } else { "" };
+            let _ = writeln!(out, "    function {header} external{mutability} {{");
+            let mut blocks = function.body.blocks.keys().copied().collect::<Vec<_>>();
+            blocks.sort_by_key(|pc| (*pc != function.entry, *pc));
+            Renderer::new(self, &function.body).render(&blocks, &mut out);
+            out.push_str("    }\n");
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the reachable code as assembly, annotated with labels, resolved jump targets,
+    /// dispatched selectors and storage slots.
+    ///
+    /// `signatures` maps selectors to their resolved function signature.
+    pub fn to_asm(&self, signatures: &HashMap<Selector, String>) -> String {
+        let name = |selector: &Selector| {
+            signatures.get(selector).cloned().unwrap_or_else(|| selector.to_string())
+        };
+        let entries = self
+            .functions
+            .iter()
+            .map(|function| (function.entry, function))
+            .collect::<BTreeMap<_, _>>();
+        let selectors = self
+            .functions
+            .iter()
+            .map(|function| (function.selector_pc, function))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut out = String::new();
+        for pc in self.program.blocks.keys() {
+            if let Some(function) = entries.get(pc) {
+                let _ = writeln!(
+                    out,
+                    "\n; function {} [{}]",
+                    name(&function.selector),
+                    function.selector
+                );
+            }
+            let _ = writeln!(out, "{}:", label(*pc));
+            for inst in self.block(*pc) {
+                let mut line = format!("    {:08x}: ", inst.pc);
+                match inst.op {
+                    Some(op) => line.push_str(op.as_str()),
+                    None => line.push_str("INVALID"),
+                }
+                if !inst.immediate.is_empty() {
+                    let _ = write!(line, " {}", hex::encode_prefixed(inst.immediate));
+                }
+
+                let annotation = if let Some(function) = selectors.get(&inst.pc) {
+                    Some(format!("selector {}", name(&function.selector)))
+                } else if let Some(targets) = self.program.jumps.get(&inst.pc) {
+                    let targets = targets.iter().map(|pc| label(*pc)).collect::<Vec<_>>();
+                    Some(format!("-> {}", targets.join(", ")))
+                } else if self.program.unresolved.contains(&inst.pc) {
+                    Some("-> unresolved".to_string())
+                } else {
+                    self.program.storage.get(&inst.pc).map(|access| match access.slot {
+                        Some(slot) => format!("slot {slot:#x}"),
+                        None => "dynamic slot".to_string(),
+                    })
+                };
+                match annotation {
+                    Some(annotation) => {
+                        let _ = writeln!(out, "{line:<40} ; {annotation}");
+                    }
+                    None => {
+                        let _ = writeln!(out, "{line}");
+                    }
+                }
+            }
+        }
+        out.trim_start().to_string()
+    }
+}
+
+/// Renders basic blocks as pseudo-Solidity statements.
+struct Renderer<'a, 'b> {
+    decompiler: &'a Decompiler<'b>,
+    reachable: &'a Reachable,
+    /// The number of variables assigned so far.
+    vars: usize,
+}
+
+impl<'a, 'b> Renderer<'a, 'b> {
+    fn new(decompiler: &'a Decompiler<'b>, reachable: &'a Reachable) -> Self {
+        Self { decompiler, reachable, vars: 0 }
+    }
+
+    /// Renders `blocks` in order, with a label per block.
+    fn render(&mut self, blocks: &[usize], out: &mut String) {
+        for (i, pc) in blocks.iter().enumerate() {
+            let _ = writeln!(out, "      {}:", label(*pc));
+            let (mut statements, falls_through) = self.render_block(*pc);
+            if falls_through {
+                if let Some(next) = self.decompiler.next_block(*pc) {
+                    if blocks.get(i + 1) != Some(&next) {
+                        statements.push(format!("goto {};", label(next)));
+                    }
+                }
+            }
+            for statement in statements {
+                let _ = writeln!(out, "        {statement}");
+            }
+        }
+    }
+
+    /// Renders the block starting at `pc`, returning its statements and whether it falls through
+    /// to the next block.
+    fn render_block(&mut self, pc: usize) -> (Vec<String>, bool) {
+        let mut stack = ExprStack::default();
+        let mut statements = vec![];
+        for inst in self.decompiler.block(pc) {
+            let Some(op) = inst.op else {
+                statements.push("invalid();".to_string());
+                return (statements, false);
+            };
+            let code = op.get();
+            match code {
+                PUSH0..=PUSH32 => stack.push(format!("{:#x}", U256::from_be_slice(inst.immediate))),
+                DUP1..=DUP16 => {
+                    let n = (code - DUP1) as usize;
+                    stack.ensure(n + 1);
+                    let value = stack.values[stack.values.len() - 1 - n].clone();
+                    stack.push(value);
+                }
+                SWAP1..=SWAP16 => {
+                    let n = (code - SWAP1 + 1) as usize;
+                    stack.ensure(n + 1);
+                    let len = stack.values.len();
+                    stack.values.swap(len - 1, len - 1 - n);
+                }
+                POP => {
+                    stack.pop();
+                }
+                JUMP => {
+                    let target = stack.pop();
+                    statements.push(format!("goto {};", self.target(inst.pc, target)));
+                    return (statements, false);
+                }
+                JUMPI => {
+                    let target = stack.pop();
+                    let condition = stack.pop();
+                    statements
+                        .push(format!("if ({condition}) goto {};", self.target(inst.pc, target)));
+                }
+                JUMPDEST => {}
+                SLOAD => {
+                    let slot = stack.pop();
+                    let value = self.assign(format!("storage[{slot}]"), &mut statements);
+                    stack.push(value);
+                }
+                SSTORE => {
+                    let (slot, value) = (stack.pop(), stack.pop());
+                    statements.push(format!("storage[{slot}] = {value};"));
+                }
+                MLOAD => {
+                    let offset = stack.pop();
+                    let value = self.assign(format!("memory[{offset}]"), &mut statements);
+                    stack.push(value);
+                }
+                MSTORE => {
+                    let (offset, value) = (stack.pop(), stack.pop());
+                    statements.push(format!("memory[{offset}] = {value};"));
+                }
+                KECCAK256 => {
+                    let (offset, len) = (stack.pop(), stack.pop());
+                    let expr = format!("keccak256({})", memory_range(&offset, &len));
+                    self.push(&mut stack, expr, &mut statements);
+                }
+                STOP => {
+                    statements.push("return;".to_string());
+                    return (statements, false);
+                }
+                RETURN | REVERT => {
+                    let (offset, len) = (stack.pop(), stack.pop());
+                    let range = memory_range(&offset, &len);
+                    statements.push(if code == RETURN {
+                        format!("return {range};")
+                    } else {
+                        format!("revert({range});")
+                    });
+                    return (statements, false);
+                }
+                LOG0..=LOG4 => {
+                    let (offset, len) = (stack.pop(), stack.pop());
+                    let mut args = vec![memory_range(&offset, &len)];
+                    args.extend((0..code - LOG0).map(|_| stack.pop()));
+                    statements.push(format!("log{}({});", code - LOG0, args.join(", ")));
+                }
+                ISZERO => {
+                    let value = stack.pop();
+                    stack.push(format!("!{value}"));
+                }
+                NOT => {
+                    let value = stack.pop();
+                    stack.push(format!("~{value}"));
+                }
+                _ => {
+                    if let Some(symbol) = infix(code) {
+                        let (a, b) = (stack.pop(), stack.pop());
+                        let expr = match code {
+                            // The shift amount is on top of the stack.
+                            SHL | SHR => format!("({b} {symbol} {a})"),
+                            _ => format!("({a} {symbol} {b})"),
+                        };
+                        self.push(&mut stack, expr, &mut statements);
+                    } else if let Some(name) = environment(code) {
+                        stack.push(name.to_string());
+                    } else {
+                        let args = (0..op.inputs()).map(|_| stack.pop()).collect::<Vec<_>>();
+                        let expr = format!("{}({})", op.as_str().to_lowercase(), args.join(", "));
+                        match op.outputs() {
+                            0 => statements.push(format!("{expr};")),
+                            _ if has_side_effects(code) => {
+                                let value = self.assign(expr, &mut statements);
+                                stack.push(value);
+                            }
+                            _ => self.push(&mut stack, expr, &mut statements),
+                        }
+                        if is_block_end(code) {
+                            return (statements, false);
+                        }
+                    }
+                }
+            }
+        }
+        (statements, true)
+    }
+
+    /// Renders the target of the jump at `pc`.
+    fn target(&self, pc: usize, expr: String) -> String {
+        match self.reachable.jumps.get(&pc) {
+            Some(targets) if targets.len() == 1 => label(*targets.first().unwrap()),
+            Some(targets) => {
+                let targets = targets.iter().map(|pc| label(*pc)).collect::<Vec<_>>();
+                format!("{expr} /* {} */", targets.join(" | "))
+            }
+            None => expr,
+        }
+    }
+
+    /// Pushes `expr` on the stack, assigning it to a variable first if it is too long.
+    fn push(&mut self, stack: &mut ExprStack, expr: String, statements: &mut Vec<String>) {
+        let value = if expr.len() > MAX_EXPR_LEN { self.assign(expr, statements) } else { expr };
+        stack.push(value);
+    }
+
+    /// Assigns `expr` to a new variable, returning its name.
+    fn assign(&mut self, expr: String, statements: &mut Vec<String>) -> String {
+        let var = format!("v{}", self.vars);
+        self.vars += 1;
+        statements.push(format!("uint256 {var} = {expr};"));
+        var
+    }
+}
+
+/// A stack of rendered expressions.
+///
+/// Values which were on the stack when the block was entered are rendered as `stack[i]`, `i`
+/// being their depth at the start of the block.
+#[derive(Default)]
+struct ExprStack {
+    values: Vec<String>,
+    /// The number of values from the entry stack accessed so far.
+    below: usize,
+}
+
+impl ExprStack {
+    fn ensure(&mut self, depth: usize) {
+        while self.values.len() < depth {
+            self.values.insert(0, format!("stack[{}]", self.below));
+            self.below += 1;
+        }
+    }
+
+    fn pop(&mut self) -> String {
+        self.ensure(1);
+        self.values.pop().unwrap()
+    }
+
+    fn push(&mut self, value: String) {
+        self.values.push(value);
+    }
+}
+
+/// Executes `op` on the symbolic stack, folding the arithmetic used to compute jump targets.
+fn step(stack: &mut Stack, op: u8, immediate: &[u8]) {
+    match op {
+        PUSH0..=PUSH32 => push(stack, Some(U256::from_be_slice(immediate))),
+        DUP1..=DUP16 => {
+            let n = (op - DUP1 + 1) as usize;
+            ensure(stack, n);
+            let value = stack[stack.len() - n];
+            push(stack, value);
+        }
+        SWAP1..=SWAP16 => {
+            let n = (op - SWAP1 + 1) as usize;
+            ensure(stack, n + 1);
+            let len = stack.len();
+            stack.swap(len - 1, len - 1 - n);
+        }
+        ADD | SUB | MUL | AND | OR | XOR | SHL | SHR => {
+            let (a, b) = (pop(stack), pop(stack));
+            let value = a.zip(b).map(|(a, b)| match op {
+                ADD => a.wrapping_add(b),
+                SUB => a.wrapping_sub(b),
+                MUL => a.wrapping_mul(b),
+                AND => a & b,
+                OR => a | b,
+                XOR => a ^ b,
+                _ if a >= U256::from(256) => U256::ZERO,
+                SHL => b << a.to::<usize>(),
+                _ => b >> a.to::<usize>(),
+            });
+            push(stack, value);
+        }
+        _ => {
+            let Some(op) = OpCode::new(op) else { return };
+            for _ in 0..op.inputs() {
+                pop(stack);
+            }
+            for _ in 0..op.outputs() {
+                push(stack, None);
+            }
+        }
+    }
+}
+
+fn pop(stack: &mut Stack) -> Option<U256> {
+    stack.pop().flatten()
+}
+
+fn push(stack: &mut Stack, value: Option<U256>) {
+    stack.push(value);
+    if stack.len() > MAX_STACK {
+        stack.remove(0);
+    }
+}
+
+/// Pads the bottom of the stack with unknown values, up to `depth` values.
+fn ensure(stack: &mut Stack, depth: usize) {
+    while stack.len() < depth {
+        stack.insert(0, None);
+    }
+}
+
+/// Returns the length of the longest prefix of `code` made of complete instructions.
+fn complete_len(code: &[u8]) -> usize {
+    let mut pc = 0;
+    while pc < code.len() {
+        let next = pc +
+            1 +
+            if (PUSH1..=PUSH32).contains(&code[pc]) { (code[pc] - PUSH0) as usize } else { 0 };
+        if next > code.len() {
+            break;
+        }
+        pc = next;
+    }
+    pc
+}
+
+/// Returns whether `op` ends a basic block.
+fn is_block_end(op: u8) -> bool {
+    matches!(op, JUMP | JUMPI | STOP | RETURN | REVERT | INVALID | SELFDESTRUCT) ||
+        OpCode::new(op).is_none()
+}
+
+/// Returns whether `op` may modify state.
+fn modifies_state(op: u8) -> bool {
+    matches!(op, SSTORE | TSTORE | CALL | CALLCODE | DELEGATECALL | CREATE | CREATE2 | SELFDESTRUCT) ||
+        (LOG0..=LOG4).contains(&op)
+}
+
+/// Returns whether `op` has effects which must be ordered with the surrounding statements.
+fn has_side_effects(op: u8) -> bool {
+    matches!(
+        op,
+        CALL | CALLCODE | DELEGATECALL | STATICCALL | CREATE | CREATE2 | TLOAD | RETURNDATASIZE
+    )
+}
+
+/// Returns the infix operator of a binary opcode.
+fn infix(op: u8) -> Option<&'static str> {
+    Some(match op {
+        ADD => "+",
+        SUB => "-",
+        MUL => "*",
+        DIV => "/",
+        MOD => "%",
+        EXP => "**",
+        LT => "<",
+        GT => ">",
+        EQ => "==",
+        AND => "&",
+        OR => "|",
+        XOR => "^",
+        SHL => "<<",
+        SHR => ">>",
+        _ => return None,
+    })
+}
+
+/// Returns the Solidity expression of an opcode reading the environment.
+fn environment(op: u8) -> Option<&'static str> {
+    Some(match op {
+        ADDRESS => "address(this)",
+        ORIGIN => "tx.origin",
+        CALLER => "msg.sender",
+        CALLVALUE => "msg.value",
+        CALLDATASIZE => "msg.data.length",
+        GASPRICE => "tx.gasprice",
+        COINBASE => "block.coinbase",
+        TIMESTAMP => "block.timestamp",
+        NUMBER => "block.number",
+        CHAINID => "block.chainid",
+        BASEFEE => "block.basefee",
+        SELFBALANCE => "address(this).balance",
+        GAS => "gasleft()",
+        _ => return None,
+    })
+}
+
+/// Renders a memory range.
+fn memory_range(offset: &str, len: &str) -> String {
+    format!("memory[{offset}:{offset} + {len}]")
+}
+
+/// Returns the label of the block starting at `pc`.
+fn label(pc: usize) -> String {
+    format!("label_{pc:04x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A dispatcher for `0xaabbccdd`, which writes 42 to slot 1, and `0x11223344`, which calls an
+    // internal function reading slot 0 and returning to its caller.
+    const CODE: &str = "60003560e01c8063aabbccdd14601e57806311223344146025576000\
+                        80fd5b602a600155005b602b602d565b005b6000545056";
+
+    #[test]
+    fn recovers_control_flow() {
+        let code = hex::decode(CODE).unwrap();
+        let decompiler = Decompiler::new(&code).unwrap();
+        let program = decompiler.program();
+
+        assert!(program.unresolved.is_empty());
+        // The internal function returns to the address pushed by its caller.
+        assert_eq!(program.jumps[&0x2a], BTreeSet::from([0x2d]));
+        assert_eq!(program.jumps[&0x32], BTreeSet::from([0x2b]));
+        assert_eq!(
+            program.storage[&0x23],
+            StorageAccess { write: true, slot: Some(U256::from(1)) }
+        );
+        assert_eq!(program.storage[&0x30], StorageAccess { write: false, slot: Some(U256::ZERO) });
+
+        let functions = decompiler.functions();
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].selector, Selector::from([0xaa, 0xbb, 0xcc, 0xdd]));
+        assert_eq!(functions[0].entry, 0x1e);
+        assert!(!functions[0].body.is_view(&decompiler));
+        assert_eq!(functions[1].selector, Selector::from([0x11, 0x22, 0x33, 0x44]));
+        assert_eq!(functions[1].entry, 0x25);
+        assert_eq!(
+            functions[1].body.blocks.keys().copied().collect::<Vec<_>>(),
+            [0x25, 0x2b, 0x2d]
+        );
+        assert!(functions[1].body.is_view(&decompiler));
+    }
+
+    #[test]
+    fn renders_solidity_and_asm() {
+        let code = hex::decode(CODE).unwrap();
+        let decompiler = Decompiler::new(&code).unwrap();
+        let signatures = HashMap::from_iter([(
+            Selector::from([0xaa, 0xbb, 0xcc, 0xdd]),
+            "setValue(uint256)".to_string(),
+        )]);
+
+        let solidity = decompiler.to_solidity(&signatures);
+        assert!(solidity.contains("function setValue(uint256 arg0) external {"));
+        assert!(solidity.contains("storage[0x1] = 0x2a;"));
+        assert!(solidity.contains("function func_0x11223344() external view {"));
+        assert!(solidity.contains("uint256 v0 = storage[0x0];"));
+
+        let asm = decompiler.to_asm(&signatures);
+        assert!(asm.contains("; function setValue(uint256) [0xaabbccdd]"));
+        assert!(asm.contains("; selector setValue(uint256)"));
+        assert!(asm.contains("; -> label_002b"));
+        assert!(asm.contains("; slot 0x1"));
+    }
+
+    #[test]
+    fn ignores_truncated_trailing_bytes() {
+        let mut code = hex::decode(CODE).unwrap();
+        code.extend_from_slice(&[PUSH32, 0x01]);
+        let decompiler = Decompiler::new(&code).unwrap();
+        assert_eq!(decompiler.functions().len(), 2);
+    }
+}
@@ -51,8 +51,14 @@
 pub use foundry_evm::*;
 
 pub mod base;
+pub mod decompile;
 pub mod errors;
+pub mod mapping_slot;
+pub mod proof;
 mod rlp_converter;
+pub mod safe;
+pub mod state_diff;
+pub mod trace_diff;
 
 use rlp_converter::Item;
 
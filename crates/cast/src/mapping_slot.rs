@@ -0,0 +1,166 @@
+//! Recovery of the mapping slots read by a call.
+
+use crate::{revm::interpreter::OpCode, traces::CallTraceArena};
+use alloy_primitives::{keccak256, map::HashMap, Address, Bytes, B256};
+use serde::Serialize;
+use std::fmt;
+
+/// A storage slot read by a call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageRead {
+    /// The account whose storage was read.
+    pub address: Address,
+    pub slot: B256,
+}
+
+/// Returns the storage reads recorded in the steps of a trace, without duplicates.
+pub fn storage_reads(arena: &CallTraceArena) -> Vec<StorageRead> {
+    let mut reads = Vec::<StorageRead>::new();
+    let steps = arena.nodes().iter().flat_map(|node| &node.trace.steps);
+    for step in steps.filter(|step| step.op == OpCode::SLOAD) {
+        let Some(slot) = step.stack.as_ref().and_then(|stack| stack.last()) else { continue };
+        let read = StorageRead { address: step.contract, slot: (*slot).into() };
+        if !reads.contains(&read) {
+            reads.push(read);
+        }
+    }
+    reads
+}
+
+/// Returns the preimages of the keccak256 hashes computed in the steps of a trace.
+pub fn keccak_preimages(arena: &CallTraceArena) -> HashMap<B256, Bytes> {
+    let mut preimages = HashMap::default();
+    let steps = arena.nodes().iter().flat_map(|node| &node.trace.steps);
+    for step in steps.filter(|step| step.op == OpCode::KECCAK256) {
+        let (Some(stack), Some(memory)) = (&step.stack, &step.memory) else { continue };
+        let [.., size, offset] = stack.as_slice() else { continue };
+        let (Ok(offset), Ok(size)) = (usize::try_from(*offset), usize::try_from(*size)) else {
+            continue;
+        };
+        // Memory is expanded with zeroes by the hashing itself.
+        let mut preimage = vec![0; size];
+        let memory = memory.as_bytes();
+        if offset < memory.len() {
+            let end = memory.len().min(offset.saturating_add(size));
+            preimage[..end - offset].copy_from_slice(&memory[offset..end]);
+        }
+        preimages.insert(keccak256(&preimage), preimage.into());
+    }
+    preimages
+}
+
+/// The order in which a mapping key and the mapping slot are hashed to get the slot of a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MappingLayout {
+    /// `keccak256(key . slot)`, used by Solidity.
+    Solidity,
+    /// `keccak256(slot . key)`, used by Vyper.
+    Vyper,
+}
+
+impl fmt::Display for MappingLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solidity => f.write_str("Solidity (keccak256(key . slot))"),
+            Self::Vyper => f.write_str("Vyper (keccak256(slot . key))"),
+        }
+    }
+}
+
+/// The storage slot of a mapping, possibly nested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct MappingSlot {
+    /// The slot of the mapping itself.
+    pub slot: B256,
+    pub layout: MappingLayout,
+}
+
+impl MappingSlot {
+    /// Finds the mapping which `slot` is the value of for the given keys, outermost key first,
+    /// by unwinding the keccak256 preimages of `slot`.
+    pub fn resolve(slot: B256, keys: &[B256], preimages: &HashMap<B256, Bytes>) -> Option<Self> {
+        let mut slot = slot;
+        let mut layout = None;
+        for key in keys.iter().rev() {
+            let preimage = preimages.get(&slot).filter(|preimage| preimage.len() == 64)?;
+            let (first, second) = preimage.split_at(32);
+            let (found, parent) = if first == key.as_slice() {
+                (MappingLayout::Solidity, second)
+            } else if second == key.as_slice() {
+                (MappingLayout::Vyper, first)
+            } else {
+                return None;
+            };
+            if layout.is_some_and(|layout| layout != found) {
+                return None;
+            }
+            layout = Some(found);
+            slot = B256::from_slice(parent);
+        }
+        Some(Self { slot, layout: layout? })
+    }
+
+    /// Returns the slot of the value of the mapping for the given keys, outermost key first.
+    pub fn value_slot(&self, keys: &[B256]) -> B256 {
+        keys.iter().fold(self.slot, |slot, key| match self.layout {
+            MappingLayout::Solidity => keccak256([key.as_slice(), slot.as_slice()].concat()),
+            MappingLayout::Vyper => keccak256([slot.as_slice(), key.as_slice()].concat()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    fn preimages(mapping: &MappingSlot, keys: &[B256]) -> HashMap<B256, Bytes> {
+        let mut preimages = HashMap::default();
+        let mut slot = mapping.slot;
+        for key in keys {
+            let preimage = match mapping.layout {
+                MappingLayout::Solidity => [key.as_slice(), slot.as_slice()].concat(),
+                MappingLayout::Vyper => [slot.as_slice(), key.as_slice()].concat(),
+            };
+            slot = keccak256(&preimage);
+            preimages.insert(slot, preimage.into());
+        }
+        preimages
+    }
+
+    #[test]
+    fn resolves_mapping_slots() {
+        let owner = address!("D0074F4E6490ae3f888d1d4f7E3E43326bD3f0f5").into_word();
+        let spender = address!("000000000000000000000000000000000000dEaD").into_word();
+
+        for layout in [MappingLayout::Solidity, MappingLayout::Vyper] {
+            let mapping = MappingSlot { slot: B256::with_last_byte(2), layout };
+            let balance = mapping.value_slot(&[owner]);
+            let allowance = mapping.value_slot(&[owner, spender]);
+            assert_eq!(
+                MappingSlot::resolve(balance, &[owner], &preimages(&mapping, &[owner])),
+                Some(mapping)
+            );
+            assert_eq!(
+                MappingSlot::resolve(
+                    allowance,
+                    &[owner, spender],
+                    &preimages(&mapping, &[owner, spender])
+                ),
+                Some(mapping)
+            );
+            assert_eq!(
+                MappingSlot::resolve(balance, &[spender], &preimages(&mapping, &[owner])),
+                None
+            );
+        }
+
+        // Matches `cast index address 0xD0074F4E6490ae3f888d1d4f7E3E43326bD3f0f5 2`.
+        assert_eq!(
+            MappingSlot { slot: B256::with_last_byte(2), layout: MappingLayout::Solidity }
+                .value_slot(&[owner]),
+            b256!("9525a448a9000053a4d151336329d6563b7e80b24f8e628e95527f218e8ab5fb")
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Local verification of `eth_getProof` responses against a state root.
+
+use crate::revm::primitives::KECCAK_EMPTY;
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_rpc_types::EIP1186AccountProofResponse;
+use anvil_core::eth::{
+    proof::BasicAccount,
+    trie::{verify_proof, KECCAK_NULL_RLP},
+};
+
+pub use anvil_core::eth::trie::ProofError;
+
+/// The result of verifying the account and storage proofs of an `eth_getProof` response.
+#[derive(Debug)]
+pub struct ProofVerification {
+    /// The verification of the account against the state root.
+    pub account: Result<(), ProofError>,
+    /// The verification of each storage slot against the storage root of the account.
+    pub storage: Vec<(B256, Result<(), ProofError>)>,
+}
+
+impl ProofVerification {
+    /// Verifies the proofs of an `eth_getProof` response against the state root of its block.
+    ///
+    /// Empty accounts and zero storage values are proven either by their absence from the trie,
+    /// or by a leaf holding them.
+    pub fn new(state_root: B256, proof: &EIP1186AccountProofResponse) -> Self {
+        // Nodes may return zero hashes for the code and storage of missing accounts.
+        let storage_root =
+            if proof.storage_hash.is_zero() { KECCAK_NULL_RLP } else { proof.storage_hash };
+        let code_hash = if proof.code_hash.is_zero() { KECCAK_EMPTY } else { proof.code_hash };
+        let account = BasicAccount {
+            nonce: U256::from(proof.nonce),
+            balance: proof.balance,
+            storage_root,
+            code_hash,
+        };
+        let is_empty = account == BasicAccount::default();
+        let account = verify_value(
+            state_root,
+            proof.address,
+            alloy_rlp::encode(&account),
+            is_empty,
+            &proof.account_proof,
+        );
+
+        let storage = proof
+            .storage_proof
+            .iter()
+            .map(|slot| {
+                let key = slot.key.as_b256();
+                let value = alloy_rlp::encode(slot.value);
+                (key, verify_value(storage_root, key, value, slot.value.is_zero(), &slot.proof))
+            })
+            .collect();
+
+        Self { account, storage }
+    }
+
+    /// Returns `true` if the account and all the storage slots are proven.
+    pub fn is_valid(&self) -> bool {
+        self.account.is_ok() && self.storage.iter().all(|(_, result)| result.is_ok())
+    }
+}
+
+/// Verifies the proof of a value, which may also be absent from the trie if it's empty.
+fn verify_value(
+    root: B256,
+    key: impl AsRef<[u8]>,
+    value: Vec<u8>,
+    is_empty: bool,
+    nodes: &[Bytes],
+) -> Result<(), ProofError> {
+    let result = verify_proof(root, &key, Some(value), nodes);
+    if result.is_err() && is_empty && verify_proof(root, &key, None, nodes).is_ok() {
+        return Ok(());
+    }
+    result
+}
@@ -0,0 +1,302 @@
+//! Offline building and signing of [Safe](https://github.com/safe-global/safe-smart-account)
+//! multisig transactions.
+
+use alloy_primitives::{eip191_hash_message, Address, Bytes, PrimitiveSignature, B256, U256};
+use alloy_sol_types::{sol, Eip712Domain, SolCall, SolStruct};
+use eyre::Result;
+use std::{fmt, str::FromStr};
+
+sol! {
+    /// A Safe transaction, as hashed with EIP-712 by the Safe.
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+
+    interface ISafe {
+        function execTransaction(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            bytes memory signatures
+        ) external payable returns (bool success);
+    }
+
+    interface IMultiSend {
+        function multiSend(bytes memory transactions) external payable;
+    }
+}
+
+/// The operation of a Safe transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Operation {
+    #[default]
+    Call,
+    DelegateCall,
+}
+
+impl FromStr for Operation {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "call" | "0" => Ok(Self::Call),
+            "delegatecall" | "delegate-call" | "1" => Ok(Self::DelegateCall),
+            _ => eyre::bail!("invalid operation `{s}`, expected `call` or `delegatecall`"),
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Call => f.write_str("call"),
+            Self::DelegateCall => f.write_str("delegatecall"),
+        }
+    }
+}
+
+impl From<Operation> for u8 {
+    fn from(operation: Operation) -> Self {
+        operation as Self
+    }
+}
+
+impl SafeTx {
+    /// Returns the EIP-712 domain of a Safe.
+    ///
+    /// Safes before version 1.3.0 don't include the chain id in their domain.
+    pub fn domain(safe: Address, chain_id: u64, legacy: bool) -> Eip712Domain {
+        Eip712Domain {
+            chain_id: (!legacy).then(|| U256::from(chain_id)),
+            verifying_contract: Some(safe),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the `safeTxHash` of the transaction, which is signed by the owners of the Safe.
+    pub fn hash(&self, domain: &Eip712Domain) -> B256 {
+        self.eip712_signing_hash(domain)
+    }
+
+    /// Returns the calldata of the `execTransaction` call executing the transaction with the
+    /// given packed signatures.
+    pub fn exec_calldata(&self, signatures: Bytes) -> Bytes {
+        ISafe::execTransactionCall {
+            to: self.to,
+            value: self.value,
+            data: self.data.clone(),
+            operation: self.operation,
+            safeTxGas: self.safeTxGas,
+            baseGas: self.baseGas,
+            gasPrice: self.gasPrice,
+            gasToken: self.gasToken,
+            refundReceiver: self.refundReceiver,
+            signatures,
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+/// A transaction of a `MultiSend` batch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiSendTx {
+    pub operation: Operation,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// Returns the calldata of the `multiSend` call executing the given transactions.
+///
+/// The Safe transaction must delegatecall a `MultiSend` or `MultiSendCallOnly` contract with
+/// this calldata.
+pub fn multi_send_calldata(txs: &[MultiSendTx]) -> Bytes {
+    let mut transactions = Vec::new();
+    for tx in txs {
+        transactions.push(tx.operation.into());
+        transactions.extend_from_slice(tx.to.as_slice());
+        transactions.extend_from_slice(&tx.value.to_be_bytes::<32>());
+        transactions.extend_from_slice(&U256::from(tx.data.len()).to_be_bytes::<32>());
+        transactions.extend_from_slice(&tx.data);
+    }
+    IMultiSend::multiSendCall { transactions: transactions.into() }.abi_encode().into()
+}
+
+/// Returns the Safe encoding of an owner signature of a `safeTxHash`.
+pub fn encode_signature(signature: &PrimitiveSignature) -> Bytes {
+    signature.as_bytes().into()
+}
+
+/// Returns the owner who produced a signature of `safe_tx_hash`.
+///
+/// Supports ECDSA signatures of the hash (`v` is 27 or 28), `eth_sign` signatures of the hash
+/// (`v` is 31 or 32) and pre-approved hashes (`v` is 1), where `r` holds the owner.
+pub fn signature_owner(safe_tx_hash: B256, signature: &[u8]) -> Result<Address> {
+    if signature.len() != 65 {
+        eyre::bail!("invalid signature length {}, expected 65 bytes", signature.len());
+    }
+    let (rs, v) = (&signature[..64], signature[64]);
+    let (hash, v) = match v {
+        1 => return Ok(Address::from_slice(&rs[12..32])),
+        27 | 28 => (safe_tx_hash, v),
+        31 | 32 => (eip191_hash_message(safe_tx_hash), v - 4),
+        0 => eyre::bail!("contract signatures are not supported"),
+        v => eyre::bail!("invalid signature type {v}"),
+    };
+    let signature = PrimitiveSignature::from_bytes_and_parity(rs, v == 28);
+    Ok(signature.recover_address_from_prehash(&hash)?)
+}
+
+/// Combines the owner signatures of `safe_tx_hash` into the signatures argument of
+/// `execTransaction`, sorted by owner as required by the Safe.
+pub fn combine_signatures(safe_tx_hash: B256, signatures: &[Bytes]) -> Result<Bytes> {
+    let mut owners = Vec::with_capacity(signatures.len());
+    for signature in signatures {
+        let owner = signature_owner(safe_tx_hash, signature)?;
+        if owners.iter().any(|(o, _)| *o == owner) {
+            eyre::bail!("duplicate signature of owner {owner}");
+        }
+        owners.push((owner, signature));
+    }
+    owners.sort_by_key(|(owner, _)| *owner);
+    Ok(owners.into_iter().flat_map(|(_, signature)| signature.iter().copied()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, hex, keccak256};
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    fn safe_tx() -> SafeTx {
+        SafeTx {
+            to: address!("000000000000000000000000000000000000dEaD"),
+            value: U256::from(1),
+            data: hex!("a9059cbb").into(),
+            nonce: U256::from(7),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn domain_separator() {
+        let safe = address!("1111111111111111111111111111111111111111");
+        let domain = SafeTx::domain(safe, 1, false);
+        let typehash = keccak256("EIP712Domain(uint256 chainId,address verifyingContract)");
+        let expected = keccak256(
+            [typehash.as_slice(), &U256::from(1).to_be_bytes::<32>(), safe.into_word().as_slice()]
+                .concat(),
+        );
+        assert_eq!(domain.separator(), expected);
+
+        let domain = SafeTx::domain(safe, 1, true);
+        let typehash = keccak256("EIP712Domain(address verifyingContract)");
+        let expected = keccak256([typehash.as_slice(), safe.into_word().as_slice()].concat());
+        assert_eq!(domain.separator(), expected);
+
+        assert_eq!(
+            SafeTx::eip712_type_hash(&safe_tx()),
+            keccak256(
+                "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,\
+                 uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,\
+                 uint256 nonce)"
+            )
+        );
+    }
+
+    // A DAI transfer of 1 ether to 0xdEaD, with the type hashes of `Safe.sol`, hashed with
+    // keccak-256 independently of the EIP-712 encoder.
+    #[test]
+    fn safe_tx_hash_vector() {
+        let safe = address!("1111111111111111111111111111111111111111");
+        let tx = SafeTx {
+            to: address!("6B175474E89094C44Da98b954EedeAC495271d0F"),
+            data: hex!(
+                "a9059cbb000000000000000000000000000000000000000000000000000000000000dead"
+                "0000000000000000000000000000000000000000000000000de0b6b3a7640000"
+            )
+            .into(),
+            nonce: U256::from(7),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            SafeTx::domain(safe, 1, false).separator(),
+            b256!("f0dcfe86ad4a409690a57dbaae9b1e14c5ea1750a48271a0a3a6037a8100624d")
+        );
+        assert_eq!(
+            tx.hash(&SafeTx::domain(safe, 1, false)),
+            b256!("68de966d4e0c4b227b6dd03a4b8e9df895c506ef0beab9ee010202aef7f458e3")
+        );
+        assert_eq!(
+            tx.hash(&SafeTx::domain(safe, 1, true)),
+            b256!("93d5a4480fd080f3dff5933f082b193bfa1cc544bcfc7ac91794954cbe40ab7e")
+        );
+    }
+
+    #[test]
+    fn combines_signatures() {
+        let hash = safe_tx().hash(&SafeTx::domain(Address::repeat_byte(0x11), 1, false));
+        let mut signers = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+        let ecdsa = encode_signature(&signers[0].sign_hash_sync(&hash).unwrap());
+        let mut eth_sign = signers[1].sign_message_sync(hash.as_slice()).unwrap().as_bytes();
+        eth_sign[64] += 4;
+        let eth_sign = Bytes::from(eth_sign);
+
+        assert_eq!(signature_owner(hash, &ecdsa).unwrap(), signers[0].address());
+        assert_eq!(signature_owner(hash, &eth_sign).unwrap(), signers[1].address());
+
+        let combined = combine_signatures(hash, &[ecdsa.clone(), eth_sign.clone()]).unwrap();
+        let reversed = combine_signatures(hash, &[eth_sign, ecdsa.clone()]).unwrap();
+        assert_eq!(combined, reversed);
+        signers.sort_by_key(|signer| signer.address());
+        assert_eq!(signature_owner(hash, &combined[..65]).unwrap(), signers[0].address());
+        assert_eq!(signature_owner(hash, &combined[65..]).unwrap(), signers[1].address());
+
+        assert!(combine_signatures(hash, &[ecdsa.clone(), ecdsa]).is_err());
+    }
+
+    #[test]
+    fn encodes_multi_send() {
+        let txs = [
+            MultiSendTx {
+                to: Address::repeat_byte(0xaa),
+                value: U256::from(2),
+                ..Default::default()
+            },
+            MultiSendTx {
+                operation: Operation::DelegateCall,
+                to: Address::repeat_byte(0xbb),
+                data: hex!("12345678").into(),
+                ..Default::default()
+            },
+        ];
+        let calldata = multi_send_calldata(&txs);
+        let transactions =
+            IMultiSend::multiSendCall::abi_decode(&calldata, true).unwrap().transactions;
+        assert_eq!(transactions.len(), 2 * (1 + 20 + 32 + 32) + 4);
+        assert_eq!(transactions[0], 0);
+        assert_eq!(&transactions[1..21], Address::repeat_byte(0xaa).as_slice());
+        assert_eq!(transactions[52], 2);
+        assert_eq!(transactions[85], 1);
+        assert_eq!(&transactions[transactions.len() - 4..], hex!("12345678"));
+    }
+}
@@ -0,0 +1,380 @@
+//! State diffs of executed transactions.
+
+use crate::revm::{
+    primitives::{AccountInfo, EvmState, KECCAK_EMPTY},
+    DatabaseRef,
+};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use eyre::Result;
+use foundry_compilers::artifacts::{Storage, StorageLayout};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt};
+
+/// A change of a value from `from` to `to`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Change<T> {
+    pub from: T,
+    pub to: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    /// Returns the change, if the value changed.
+    fn new(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
+
+/// A variable stored, entirely or in part, in a storage slot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SlotVariable {
+    /// The name of the variable, including the path to struct members and array elements.
+    pub label: String,
+    /// The type of the variable.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The offset of the variable in the slot, in bytes.
+    pub offset: usize,
+    /// The value of the variable, before and after the transaction.
+    #[serde(flatten)]
+    pub value: Change<B256>,
+}
+
+/// A change of a storage slot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SlotDiff {
+    #[serde(flatten)]
+    pub value: Change<B256>,
+    /// The variables stored in the slot, if decoded with a storage layout.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<SlotVariable>,
+}
+
+/// The changes of an account.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AccountDiff {
+    /// The name of the contract, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balance: Option<Change<U256>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Change<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Change<Bytes>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, SlotDiff>,
+}
+
+impl AccountDiff {
+    /// Returns whether the account didn't change.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none() &&
+            self.nonce.is_none() &&
+            self.code.is_none() &&
+            self.storage.is_empty()
+    }
+
+    /// Decodes the changed storage slots into variables of `layout`.
+    ///
+    /// Only variables at statically known slots are decoded: values of mappings and dynamic arrays
+    /// live at hashed slots which can't be traced back to their variable.
+    pub fn decode_storage(&mut self, layout: &StorageLayout) {
+        let mut variables = vec![];
+        for storage in &layout.storage {
+            flatten_variable(layout, storage, storage.label.clone(), U256::ZERO, &mut variables);
+        }
+
+        for (slot, diff) in &mut self.storage {
+            let slot = U256::from_be_bytes(slot.0);
+            diff.variables = variables
+                .iter()
+                .filter(|variable| variable.slot == slot)
+                .map(|variable| SlotVariable {
+                    label: variable.label.clone(),
+                    ty: variable.ty.clone(),
+                    offset: variable.offset,
+                    value: Change {
+                        from: variable.extract(diff.value.from),
+                        to: variable.extract(diff.value.to),
+                    },
+                })
+                .collect();
+        }
+    }
+}
+
+/// The changes of the accounts touched by a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct StateDiff(pub BTreeMap<Address, AccountDiff>);
+
+impl StateDiff {
+    /// Computes the changes of `state` over the state of `db`, as it was before the transaction.
+    pub fn new<DB>(db: &DB, state: &EvmState) -> Result<Self>
+    where
+        DB: DatabaseRef,
+        DB::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let mut diff = BTreeMap::new();
+        for (address, account) in state {
+            if !account.is_touched() {
+                continue;
+            }
+
+            let before = db.basic_ref(*address)?.unwrap_or_default();
+            let code_before = match before.code {
+                Some(code) => code.original_bytes(),
+                None if before.code_hash == KECCAK_EMPTY => Bytes::new(),
+                None => db.code_by_hash_ref(before.code_hash)?.original_bytes(),
+            };
+            let destroyed = AccountInfo::default();
+            let after = if account.is_selfdestructed() { &destroyed } else { &account.info };
+            let code_after =
+                after.code.as_ref().map(|code| code.original_bytes()).unwrap_or_else(|| {
+                    if after.code_hash == before.code_hash {
+                        code_before.clone()
+                    } else {
+                        Bytes::new()
+                    }
+                });
+
+            let account_diff = AccountDiff {
+                contract: None,
+                balance: Change::new(before.balance, after.balance),
+                nonce: Change::new(before.nonce, after.nonce),
+                code: Change::new(code_before, code_after),
+                storage: account
+                    .changed_storage_slots()
+                    .filter_map(|(slot, value)| {
+                        Change::new(value.original_value, value.present_value).map(|value| {
+                            let value = Change { from: value.from.into(), to: value.to.into() };
+                            (B256::from(*slot), SlotDiff { value, variables: vec![] })
+                        })
+                    })
+                    .collect(),
+            };
+            if !account_diff.is_empty() {
+                diff.insert(*address, account_diff);
+            }
+        }
+        Ok(Self(diff))
+    }
+}
+
+impl fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (address, diff)) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            match &diff.contract {
+                Some(contract) => writeln!(f, "{address} ({contract}):")?,
+                None => writeln!(f, "{address}:")?,
+            }
+            if let Some(Change { from, to }) = &diff.balance {
+                writeln!(f, "  balance: {from} -> {to}")?;
+            }
+            if let Some(Change { from, to }) = &diff.nonce {
+                writeln!(f, "  nonce: {from} -> {to}")?;
+            }
+            if let Some(Change { from, to }) = &diff.code {
+                writeln!(f, "  code: {} bytes -> {} bytes", from.len(), to.len())?;
+            }
+            for (slot, slot_diff) in &diff.storage {
+                let Change { from, to } = &slot_diff.value;
+                writeln!(f, "  @ {slot}: {from} -> {to}")?;
+                for variable in &slot_diff.variables {
+                    let Change { from, to } = &variable.value;
+                    writeln!(
+                        f,
+                        "    {} ({}): {} -> {}",
+                        variable.label,
+                        variable.ty,
+                        U256::from_be_bytes(from.0),
+                        U256::from_be_bytes(to.0)
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A variable of a storage layout, located in a single slot.
+struct LayoutVariable {
+    label: String,
+    ty: String,
+    slot: U256,
+    offset: usize,
+    size: usize,
+}
+
+impl LayoutVariable {
+    /// Extracts the value of the variable from the value of its slot.
+    fn extract(&self, slot: B256) -> B256 {
+        let end = 32 - self.offset;
+        let start = end.saturating_sub(self.size);
+        let mut value = B256::ZERO;
+        value[32 - (end - start)..].copy_from_slice(&slot[start..end]);
+        value
+    }
+}
+
+/// Flattens a variable of a storage layout into the single-slot variables it is made of,
+/// descending into structs and static arrays.
+fn flatten_variable(
+    layout: &StorageLayout,
+    storage: &Storage,
+    label: String,
+    base: U256,
+    variables: &mut Vec<LayoutVariable>,
+) {
+    let Ok(slot) = storage.slot.parse::<U256>() else { return };
+    flatten_type(
+        layout,
+        &storage.storage_type,
+        label,
+        base + slot,
+        storage.offset as usize,
+        variables,
+    );
+}
+
+fn flatten_type(
+    layout: &StorageLayout,
+    ty: &str,
+    label: String,
+    slot: U256,
+    offset: usize,
+    variables: &mut Vec<LayoutVariable>,
+) {
+    let Some(storage_type) = layout.types.get(ty) else { return };
+    let Ok(size) = storage_type.number_of_bytes.parse::<usize>() else { return };
+
+    if let Some(members) = storage_type
+        .other
+        .get("members")
+        .and_then(|members| serde_json::from_value::<Vec<Storage>>(members.clone()).ok())
+    {
+        for member in &members {
+            flatten_variable(layout, member, format!("{label}.{}", member.label), slot, variables);
+        }
+        return;
+    }
+
+    // Static arrays are encoded inplace, dynamic arrays are encoded as `dynamic_array`.
+    if storage_type.encoding == "inplace" {
+        if let Some(base) = storage_type.other.get("base").and_then(|base| base.as_str()) {
+            let Some(element_size) =
+                layout.types.get(base).and_then(|base| base.number_of_bytes.parse::<usize>().ok())
+            else {
+                return;
+            };
+            let (per_slot, slots_per_element) = if element_size <= 16 {
+                (32 / element_size.max(1), 1)
+            } else {
+                (1, element_size.div_ceil(32))
+            };
+            let len = if per_slot > 1 {
+                size / 32 * per_slot
+            } else {
+                size.div_ceil(32) / slots_per_element
+            };
+            for i in 0..len {
+                let (element_slot, element_offset) = if per_slot > 1 {
+                    (slot + U256::from(i / per_slot), (i % per_slot) * element_size)
+                } else {
+                    (slot + U256::from(i * slots_per_element), 0)
+                };
+                flatten_type(
+                    layout,
+                    base,
+                    format!("{label}[{i}]"),
+                    element_slot,
+                    element_offset,
+                    variables,
+                );
+            }
+            return;
+        }
+    }
+
+    variables.push(LayoutVariable {
+        label,
+        ty: storage_type.label.clone(),
+        slot,
+        offset,
+        size: if storage_type.encoding == "inplace" { size.min(32) } else { 32 },
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT: &str = r#"{
+        "storage": [
+            { "astId": 1, "contract": "C.sol:C", "label": "owner", "offset": 0, "slot": "0", "type": "t_address" },
+            { "astId": 2, "contract": "C.sol:C", "label": "paused", "offset": 20, "slot": "0", "type": "t_bool" },
+            { "astId": 3, "contract": "C.sol:C", "label": "totals", "offset": 0, "slot": "1", "type": "t_array(t_uint256)2_storage" },
+            { "astId": 4, "contract": "C.sol:C", "label": "config", "offset": 0, "slot": "3", "type": "t_struct(Config)1_storage" },
+            { "astId": 5, "contract": "C.sol:C", "label": "balances", "offset": 0, "slot": "5", "type": "t_mapping(t_address,t_uint256)" }
+        ],
+        "types": {
+            "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "20" },
+            "t_bool": { "encoding": "inplace", "label": "bool", "numberOfBytes": "1" },
+            "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" },
+            "t_uint64": { "encoding": "inplace", "label": "uint64", "numberOfBytes": "8" },
+            "t_array(t_uint256)2_storage": { "encoding": "inplace", "label": "uint256[2]", "numberOfBytes": "64", "base": "t_uint256" },
+            "t_struct(Config)1_storage": {
+                "encoding": "inplace", "label": "struct C.Config", "numberOfBytes": "64",
+                "members": [
+                    { "astId": 6, "contract": "C.sol:C", "label": "fee", "offset": 0, "slot": "0", "type": "t_uint256" },
+                    { "astId": 7, "contract": "C.sol:C", "label": "delay", "offset": 0, "slot": "1", "type": "t_uint64" }
+                ]
+            },
+            "t_mapping(t_address,t_uint256)": { "encoding": "mapping", "key": "t_address", "label": "mapping(address => uint256)", "numberOfBytes": "32", "value": "t_uint256" }
+        }
+    }"#;
+
+    fn slot_diff(from: B256, to: B256) -> SlotDiff {
+        SlotDiff { value: Change { from, to }, variables: vec![] }
+    }
+
+    #[test]
+    fn decodes_storage_slots() {
+        let layout: StorageLayout = serde_json::from_str(LAYOUT).unwrap();
+
+        let owner = Address::repeat_byte(0x11);
+        let mut packed = B256::left_padding_from(owner.as_slice());
+        packed[11] = 1;
+        let mut diff = AccountDiff::default();
+        diff.storage.insert(B256::ZERO, slot_diff(B256::ZERO, packed));
+        diff.storage
+            .insert(B256::with_last_byte(2), slot_diff(B256::ZERO, B256::with_last_byte(7)));
+        diff.storage
+            .insert(B256::with_last_byte(4), slot_diff(B256::ZERO, B256::with_last_byte(9)));
+        diff.storage
+            .insert(B256::repeat_byte(0xab), slot_diff(B256::ZERO, B256::with_last_byte(1)));
+        diff.decode_storage(&layout);
+
+        let variables = &diff.storage[&B256::ZERO].variables;
+        assert_eq!(variables.len(), 2);
+        assert_eq!(variables[0].label, "owner");
+        assert_eq!(variables[0].value.to, B256::left_padding_from(owner.as_slice()));
+        assert_eq!(variables[1].label, "paused");
+        assert_eq!(variables[1].offset, 20);
+        assert_eq!(variables[1].value.to, B256::with_last_byte(1));
+
+        let variables = &diff.storage[&B256::with_last_byte(2)].variables;
+        assert_eq!(variables[0].label, "totals[1]");
+        assert_eq!(variables[0].ty, "uint256");
+
+        let variables = &diff.storage[&B256::with_last_byte(4)].variables;
+        assert_eq!(variables[0].label, "config.delay");
+        assert_eq!(variables[0].value.to, B256::with_last_byte(9));
+
+        // Mapping values live at hashed slots.
+        assert!(diff.storage[&B256::repeat_byte(0xab)].variables.is_empty());
+    }
+}
@@ -0,0 +1,389 @@
+//! Structural diff of decoded call traces.
+
+use crate::traces::{CallKind, CallTraceArena, CallTraceNode, DecodedCallData};
+use alloy_primitives::hex;
+use serde::Serialize;
+use std::fmt::Write;
+use yansi::Paint;
+
+/// A call of a decoded call tree, reduced to the parts compared by a [`TraceDiff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallNode {
+    /// Identifies the call when aligning the subcalls of two traces: its kind, target and
+    /// function, without arguments.
+    pub id: String,
+    /// The call, including its target, function and arguments.
+    pub call: String,
+    /// The gas used by the call.
+    pub gas_used: u64,
+    /// The status and the return value of the call.
+    pub output: String,
+    /// The events emitted by the call.
+    pub events: Vec<String>,
+    /// The subcalls of the call.
+    pub children: Vec<Self>,
+}
+
+impl CallNode {
+    /// Builds the call tree of a decoded trace arena, starting at its root call.
+    pub fn from_arena(arena: &CallTraceArena) -> Self {
+        Self::from_node(arena.nodes(), 0)
+    }
+
+    fn from_node(nodes: &[CallTraceNode], idx: usize) -> Self {
+        let node = &nodes[idx];
+        let trace = &node.trace;
+        let address = trace.address.to_checksum(None);
+
+        let (id, call) = if trace.kind.is_any_create() {
+            let label = trace.decoded.label.as_deref().unwrap_or("<unknown>");
+            (format!("new {label}"), format!("new {label}@{address}"))
+        } else {
+            let target = trace.decoded.label.as_deref().unwrap_or(&address);
+            let (func, inputs) = match &trace.decoded.call_data {
+                Some(DecodedCallData { signature, args }) => {
+                    (signature.split('(').next().unwrap_or_default().to_string(), args.join(", "))
+                }
+                None if trace.data.len() < 4 => ("fallback".to_string(), hex::encode(&trace.data)),
+                None => (hex::encode(&trace.data[..4]), hex::encode(&trace.data[4..])),
+            };
+            let value = if trace.value.is_zero() {
+                String::new()
+            } else {
+                format!("{{value: {}}}", trace.value)
+            };
+            let kind = if trace.kind == CallKind::Call {
+                String::new()
+            } else {
+                format!(" [{}]", trace.kind)
+            };
+            (format!("{target}::{func}{kind}"), format!("{target}::{func}{value}({inputs}){kind}"))
+        };
+
+        let output = if let Some(decoded) = &trace.decoded.return_data {
+            decoded.clone()
+        } else if trace.kind.is_any_create() && trace.status.is_ok() {
+            format!("{} bytes of code", trace.output.len())
+        } else {
+            trace.output.to_string()
+        };
+
+        let events = node
+            .logs
+            .iter()
+            .map(|log| match (&log.decoded.name, &log.decoded.params) {
+                (Some(name), params) => {
+                    let params = params
+                        .iter()
+                        .flatten()
+                        .map(|(name, value)| format!("{name}: {value}"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("emit {name}({params})")
+                }
+                (None, _) => {
+                    let topics = log.raw_log.topics().iter().map(ToString::to_string);
+                    format!(
+                        "emit topics: [{}], data: {}",
+                        topics.collect::<Vec<_>>().join(", "),
+                        log.raw_log.data
+                    )
+                }
+            })
+            .collect();
+
+        Self {
+            id,
+            call,
+            gas_used: trace.gas_used,
+            output: format!("[{:?}] {output}", trace.status).trim_end().to_string(),
+            events,
+            children: node.children.iter().map(|&child| Self::from_node(nodes, child)).collect(),
+        }
+    }
+
+    fn header(&self, gas: bool) -> String {
+        if gas {
+            format!("[{}] {}", self.gas_used, self.call)
+        } else {
+            self.call.clone()
+        }
+    }
+
+    fn footer(&self) -> String {
+        format!("← {}", self.output)
+    }
+}
+
+/// How a line of a [`TraceDiff`] differs between the two traces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    /// The line is the same in both traces.
+    Equal,
+    /// The line is only in the first trace.
+    Removed,
+    /// The line is only in the second trace.
+    Added,
+    /// The line matches a line of the other trace, with different contents.
+    Changed,
+}
+
+/// A line of a [`TraceDiff`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    /// The depth of the call the line belongs to.
+    pub depth: usize,
+    /// The line in the first trace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<String>,
+    /// The line in the second trace.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<String>,
+}
+
+/// A structural diff of two call trees.
+///
+/// Subcalls are aligned by their target and function, so that a call which is made in only one
+/// of the traces doesn't shift the rest of the tree. Emitted events are aligned by their contents.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct TraceDiff {
+    pub lines: Vec<DiffLine>,
+}
+
+impl TraceDiff {
+    /// Diffs two call trees. Gas usage is compared only if `gas` is set.
+    pub fn new(left: &CallNode, right: &CallNode, gas: bool) -> Self {
+        let mut diff = Self::default();
+        diff.diff_call(left, right, 0, gas);
+        diff
+    }
+
+    /// Returns true if the two traces are identical.
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|line| line.kind == DiffKind::Equal)
+    }
+
+    fn diff_call(&mut self, left: &CallNode, right: &CallNode, depth: usize, gas: bool) {
+        self.push_pair(depth, left.header(gas), right.header(gas));
+
+        for (l, r) in align(&left.events, &right.events, |a, b| a == b) {
+            match (l, r) {
+                (Some(l), Some(r)) => {
+                    self.push_pair(depth + 1, left.events[l].clone(), right.events[r].clone())
+                }
+                (Some(l), None) => self.push(DiffKind::Removed, depth + 1, &left.events[l]),
+                (None, Some(r)) => self.push(DiffKind::Added, depth + 1, &right.events[r]),
+                (None, None) => unreachable!(),
+            }
+        }
+
+        for (l, r) in align(&left.children, &right.children, |a, b| a.id == b.id) {
+            match (l, r) {
+                (Some(l), Some(r)) => {
+                    self.diff_call(&left.children[l], &right.children[r], depth + 1, gas)
+                }
+                (Some(l), None) => {
+                    self.push_call(DiffKind::Removed, &left.children[l], depth + 1, gas)
+                }
+                (None, Some(r)) => {
+                    self.push_call(DiffKind::Added, &right.children[r], depth + 1, gas)
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+
+        self.push_pair(depth + 1, left.footer(), right.footer());
+    }
+
+    /// Pushes a call and all of its subcalls which are only in one of the traces.
+    fn push_call(&mut self, kind: DiffKind, call: &CallNode, depth: usize, gas: bool) {
+        self.push(kind, depth, &call.header(gas));
+        for event in &call.events {
+            self.push(kind, depth + 1, event);
+        }
+        for child in &call.children {
+            self.push_call(kind, child, depth + 1, gas);
+        }
+        self.push(kind, depth + 1, &call.footer());
+    }
+
+    fn push(&mut self, kind: DiffKind, depth: usize, line: &str) {
+        let (left, right) = match kind {
+            DiffKind::Removed => (Some(line.to_string()), None),
+            _ => (None, Some(line.to_string())),
+        };
+        self.lines.push(DiffLine { kind, depth, left, right });
+    }
+
+    fn push_pair(&mut self, depth: usize, left: String, right: String) {
+        let kind = if left == right { DiffKind::Equal } else { DiffKind::Changed };
+        self.lines.push(DiffLine { kind, depth, left: Some(left), right: Some(right) });
+    }
+
+    /// Renders the diff as a single tree, with the lines of the first trace prefixed with `-` and
+    /// the lines of the second trace prefixed with `+`.
+    pub fn unified(&self) -> String {
+        let mut out = String::new();
+        for line in &self.lines {
+            let indent = "  ".repeat(line.depth);
+            let left = line.left.as_deref().unwrap_or_default();
+            let right = line.right.as_deref().unwrap_or_default();
+            let _ = match line.kind {
+                DiffKind::Equal => writeln!(out, "  {indent}{left}"),
+                DiffKind::Removed => writeln!(out, "{}", format!("- {indent}{left}").red()),
+                DiffKind::Added => writeln!(out, "{}", format!("+ {indent}{right}").green()),
+                DiffKind::Changed => writeln!(
+                    out,
+                    "{}\n{}",
+                    format!("- {indent}{left}").red(),
+                    format!("+ {indent}{right}").green()
+                ),
+            };
+        }
+        out
+    }
+
+    /// Renders the diff as two trees side by side, separated by a marker: `<` for lines only in
+    /// the first trace, `>` for lines only in the second one and `|` for changed lines.
+    pub fn side_by_side(&self) -> String {
+        let column = |line: &DiffLine, text: &Option<String>| {
+            text.as_deref().map(|text| format!("{}{text}", "  ".repeat(line.depth)))
+        };
+        let width = self
+            .lines
+            .iter()
+            .filter_map(|line| column(line, &line.left))
+            .map(|text| text.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut out = String::new();
+        for line in &self.lines {
+            let left = format!("{:width$}", column(line, &line.left).unwrap_or_default());
+            let right = column(line, &line.right).unwrap_or_default();
+            let _ = match line.kind {
+                DiffKind::Equal => writeln!(out, "{left}   {right}"),
+                DiffKind::Removed => writeln!(out, "{} <", left.red()),
+                DiffKind::Added => writeln!(out, "{left} > {}", right.green()),
+                DiffKind::Changed => writeln!(out, "{} | {}", left.red(), right.green()),
+            };
+        }
+        out
+    }
+}
+
+/// Aligns two sequences on their longest common subsequence, returning the pairs of matched
+/// indices and the unmatched indices of either side, in order.
+fn align<T>(
+    left: &[T],
+    right: &[T],
+    eq: impl Fn(&T, &T) -> bool,
+) -> Vec<(Option<usize>, Option<usize>)> {
+    let (n, m) = (left.len(), right.len());
+    // lengths[i][j] is the length of the LCS of left[i..] and right[j..].
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if eq(&left[i], &right[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if eq(&left[i], &right[j]) {
+            pairs.push((Some(i), Some(j)));
+            (i, j) = (i + 1, j + 1);
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            pairs.push((Some(i), None));
+            i += 1;
+        } else {
+            pairs.push((None, Some(j)));
+            j += 1;
+        }
+    }
+    pairs.extend((i..n).map(|i| (Some(i), None)));
+    pairs.extend((j..m).map(|j| (None, Some(j))));
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(id: &str, args: &str, output: &str, children: Vec<CallNode>) -> CallNode {
+        CallNode {
+            id: id.to_string(),
+            call: format!("{id}({args})"),
+            gas_used: 100,
+            output: output.to_string(),
+            events: vec![],
+            children,
+        }
+    }
+
+    #[test]
+    fn aligns_subcalls() {
+        let left = call(
+            "Vault::withdraw",
+            "1",
+            "[Return]",
+            vec![
+                call("Oracle::price", "", "[Return] 10", vec![]),
+                call("Token::transfer", "alice, 1", "[Return] true", vec![]),
+            ],
+        );
+        let mut right = call(
+            "Vault::withdraw",
+            "1",
+            "[Revert] stale price",
+            vec![
+                call("Sequencer::isUp", "", "[Return] true", vec![]),
+                call("Oracle::price", "", "[Return] 0", vec![]),
+            ],
+        );
+        right.events.push("emit Stale()".to_string());
+
+        let diff = TraceDiff::new(&left, &right, true);
+        assert!(!diff.is_empty());
+        let kinds = diff.lines.iter().map(|line| (line.kind, line.depth)).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (DiffKind::Equal, 0),
+                // emit Stale()
+                (DiffKind::Added, 1),
+                // Sequencer::isUp
+                (DiffKind::Added, 1),
+                (DiffKind::Added, 2),
+                // Oracle::price
+                (DiffKind::Equal, 1),
+                (DiffKind::Changed, 2),
+                // Token::transfer
+                (DiffKind::Removed, 1),
+                (DiffKind::Removed, 2),
+                // Vault::withdraw return
+                (DiffKind::Changed, 1),
+            ]
+        );
+        assert_eq!(diff.lines[5].left.as_deref(), Some("← [Return] 10"));
+        assert_eq!(diff.lines[5].right.as_deref(), Some("← [Return] 0"));
+    }
+
+    #[test]
+    fn compares_gas() {
+        let left = call("Token::transfer", "alice, 1", "[Return] true", vec![]);
+        let mut right = left.clone();
+        right.gas_used += 1;
+
+        assert!(!TraceDiff::new(&left, &right, true).is_empty());
+        assert!(TraceDiff::new(&left, &right, false).is_empty());
+    }
+}
@@ -2,9 +2,10 @@
 
 use alloy_chains::NamedChain;
 use alloy_network::{TransactionBuilder, TransactionResponse};
-use alloy_primitives::{address, b256, Bytes, B256};
+use alloy_primitives::{address, b256, keccak256, Bytes, B256, U256};
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_rpc_types::{BlockNumberOrTag, Index, TransactionRequest};
+use alloy_serde::WithOtherFields;
 use anvil::{EthereumHardfork, NodeConfig};
 use foundry_test_utils::{
     casttest, file, forgetest, forgetest_async,
@@ -1500,6 +1501,195 @@
 "#]]);
 });
 
+casttest!(send_batch, async |prj, cmd| {
+    let (api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+
+    let batch = prj.root().join("txs.csv");
+    fs::write(
+        &batch,
+        "to,sig,args,value
+0x000000000000000000000000000000000000dEaD,,,1ether
+0x000000000000000000000000000000000000bEEF,,,2
+",
+    )
+    .unwrap();
+
+    cmd.args([
+        "send",
+        "--batch",
+        batch.to_str().unwrap(),
+        "--concurrency",
+        "2",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &endpoint,
+    ])
+    .assert_success();
+
+    let report: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(prj.root().join("txs.report.json")).unwrap())
+            .unwrap();
+    assert_eq!(report[0]["status"], "success");
+    assert_eq!(report[1]["status"], "success");
+    assert_eq!(report[1]["nonce"], 1);
+    assert!(prj.root().join("txs.journal.json").exists());
+
+    // Running the batch again resumes from the journal, which has nothing left to send.
+    cmd.assert_success().stdout_eq(str![[r#"
+Resuming the batch from [..]txs.journal.json
+Wrote the report of the batch to [..]txs.report.json
+
+"#]]);
+    let sender = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+    assert_eq!(api.transaction_count(sender, None).await.unwrap().to::<u64>(), 2);
+});
+
+casttest!(verify_proof, async |prj, cmd| {
+    let (_api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+    let address = "0x000000000000000000000000000000000000bEEF";
+
+    cmd.args([
+        "rpc",
+        "anvil_setStorageAt",
+        address,
+        "0x0000000000000000000000000000000000000000000000000000000000000001",
+        "0x000000000000000000000000000000000000000000000000000000000000002a",
+        "--rpc-url",
+        &endpoint,
+    ])
+    .assert_success();
+    // The state root of the genesis block is not computed.
+    cmd.cast_fuse().args(["rpc", "evm_mine", "--rpc-url", &endpoint]).assert_success();
+
+    cmd.cast_fuse()
+        .args(["proof", address, "1", "2", "--verify", "--rpc-url", &endpoint])
+        .assert_success()
+        .stdout_eq(str![[r#"
+Account 0x000000000000000000000000000000000000bEEF: valid
+Slot 0x0000000000000000000000000000000000000000000000000000000000000001: valid
+Slot 0x0000000000000000000000000000000000000000000000000000000000000002: valid
+
+"#]]);
+
+    let proof = cmd
+        .cast_fuse()
+        .args(["proof", address, "1", "--rpc-url", &endpoint])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let path = prj.root().join("proof.json");
+    fs::write(&path, proof.replace("\"value\":\"0x2a\"", "\"value\":\"0x2b\"")).unwrap();
+
+    cmd.cast_fuse()
+        .args(["verify-proof", path.to_str().unwrap(), "--rpc-url", &endpoint])
+        .assert_failure()
+        .stdout_eq(str![[r#"
+Account 0x000000000000000000000000000000000000bEEF: valid
+Slot 0x0000000000000000000000000000000000000000000000000000000000000001: invalid, proof node 0 is invalid: value mismatch [..]
+
+"#]])
+        .stderr_eq(str![[r#"
+Error: the proof does not match the state root [..]
+
+"#]]);
+});
+
+casttest!(mempool, async |_prj, cmd| {
+    let (_api, handle) = anvil::spawn(NodeConfig::test().with_no_mining(true)).await;
+    let endpoint = handle.http_endpoint();
+    let private_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    cmd.args([
+        "send",
+        "--async",
+        "--private-key",
+        private_key,
+        "--rpc-url",
+        &endpoint,
+        "0x000000000000000000000000000000000000dEaD",
+        "approve(address,uint256)",
+        "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+        "5",
+    ])
+    .assert_success();
+    cmd.cast_fuse()
+        .args([
+            "send",
+            "--async",
+            "--nonce",
+            "1",
+            "--private-key",
+            private_key,
+            "--rpc-url",
+            &endpoint,
+            "0x000000000000000000000000000000000000bEEF",
+            "--value",
+            "7",
+        ])
+        .assert_success();
+
+    cmd.cast_fuse()
+        .args([
+            "mempool",
+            "-n",
+            "1",
+            "--to",
+            "0x000000000000000000000000000000000000bEEF",
+            "--from",
+            "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+            "--rpc-url",
+            &endpoint,
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+- hash: [..]
+  from: 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266
+  to: 0x000000000000000000000000000000000000bEEF
+  nonce: 1
+  value: 7
+
+"#]]);
+
+    // Subscriptions only stream the transactions sent after they start, so keep sending some
+    // from another account until one is seen.
+    let provider = handle.http_provider();
+    let sender = tokio::spawn(async move {
+        let approve = Bytes::from_str("0x095ea7b3000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb922660000000000000000000000000000000000000000000000000000000000000005").unwrap();
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            let tx = TransactionRequest::default()
+                .with_from(address!("70997970C51812dc3A010C7d01b50e0d17dc79C8"))
+                .with_to(address!("000000000000000000000000000000000000dEaD"))
+                .with_input(approve.clone());
+            let _ = provider.send_transaction(WithOtherFields::new(tx)).await.unwrap();
+        }
+    });
+    let tx = cmd
+        .cast_fuse()
+        .args([
+            "mempool",
+            "-n",
+            "1",
+            "--selector",
+            "approve(address,uint256)",
+            "--from",
+            "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+            "--json",
+            "--rpc-url",
+            &handle.ws_endpoint(),
+        ])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    sender.abort();
+    let tx: serde_json::Value = serde_json::from_str(&tx).unwrap();
+    assert_eq!(tx["to"], "0x000000000000000000000000000000000000dead");
+    assert!(tx["input"].as_str().unwrap().starts_with("0x095ea7b3"));
+});
+
 casttest!(hash_message, |_prj, cmd| {
     cmd.args(["hash-message", "hello"]).assert_success().stdout_eq(str![[r#"
 0x50b2c43fd39106bafbba0da34fc430e1f91e3c96ea2acee2bc34119f92b37750
@@ -1863,6 +2053,59 @@
 "#]]);
 });
 
+// tests that `cast run` writes the folded stacks and flamegraph of a local transaction
+casttest!(run_writes_gas_profiles, async |prj, cmd| {
+    let (api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+
+    // Deploys a contract whose init code stores 1 in slot 0.
+    cmd.args([
+        "send",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &endpoint,
+        "--create",
+        "0x6001600055",
+    ])
+    .assert_success();
+    let tx_hash = api
+        .transaction_by_block_number_and_index(BlockNumberOrTag::Latest, Index::from(0))
+        .await
+        .unwrap()
+        .unwrap()
+        .tx_hash();
+
+    let folded = prj.root().join("tx.folded");
+    let flamegraph = prj.root().join("tx.svg");
+    cmd.cast_fuse()
+        .args([
+            "run",
+            &tx_hash.to_string(),
+            "--folded",
+            folded.to_str().unwrap(),
+            "--flamegraph",
+            flamegraph.to_str().unwrap(),
+            "--rpc-url",
+            &endpoint,
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+Folded stacks saved to [..]tx.folded
+Flamegraph saved to [..]tx.svg
+
+"#]]);
+
+    // The creation is the only frame, and uses the gas of the `SSTORE`.
+    let stacks = fs::read_to_string(folded).unwrap();
+    let (stack, gas) = stacks.trim_end().rsplit_once(' ').unwrap();
+    assert!(!stacks.trim_end().contains('\n'), "{stacks}");
+    assert!(stack.starts_with("new "), "{stacks}");
+    assert!(gas.parse::<u64>().unwrap() > 20_000, "{stacks}");
+    assert!(fs::read_to_string(flamegraph).unwrap().contains("<svg"));
+});
+
 // tests cast can decode traces when running with verbosity level > 4
 forgetest_async!(show_state_changes_in_traces, |prj, cmd| {
     let (api, handle) = anvil::spawn(NodeConfig::test()).await;
@@ -2069,3 +2312,306 @@ struct Info {
         .args(["run", &receipt.transaction_hash.to_string(), "--rpc-url", &http_endpoint])
         .assert_success();
 });
+
+casttest!(bisect_reverting_calls, async |_prj, cmd| {
+    let (api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+
+    // Anvil sets code and storage in the state of the latest block. The contract returns the
+    // value of slot 0 from block 1, and reverts while it's zero, until block 3.
+    let contract = address!("0x0000000000000000000000000000000000001234");
+    let code = Bytes::from_str("0x60005480600b57600080fd5b60005260206000f3").unwrap();
+    api.mine_one().await;
+    api.anvil_set_code(contract, code).await.unwrap();
+    for _ in 0..2 {
+        api.mine_one().await;
+    }
+    api.anvil_set_storage_at(contract, U256::ZERO, B256::with_last_byte(7)).await.unwrap();
+    for _ in 0..2 {
+        api.mine_one().await;
+    }
+
+    cmd.args([
+        "bisect",
+        &contract.to_string(),
+        "value()(uint256)",
+        "--from",
+        "1",
+        "--rpc-url",
+        &endpoint,
+    ])
+    .assert_success()
+    .stdout_eq(str![[r#"
+Block 3
+Before: execution reverted
+After: 7
+No transaction of the block touched 0x0000000000000000000000000000000000001234
+
+"#]]);
+
+    // Errors other than reverts, here the missing contract at genesis, abort the search.
+    cmd.cast_fuse()
+        .args([
+            "bisect",
+            &contract.to_string(),
+            "value()(uint256)",
+            "--from",
+            "0",
+            "--rpc-url",
+            &endpoint,
+        ])
+        .assert_failure()
+        .stderr_eq(str![[r#"
+Error: contract 0x0000000000000000000000000000000000001234 does not have any code
+
+"#]]);
+});
+
+casttest!(decompile_bytecode, async |_prj, cmd| {
+    // A dispatcher for `0xaabbccdd`, which writes 42 to slot 1, and `0x11223344`, which calls an
+    // internal function reading slot 0 and returning to its caller.
+    let code = "0x60003560e01c8063aabbccdd14601e57806311223344146025576000\
+                80fd5b602a600155005b602b602d565b005b6000545056";
+
+    cmd.args(["decompile", code, "--offline"]).assert_success().stdout_eq(str![[r#"
+// Decompiled from 33 instructions: 7 reachable basic blocks, 2 functions.
+contract Decompiled {
+    // Storage slots accessed with a constant key:
+    //   0x0: read
+    //   0x1: write
+
+    /// Selector 0xaabbccdd, entry at label_001e.
+    function func_0xaabbccdd() external {
+      label_001e:
+        storage[0x1] = 0x2a;
+        return;
+    }
+
+    /// Selector 0x11223344, entry at label_0025.
+    function func_0x11223344() external view {
+      label_0025:
+        goto label_002d;
+      label_002b:
+        return;
+      label_002d:
+        uint256 v0 = storage[0x0];
+        goto label_002b;
+    }
+}
+
+"#]]);
+
+    // The code of a contract is fetched by its address.
+    let (api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let contract = address!("0x0000000000000000000000000000000000001234");
+    api.anvil_set_code(contract, Bytes::from_str(code).unwrap()).await.unwrap();
+    cmd.cast_fuse()
+        .args([
+            "decompile",
+            &contract.to_string(),
+            "--asm",
+            "--offline",
+            "--rpc-url",
+            &handle.http_endpoint(),
+        ])
+        .assert_success()
+        .stdout_eq(str![[r#"
+label_0000:
+    00000000: PUSH1 0x00
+    00000002: CALLDATALOAD
+    00000003: PUSH1 0xe0
+    00000005: SHR
+    00000006: DUP1
+    00000007: PUSH4 0xaabbccdd           ; selector 0xaabbccdd
+    0000000c: EQ
+    0000000d: PUSH1 0x1e
+    0000000f: JUMPI                      ; -> label_001e
+label_0010:
+    00000010: DUP1
+    00000011: PUSH4 0x11223344           ; selector 0x11223344
+    00000016: EQ
+    00000017: PUSH1 0x25
+    00000019: JUMPI                      ; -> label_0025
+label_001a:
+    0000001a: PUSH1 0x00
+    0000001c: DUP1
+    0000001d: REVERT
+
+; function 0xaabbccdd [0xaabbccdd]
+label_001e:
+    0000001e: JUMPDEST
+    0000001f: PUSH1 0x2a
+    00000021: PUSH1 0x01
+    00000023: SSTORE                     ; slot 0x1
+    00000024: STOP
+
+; function 0x11223344 [0x11223344]
+label_0025:
+    00000025: JUMPDEST
+    00000026: PUSH1 0x2b
+    00000028: PUSH1 0x2d
+    0000002a: JUMP                       ; -> label_002d
+label_002b:
+    0000002b: JUMPDEST
+    0000002c: STOP
+label_002d:
+    0000002d: JUMPDEST
+    0000002e: PUSH1 0x00
+    00000030: SLOAD                      ; slot 0x0
+    00000031: POP
+    00000032: JUMP                       ; -> label_002b
+
+
+"#]]);
+});
+
+casttest!(safe_exec_signed_transaction, async |_prj, cmd| {
+    let (api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+    let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    let owner = address!("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+    // A single owner Safe, with its nonce in slot 0 and its owner in slot 1. `execTransaction`
+    // hashes the transaction as `Safe.sol` does, recovers the signer of the hash with the
+    // `ecrecover` precompile, reverts unless it's the owner, then increments the nonce and calls
+    // the destination.
+    let safe = address!("0x1111111111111111111111111111111111111111");
+    let code = "0x60443560040180359060200181816102003781610200207fbb8310d486368db6bd6f849402fdd73ad53d\
+                316b5a4b2644ad6efe0f941286d860005260043560205260243560405260605260643560805260843560\
+                a05260a43560c05260c43560e05260e435610100526101043561012052600054610140526101606000\
+                207f47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a7946921860005246602052\
+                3060405260606000206119016000526020526040526042601e2060005261012435602401803560405280\
+                602001356060526040013560f81c602052602060806080600060015afa50608051600154146100fd57\
+                600080fd5b60005460010160005560006000836102006024356004355af160005260206000f3";
+    api.anvil_set_code(safe, Bytes::from_str(code).unwrap()).await.unwrap();
+    api.anvil_set_storage_at(safe, U256::from(1), owner.into_word()).await.unwrap();
+    api.anvil_set_balance(safe, U256::from(10).pow(U256::from(18))).await.unwrap();
+
+    let safe = safe.to_string();
+    let tx = [
+        "--safe",
+        &safe,
+        "--chain",
+        "31337",
+        "--nonce",
+        "0",
+        "--to",
+        "0x000000000000000000000000000000000000dEaD",
+        "--value",
+        "1ether",
+    ];
+    let signature = cmd
+        .args(["safe", "sign"])
+        .args(tx)
+        .args(["--private-key", key])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+    let calldata = cmd
+        .cast_fuse()
+        .args(["safe", "exec-calldata"])
+        .args(tx)
+        .args(["--signature", signature.trim()])
+        .assert_success()
+        .get_output()
+        .stdout_lossy();
+
+    let send = ["send", &safe, calldata.trim(), "--private-key", key, "--rpc-url", &endpoint];
+    cmd.cast_fuse().args(send).assert_success();
+    cmd.cast_fuse()
+        .args(["balance", "0x000000000000000000000000000000000000dEaD", "--rpc-url", &endpoint])
+        .assert_success()
+        .stdout_eq(str![[r#"
+1000000000000000000
+
+"#]]);
+
+    // The nonce of the signed transaction has been used.
+    cmd.cast_fuse().args(send).assert_failure();
+});
+
+casttest!(find_slot_at_block_tag_and_hash, async |_prj, cmd| {
+    let (api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+
+    // A token from block 1, whose `balanceOf(owner)` returns `balances[owner]` from slot 0.
+    let token = address!("0x0000000000000000000000000000000000001234");
+    let holder = address!("0x000000000000000000000000000000000000dEaD");
+    let code = Bytes::from_str("0x600435600052600060205260406000205460005260206000f3").unwrap();
+    api.mine_one().await;
+    api.anvil_set_code(token, code).await.unwrap();
+    let slot = keccak256([holder.into_word(), B256::ZERO].concat());
+    api.anvil_set_storage_at(token, slot.into(), B256::with_last_byte(1)).await.unwrap();
+    api.mine_one().await;
+    let hash = api.block_by_number(BlockNumberOrTag::Number(1)).await.unwrap().unwrap().header.hash;
+
+    let mut find_slot = |block: &str| {
+        cmd.cast_fuse().args([
+            "find-slot",
+            &token.to_string(),
+            "balanceOf",
+            &holder.to_string(),
+            "--block",
+            block,
+            "--rpc-url",
+            &endpoint,
+        ]);
+        cmd.assert()
+    };
+    find_slot(&hash.to_string()).success().stdout_eq(str![[r#"
+Slot: 0x44ad89ba62b98ff34f51403ac22759b55759460c0bb5521eb4b6ee3cff49cf83
+Mapping slot: 0
+Layout: Solidity (keccak256(key . slot))
+
+"#]]);
+
+    // The token doesn't exist yet at the earliest block.
+    find_slot("earliest").failure().stderr_eq(str![[r#"
+Error: none of the 0 storage slots read by the call holds its result
+
+"#]]);
+});
+
+casttest!(run_prints_state_diff, async |_prj, cmd| {
+    let (api, handle) = anvil::spawn(NodeConfig::test()).await;
+    let endpoint = handle.http_endpoint();
+
+    // Deploys a contract whose init code stores 1 in slot 0. The balances change with the fees paid
+    // to the coinbase.
+    cmd.args([
+        "send",
+        "--private-key",
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        "--rpc-url",
+        &endpoint,
+        "--create",
+        "0x6001600055",
+    ])
+    .assert_success();
+    let tx_hash = api
+        .transaction_by_block_number_and_index(BlockNumberOrTag::Latest, Index::from(0))
+        .await
+        .unwrap()
+        .unwrap()
+        .tx_hash();
+
+    cmd.cast_fuse()
+        .args(["run", &tx_hash.to_string(), "--state-diff", "--rpc-url", &endpoint])
+        .assert_success()
+        .stdout_eq(str![[r#"
+...
+State diff:
+0x0000000000000000000000000000000000000000:
+  balance: 0 -> [..]
+
+0x5FbDB2315678afecb367f032d93F642f64180aa3:
+  nonce: 0 -> 1
+  @ 0x0000000000000000000000000000000000000000000000000000000000000000: 0x0000000000000000000000000000000000000000000000000000000000000000 -> 0x0000000000000000000000000000000000000000000000000000000000000001
+
+0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266:
+  balance: 100000000000000000000 -> [..]
+  nonce: 0 -> 1
+
+
+"#]]);
+});
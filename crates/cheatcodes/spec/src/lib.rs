@@ -77,6 +77,7 @@ pub fn new() -> Self {
                 Vm::Log::STRUCT.clone(),
                 Vm::Rpc::STRUCT.clone(),
                 Vm::EthGetLogs::STRUCT.clone(),
+                Vm::EthDecodedLog::STRUCT.clone(),
                 Vm::DirEntry::STRUCT.clone(),
                 Vm::FsMetadata::STRUCT.clone(),
                 Vm::Wallet::STRUCT.clone(),
@@ -88,6 +89,10 @@ pub fn new() -> Self {
                 Vm::DebugStep::STRUCT.clone(),
                 Vm::BroadcastTxSummary::STRUCT.clone(),
                 Vm::SignedDelegation::STRUCT.clone(),
+                Vm::BlockReceipt::STRUCT.clone(),
+                Vm::OpcodeCount::STRUCT.clone(),
+                Vm::StorageSlot::STRUCT.clone(),
+                Vm::OpcodeRecord::STRUCT.clone(),
             ]),
             enums: Cow::Owned(vec![
                 Vm::CallerMode::ENUM.clone(),
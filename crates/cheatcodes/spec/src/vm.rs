@@ -142,6 +142,27 @@ struct EthGetLogs {
         bool removed;
     }
 
+    /// An RPC log object decoded against an event signature. Returned by `eth_getLogs`.
+    struct EthDecodedLog {
+        /// The address of the log's emitter.
+        address emitter;
+        /// The ABI-encoded parameters of the event, in the order of the signature. Indexed parameters
+        /// of dynamic types are encoded as their `bytes32` topic.
+        bytes args;
+        /// The block hash.
+        bytes32 blockHash;
+        /// The block number.
+        uint64 blockNumber;
+        /// The transaction hash.
+        bytes32 transactionHash;
+        /// The transaction index in the block.
+        uint64 transactionIndex;
+        /// The log index.
+        uint256 logIndex;
+        /// Whether the log was removed.
+        bool removed;
+    }
+
     /// A single entry in a directory listing. Returned by `readDir`.
     struct DirEntry {
         /// The error message, if any.
@@ -325,6 +346,56 @@ struct SignedDelegation {
         address implementation;
     }
 
+    /// The receipt of a transaction applied to a simulated block. Returned by `blockTransact`.
+    struct BlockReceipt {
+        /// The index of the transaction in the block.
+        uint64 transactionIndex;
+        /// Whether the transaction succeeded.
+        bool success;
+        /// The gas used by the transaction.
+        uint64 gasUsed;
+        /// The gas used by the block up to and including the transaction.
+        uint64 cumulativeGasUsed;
+        /// The address of the created contract, if the transaction created one.
+        address contractAddress;
+        /// The returned data of the transaction, or its revert data if it reverted.
+        bytes output;
+        /// The logs emitted by the transaction.
+        Log[] logs;
+    }
+
+    /// The number of times an opcode was executed. Part of an `OpcodeRecord`.
+    struct OpcodeCount {
+        /// The opcode.
+        uint8 opcode;
+        /// The number of times the opcode was executed.
+        uint64 count;
+    }
+
+    /// A storage slot of an account.
+    struct StorageSlot {
+        /// The account the slot belongs to.
+        address account;
+        /// The slot.
+        bytes32 slot;
+    }
+
+    /// The opcodes executed by the calls made between `startOpcodeRecording` and
+    /// `stopAndReturnOpcodes`.
+    struct OpcodeRecord {
+        /// The number of times each executed opcode was executed, ordered by opcode.
+        OpcodeCount[] opcodes;
+        /// The number of `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL`s, excluding calls to
+        /// the cheatcode and console addresses.
+        uint64 externalCalls;
+        /// The number of accesses to cold accounts and storage slots.
+        uint64 coldAccesses;
+        /// The number of accesses to warm accounts and storage slots.
+        uint64 warmAccesses;
+        /// The slots written by `SSTORE`s, in execution order.
+        StorageSlot[] storageWrites;
+    }
+
     // ======== EVM ========
 
     /// Gets the address for a given private key.
@@ -418,6 +489,28 @@ struct SignedDelegation {
         external
         returns (bool found, bytes32 key, bytes32 parent);
 
+    /// Starts recording the opcodes executed by the calls made from the current call frame.
+    ///
+    /// Opcodes of the current call frame itself are not recorded.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function startOpcodeRecording() external;
+
+    /// Stops recording opcodes and returns the recorded opcodes.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function stopAndReturnOpcodes() external returns (OpcodeRecord memory record);
+
+    /// Asserts that `opcode` was not executed in the opcode record.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function assertNoOpcode(OpcodeRecord calldata record, uint8 opcode) external pure;
+
+    /// Asserts that at most `maxCalls` external calls were made in the opcode record.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function assertMaxExternalCalls(OpcodeRecord calldata record, uint256 maxCalls) external pure;
+
+    /// Asserts that `slot` of `account` was not written in the opcode record.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function assertNoStorageWrite(OpcodeRecord calldata record, address account, bytes32 slot) external pure;
+
     // -------- Block and Transaction Properties --------
 
     /// Sets `block.chainid`.
@@ -503,6 +596,37 @@ struct SignedDelegation {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function setBlockhash(uint256 blockNumber, bytes32 blockHash) external;
 
+    /// Starts a simulated block at `block.number + 1` and `block.timestamp + 12`, after taking a state
+    /// snapshot which `reorg` reverts to. After `endBlock`, the block it moved to is started instead,
+    /// so that simulated blocks have consecutive numbers.
+    ///
+    /// Transactions are applied to the block with `blockTransact` until `endBlock` is called.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function startBlock() external returns (uint256 blockNumber);
+
+    /// Applies a transaction from `from` to the simulated block, as its own transaction with
+    /// `tx.origin` set to `from`, and commits its state changes.
+    ///
+    /// If `to` is the zero address, `data` is the init code of the contract created by the
+    /// transaction. The transaction may use the gas left in the block, and pays it at the base fee.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function blockTransact(address from, address to, uint256 value, bytes calldata data)
+        external
+        returns (BlockReceipt memory receipt);
+
+    /// Ends the simulated block, sets its simulated hash with `setBlockhash`, and moves to the next
+    /// block so that the hash is visible to `blockhash`.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function endBlock() external returns (bytes32 blockHash);
+
+    /// Reorgs away the last `depth` simulated blocks, including the open block, if any.
+    ///
+    /// Reverts to the state and block of the snapshot taken before the first of them, and clears
+    /// their hashes with `setBlockhash`. Returns the hashes of the reorged blocks, the zero hash for
+    /// an open block.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function reorg(uint256 depth) external returns (bytes32[] memory blockHashes);
+
     // -------- Account State --------
 
     /// Sets an address' balance.
@@ -816,11 +940,37 @@ struct SignedDelegation {
         returns (bytes memory data);
 
     /// Gets all the logs according to specified filter.
+    ///
+    /// Ranges rejected by the provider are split into smaller requests, and ranges up to the block of
+    /// the fork are cached in the RPC cache of its chain.
     #[cheatcode(group = Evm, safety = Safe)]
     function eth_getLogs(uint256 fromBlock, uint256 toBlock, address target, bytes32[] calldata topics)
         external
         returns (EthGetLogs[] memory logs);
 
+    /// Gets all the logs emitted by any of `targets`, or by any address if empty, matching `topics`.
+    ///
+    /// Each element of `topics` lists the alternatives of a topic position, and matches any topic if
+    /// empty. See `eth_getLogs(uint256,uint256,address,bytes32[])` for range splitting and caching.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function eth_getLogs(
+        uint256 fromBlock,
+        uint256 toBlock,
+        address[] calldata targets,
+        bytes32[][] calldata topics
+    ) external returns (EthGetLogs[] memory logs);
+
+    /// Gets all the logs of the event `eventSignature` emitted by any of `targets`, or by any address
+    /// if empty, and decodes them, e.g. with the signature
+    /// `Transfer(address indexed from, address indexed to, uint256 value)`.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function eth_getLogs(
+        uint256 fromBlock,
+        uint256 toBlock,
+        address[] calldata targets,
+        string calldata eventSignature
+    ) external returns (EthDecodedLog[] memory logs);
+
     // --- Behavior ---
 
     /// In forking mode, explicitly grant the given address cheatcode access.
@@ -2515,6 +2665,14 @@ struct SignedDelegation {
     #[cheatcode(group = Crypto)]
     function signCompact(uint256 privateKey, bytes32 digest) external pure returns (bytes32 r, bytes32 vs);
 
+    /// Signs the EIP-712 signing hash of the typed data JSON payload with `privateKey` using the
+    /// secp256k1 curve. The payload is hashed as in `eip712HashTypedData`.
+    #[cheatcode(group = Crypto)]
+    function signTypedData(uint256 privateKey, string calldata jsonData)
+        external
+        view
+        returns (uint8 v, bytes32 r, bytes32 s);
+
     /// Signs `digest` with signer provided to script using the secp256k1 curve.
     ///
     /// If `--sender` is provided, the signer with provided address is used, otherwise,
@@ -2706,10 +2864,50 @@ struct SignedDelegation {
     #[cheatcode(group = Utilities)]
     function setArbitraryStorage(address target) external;
 
+    /// Computes the EIP-712 `hashStruct` of the ABI-encoded struct `abiEncodedData`.
+    ///
+    /// `typeNameOrDefinition` is either an EIP-712 `encodeType` such as
+    /// `Mail(address from,address to,string contents)`, or the name of a struct defined in the
+    /// project sources, optionally qualified by its contract as `Contract.Mail`, whose definition
+    /// is resolved as by `forge eip712`.
+    ///
+    /// The project sources are compiled once, the first time a struct name is resolved, and the
+    /// resolved definitions are cached for the rest of the run: changes to the sources made
+    /// afterwards, e.g. by `writeFile`, are not picked up.
+    #[cheatcode(group = Utilities)]
+    function eip712HashStruct(string calldata typeNameOrDefinition, bytes calldata abiEncodedData)
+        external
+        view
+        returns (bytes32 structHash);
+
+    /// Computes the EIP-712 signing hash of the typed data JSON payload, as for `eth_signTypedData_v4`.
+    ///
+    /// If `types` does not define the `primaryType`, its definition is resolved from the structs of the
+    /// project sources, as in `eip712HashStruct`.
+    #[cheatcode(group = Utilities)]
+    function eip712HashTypedData(string calldata jsonData) external view returns (bytes32 digest);
+
     /// Connect SQL Database
     #[cheatcode(group = Database, safety = Safe)]
     function connectDb(string calldata host, uint16 port,string calldata user,string calldata password, string calldata name) external;
 
+    /// Connect to the database of the `[db_endpoints]` endpoint with the given alias, and make it the
+    /// current connection. Connections stay open, so that several databases can be used with `selectDb`.
+    /// The database is read-only unless the endpoint's `writers` match the name of the running contract.
+    /// For PostgreSQL, this only sets the default of the session, which statements can change.
+    #[cheatcode(group = Database, safety = Safe)]
+    function connectDb(string calldata alias) external;
+
+    /// Make the connected database with the given alias the current connection.
+    #[cheatcode(group = Database, safety = Safe)]
+    function selectDb(string calldata alias) external;
+
+    /// Connect to an SQLite database file, or to a new in-memory database with `:memory:`.
+    /// The file is subject to `fs_permissions`: it's opened read-only without write access, and is
+    /// created if missing otherwise.
+    #[cheatcode(group = Database, safety = Safe)]
+    function connectSqlite(string calldata path) external;
+
     /// Open transaction and automatically commit all of transactions in end execution
     #[cheatcode(group = Database, safety = Safe)]
     function openTransaction() external returns(uint128);
@@ -2753,6 +2951,74 @@ struct SignedDelegation {
     /// Execute SQL in Database transaction
     #[cheatcode(group = Database, safety = Safe)]
     function executeInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params) external returns (uint64);
+
+    /// Execute SQL to Database, binding the ABI-encoded `params` of the tuple type `paramTypes`, e.g.
+    /// `abi.encode(amount, name)` with `(uint256,string)`. `null` members of `paramTypes` are bound
+    /// as NULL, and have no value in `params`.
+    #[cheatcode(group = Database, safety = Safe)]
+    function executeTyped(string calldata query, string calldata paramTypes, bytes calldata params) external returns (uint64);
+
+    /// Execute SQL in Database transaction, with typed parameters as in `executeTyped`.
+    #[cheatcode(group = Database, safety = Safe)]
+    function executeTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params) external returns (uint64);
+
+    /// Query SQL to Database, with typed parameters as in `executeTyped`, returning each row ABI-encoded
+    /// as the tuple type `rowType` of its columns, e.g. `(uint256,string,bool)`.
+    /// NULL columns are returned as zero values, and TIMESTAMP columns as seconds for integer types.
+    #[cheatcode(group = Database, safety = Safe)]
+    function queryTyped(string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes[] memory rows);
+
+    /// Query SQL to Database for at most one row, as in `queryTyped`. Returns empty bytes if there is no row.
+    #[cheatcode(group = Database, safety = Safe)]
+    function queryOptTyped(string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes memory row);
+
+    /// Query SQL in Database transaction, as in `queryTyped`.
+    #[cheatcode(group = Database, safety = Safe)]
+    function queryTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes[] memory rows);
+
+    /// Query SQL in Database transaction for at most one row, as in `queryOptTyped`.
+    #[cheatcode(group = Database, safety = Safe)]
+    function queryOptTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes memory row);
+
+    /// Execute SQL to Database as in `execute`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded result or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryExecute(string calldata query, bytes[] calldata params) external returns (bool ok, bytes memory data);
+
+    /// Execute SQL in Database transaction as in `executeInTransaction`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded result or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryExecuteInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params) external returns (bool ok, bytes memory data);
+
+    /// Query SQL to Database as in `query`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded rows or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryQuery(string calldata query, bytes[] calldata params, string[] calldata selects) external view returns (bool ok, bytes memory data);
+
+    /// Query SQL in Database transaction as in `queryInTransaction`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded rows or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryQueryInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params, string[] calldata selects) external view returns (bool ok, bytes memory data);
+
+    /// Execute SQL to Database as in `executeTyped`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded result or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryExecuteTyped(string calldata query, string calldata paramTypes, bytes calldata params) external returns (bool ok, bytes memory data);
+
+    /// Execute SQL in Database transaction as in `executeTypedInTransaction`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded result or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryExecuteTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params) external returns (bool ok, bytes memory data);
+
+    /// Query SQL to Database as in `queryTyped`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded `bytes[]` rows or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryQueryTyped(string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bool ok, bytes memory data);
+
+    /// Query SQL in Database transaction as in `queryTypedInTransaction`, without reverting if it fails.
+    /// Returns whether it succeeded, and the ABI-encoded `bytes[]` rows or the error message.
+    #[cheatcode(group = Database, safety = Safe)]
+    function tryQueryTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bool ok, bytes memory data);
 }
 }
 
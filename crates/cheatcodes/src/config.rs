@@ -5,7 +5,7 @@
 use foundry_compilers::{utils::canonicalize, ProjectPathsConfig};
 use foundry_config::{
     cache::StorageCachingConfig, fs_permissions::FsAccessKind, Config, FsPermissions,
-    ResolvedRpcEndpoint, ResolvedRpcEndpoints, RpcEndpoint, RpcEndpointUrl,
+    ResolvedDbEndpoints, ResolvedRpcEndpoint, ResolvedRpcEndpoints, RpcEndpoint, RpcEndpointUrl,
 };
 use foundry_evm_core::opts::EvmOpts;
 use semver::Version;
@@ -31,6 +31,8 @@ pub struct CheatsConfig {
     pub no_storage_caching: bool,
     /// All known endpoints and their aliases
     pub rpc_endpoints: ResolvedRpcEndpoints,
+    /// All known database endpoints and their aliases
+    pub db_endpoints: ResolvedDbEndpoints,
     /// Project's paths as configured
     pub paths: ProjectPathsConfig,
     /// Filesystem permissions for cheatcodes like `writeFile`, `readFile`
@@ -86,6 +88,7 @@ pub fn new(
             rpc_storage_caching: config.rpc_storage_caching.clone(),
             no_storage_caching: config.no_storage_caching,
             rpc_endpoints,
+            db_endpoints: config.db_endpoints.clone().resolved(),
             paths: config.project_paths(),
             fs_permissions: config.fs_permissions.clone().joined(config.root.as_ref()),
             root: config.root.clone(),
@@ -222,6 +225,7 @@ fn default() -> Self {
             rpc_storage_caching: Default::default(),
             no_storage_caching: false,
             rpc_endpoints: Default::default(),
+            db_endpoints: Default::default(),
             paths: ProjectPathsConfig::builder().build_with_root("./"),
             fs_permissions: Default::default(),
             root: Default::default(),
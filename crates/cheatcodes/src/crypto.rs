@@ -146,6 +146,15 @@ fn apply(&self, _state: &mut Cheatcodes) -> Result {
     }
 }
 
+impl Cheatcode for signTypedDataCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { privateKey, jsonData } = self;
+        let digest = crate::utils::parse_typed_data(state, jsonData)?.eip712_signing_hash()?;
+        let sig = sign(privateKey, &digest)?;
+        Ok(encode_full_sig(sig))
+    }
+}
+
 impl Cheatcode for sign_2Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { digest } = self;
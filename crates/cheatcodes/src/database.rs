@@ -1,85 +1,285 @@
-use alloy_primitives::{Bytes, B128, B64};
+use std::{fmt::Write, fs, path::Path, sync::Arc};
+
+use alloy_dyn_abi::DynSolType;
+use alloy_primitives::Bytes;
 use alloy_sol_types::SolValue;
-use postgres::{types::ToSql, Client, NoTls};
+use foundry_config::{fs_permissions::FsAccessKind, ResolvedDbEndpoint};
+use itertools::Itertools;
 use spec::Vm::{
-    commitTransactionCall, commitTransactionsCall, connectDbCall, executeCall,
-    executeInTransactionCall, openTransactionCall, queryCall, queryInTransactionCall, queryOptCall,
-    queryOptInTransactionCall, rollbackTransactionCall, rollbackTransactionsCall,
+    commitTransactionCall, commitTransactionsCall, connectDb_0Call, connectDb_1Call,
+    connectSqliteCall, executeCall, executeInTransactionCall, executeTypedCall,
+    executeTypedInTransactionCall, openTransactionCall, queryCall, queryInTransactionCall,
+    queryOptCall, queryOptInTransactionCall, queryOptTypedCall, queryOptTypedInTransactionCall,
+    queryTypedCall, queryTypedInTransactionCall, rollbackTransactionCall, rollbackTransactionsCall,
+    selectDbCall, tryExecuteCall, tryExecuteInTransactionCall, tryExecuteTypedCall,
+    tryExecuteTypedInTransactionCall, tryQueryCall, tryQueryInTransactionCall, tryQueryTypedCall,
+    tryQueryTypedInTransactionCall,
 };
 use uuid::Uuid;
 
-use crate::{Cheatcode, Cheatcodes, Result};
+use crate::{Cheatcode, Cheatcodes, CheatsConfig, CheatsCtxt, Result};
 
 mod client;
 mod error;
-pub use client::{DatabaseClient, PostgresClient};
-use error::Error;
+mod isolated;
+mod postgres;
+mod sqlite;
+mod value;
+pub use client::DatabaseClient;
+pub use error::Error;
+pub use isolated::{Connect, IsolatedClient};
+pub use postgres::PostgresClient;
+pub use sqlite::SqliteClient;
+pub use value::{decode_params, Row, SqlValue};
+
+/// A transaction opened by the database cheatcodes.
+#[derive(Clone, Debug)]
+pub struct DbTransaction {
+    pub id: Uuid,
+    /// The client of the connection which opened the transaction.
+    pub client: Arc<dyn DatabaseClient>,
+    /// The call depth the transaction was opened at. It's rolled back when that call reverts, or
+    /// when the top-level call ends.
+    pub depth: u64,
+}
+
+/// Returns the client of the current database connection.
+fn db_client(state: &Cheatcodes) -> Result<&Arc<dyn DatabaseClient>> {
+    state.db_client.as_ref().ok_or_else(|| {
+        fmt_err!("no database connection; call `connectDb` or `connectSqlite` first")
+    })
+}
+
+/// Returns the client of the connection which opened the transaction, falling back to the current
+/// database connection for unknown transactions.
+fn transaction_client(state: &Cheatcodes, id: Uuid) -> Result<Arc<dyn DatabaseClient>> {
+    match state.db_transactions.iter().find(|transaction| transaction.id == id) {
+        Some(transaction) => Ok(transaction.client.clone()),
+        None => db_client(state).cloned(),
+    }
+}
 
-trait ToParam {
-    fn with_params<F, R>(&self, f: F) -> R
-    where
-        F: FnOnce(&[&(dyn ToSql + Sync)]) -> R;
+/// Opens a connection to the `[db_endpoints]` endpoint with the given alias.
+fn connect_endpoint(config: &CheatsConfig, alias: &str) -> Result<Arc<dyn DatabaseClient>> {
+    let Some(endpoint) = config.db_endpoints.get(alias) else {
+        bail!("database endpoint `{alias}` not found in `[db_endpoints]`")
+    };
+    // Contracts which aren't writers of the endpoint get a read-only connection.
+    let read_only = !endpoint.is_writer(config.running_contract.as_deref().unwrap_or_default());
+    if !read_only {
+        if let Some(path) = sqlite_path(&endpoint.url()?).filter(|path| *path != sqlite::MEMORY) {
+            config.ensure_not_foundry_toml(config.root.join(path))?;
+        }
+    }
+    connect_db_endpoint(endpoint, alias, &config.root, read_only)
 }
 
-impl ToParam for Vec<Bytes> {
-    #[inline]
-    fn with_params<F, R>(&self, f: F) -> R
-    where
-        F: FnOnce(&[&(dyn ToSql + Sync)]) -> R,
+/// Opens a connection to a `[db_endpoints]` endpoint and loads its fixtures.
+///
+/// Relative SQLite paths and fixtures are resolved against `root`.
+pub fn connect_db_endpoint(
+    endpoint: &ResolvedDbEndpoint,
+    alias: &str,
+    root: &Path,
+    read_only: bool,
+) -> Result<Arc<dyn DatabaseClient>> {
+    let url = endpoint.url()?;
+    let connect: Arc<Connect> = if url.starts_with("postgres://") ||
+        url.starts_with("postgresql://")
     {
-        let owned_params: Vec<Vec<u8>> = self.iter().map(|p| p.to_vec()).collect();
+        Arc::new(move || {
+            Ok(Box::new(PostgresClient::connect_url(&url, read_only)?) as Box<dyn DatabaseClient>)
+        })
+    } else if let Some(path) = sqlite_path(&url) {
+        let path = (path != sqlite::MEMORY).then(|| root.join(path));
+        let isolate = endpoint.isolate;
+        Arc::new(move || {
+            let client = match &path {
+                None => SqliteClient::open(Path::new(sqlite::MEMORY), false)?,
+                // Isolated tests run on private copies of the database, so the file is never
+                // written or locked by them.
+                Some(path) if isolate => SqliteClient::open_copy(path)?,
+                Some(path) => SqliteClient::open(path, read_only)?,
+            };
+            Ok(Box::new(client) as Box<dyn DatabaseClient>)
+        })
+    } else {
+        bail!("unsupported url of database endpoint `{alias}`; expected `postgres://`, `postgresql://` or `sqlite:`")
+    };
+    let client: Arc<dyn DatabaseClient> =
+        if endpoint.isolate { Arc::new(IsolatedClient::new(connect)?) } else { connect()?.into() };
+
+    for fixture in &endpoint.fixtures {
+        let path = root.join(fixture);
+        load_fixture(&*client, &path)
+            .map_err(|e| fmt_err!("failed to load database fixture {}: {e}", fixture.display()))?;
+    }
+    Ok(client)
+}
 
-        let param_refs: Vec<&(dyn ToSql + Sync)> =
-            owned_params.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+/// Returns the path of an `sqlite:` url.
+fn sqlite_path(url: &str) -> Option<&str> {
+    url.strip_prefix("sqlite:").map(|path| path.strip_prefix("//").unwrap_or(path))
+}
 
-        f(param_refs.as_slice())
+/// Loads an SQL or CSV fixture into the database.
+fn load_fixture(client: &dyn DatabaseClient, path: &Path) -> Result<()> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("sql") => client.batch_execute(&fs::read_to_string(path)?)?,
+        Some("csv") => client.batch_execute(&csv_inserts(path)?)?,
+        _ => bail!("expected an `.sql` or `.csv` file"),
     }
+    Ok(())
+}
+
+/// Returns the statements inserting the rows of a CSV fixture into the table named after the file.
+///
+/// Values are inserted as literals, which the database converts to the types of the columns, and
+/// empty values as NULL.
+fn csv_inserts(path: &Path) -> Result<String> {
+    let table = quote_identifier(&path.file_stem().unwrap_or_default().to_string_lossy());
+    let mut reader = csv::Reader::from_path(path)?;
+    let columns = reader.headers()?.iter().map(quote_identifier).join(", ");
+    let mut sql = String::new();
+    for record in reader.records() {
+        let values = record?
+            .iter()
+            .map(|value| if value.is_empty() { "NULL".to_string() } else { quote_literal(value) })
+            .join(", ");
+        writeln!(sql, "INSERT INTO {table} ({columns}) VALUES ({values});").unwrap();
+    }
+    Ok(sql)
+}
+
+/// Quotes an SQL identifier.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Quotes an SQL string literal.
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Returns the result of a database cheatcode as `(bool ok, bytes data)`, with the error message as
+/// data if it failed.
+fn try_db(result: Result) -> Result {
+    let (ok, data) = match result {
+        Ok(data) => (true, data),
+        Err(err) => (false, err.data().to_vec()),
+    };
+    Ok((ok, Bytes::from(data)).abi_encode_params())
+}
+
+/// Binds the parameters of the cheatcodes as BLOBs.
+fn bytes_params(params: &[Bytes]) -> Vec<SqlValue> {
+    params.iter().map(|p| SqlValue::Blob(p.to_vec())).collect()
+}
+
+/// Returns the selected columns of a row as bytes.
+fn select_bytes(row: &Row, selects: &[String]) -> Result<Vec<Vec<u8>>, Error> {
+    selects.iter().map(|s| row.get_bytes(s)).collect()
+}
+
+/// Parses the tuple type of the rows of a typed query.
+fn parse_row_type(row_type: &str) -> Result<DynSolType, Error> {
+    DynSolType::parse(row_type)
+        .map_err(|e| Error::Conversion(format!("invalid row type {row_type:?}: {e}")))
+}
+
+/// Returns the ABI-encoded rows of a typed query.
+fn encode_rows(rows: &[Row], row_type: &str) -> Result<Vec<Bytes>, Error> {
+    let ty = parse_row_type(row_type)?;
+    rows.iter().map(|r| r.abi_encode(&ty).map(Bytes::from)).collect()
 }
 
-fn new_client<DBClient: DatabaseClient>(
-    host: &String,
-    port: &u16,
-    user: &String,
-    password: &String,
-    name: &String,
-) -> Result<DBClient, Error> {
-    let conn_str =
-        format!("host={} port={} user={} password={} dbname={}", host, port, user, password, name);
-    let client = Client::connect(&conn_str, NoTls)?;
-    Ok(DBClient::new(client))
+/// Returns the ABI-encoded row of a typed query, or empty bytes if there is none.
+fn encode_row_opt(row: Option<Row>, row_type: &str) -> Result<Bytes, Error> {
+    let ty = parse_row_type(row_type)?;
+    Ok(row.map(|r| r.abi_encode(&ty)).transpose()?.unwrap_or_default().into())
 }
 
-impl Cheatcode for connectDbCall {
+impl Cheatcode for connectDb_0Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { host, port, user, password, name } = self;
-        let client = new_client(host, port, user, password, name).unwrap();
+        let client = PostgresClient::connect(host, *port, user, password, name)?;
+        state.set_db_client(Arc::new(client));
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for connectDb_1Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { alias } = self;
+        let client = match state.db_clients.get(alias) {
+            Some(client) => client.clone(),
+            None => {
+                let client = connect_endpoint(&state.config, alias)?;
+                state.db_clients.insert(alias.clone(), client.clone());
+                client
+            }
+        };
         state.set_db_client(client);
         Ok(Default::default())
     }
 }
 
-impl Cheatcode for openTransactionCall {
+impl Cheatcode for selectDbCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
-        let db_client = state.db_client.as_mut().unwrap();
-        let id = db_client.build_transaction().unwrap();
-        Ok(B128::from(id.to_u128_le()).abi_encode())
+        let Self { alias } = self;
+        let Some(client) = state.db_clients.get(alias) else {
+            bail!("database `{alias}` is not connected; call `connectDb(\"{alias}\")` first")
+        };
+        state.set_db_client(client.clone());
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for connectSqliteCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { path } = self;
+        let client = if path == sqlite::MEMORY {
+            SqliteClient::open(Path::new(path), false)?
+        } else {
+            let path = state.config.ensure_path_allowed(path, FsAccessKind::Read)?;
+            // Without write access the database is opened read-only, and isn't created.
+            let read_only = state.config.ensure_path_allowed(&path, FsAccessKind::Write).is_err();
+            if !read_only {
+                state.config.ensure_not_foundry_toml(&path)?;
+            }
+            SqliteClient::open(&path, read_only)?
+        };
+        state.set_db_client(Arc::new(client));
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for openTransactionCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let client = db_client(ccx.state)?.clone();
+        let id = client.build_transaction()?;
+        let depth = ccx.ecx.journaled_state.depth();
+        ccx.state.db_transactions.push(DbTransaction { id, client, depth });
+        Ok(id.to_u128_le().abi_encode())
     }
 }
 
 impl Cheatcode for commitTransactionCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { transactionId } = self;
-        let db_client = state.db_client.as_mut().unwrap();
         let uuid = Uuid::from_u128_le(*transactionId);
-        db_client.commit(uuid).unwrap();
+        let db_client = transaction_client(state, uuid)?;
+        db_client.commit(uuid)?;
+        state.db_transactions.retain(|transaction| transaction.id != uuid);
         Ok(Default::default())
     }
 }
 
 impl Cheatcode for commitTransactionsCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
-        let db_client = state.db_client.as_mut().unwrap();
-        db_client.commit_all_transcation().unwrap();
+        let db_client = db_client(state)?.clone();
+        db_client.commit_all_transcation()?;
+        state.db_transactions.retain(|transaction| !Arc::ptr_eq(&transaction.client, &db_client));
         Ok(Default::default())
     }
 }
@@ -87,17 +287,19 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
 impl Cheatcode for rollbackTransactionCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { transactionId } = self;
-        let db_client = state.db_client.as_mut().unwrap();
         let uuid = Uuid::from_u128_le(*transactionId);
-        db_client.rollback(uuid).unwrap();
+        let db_client = transaction_client(state, uuid)?;
+        db_client.rollback(uuid)?;
+        state.db_transactions.retain(|transaction| transaction.id != uuid);
         Ok(Default::default())
     }
 }
 
 impl Cheatcode for rollbackTransactionsCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
-        let db_client = state.db_client.as_mut().unwrap();
-        db_client.rollback_all_transcation().unwrap();
+        let db_client = db_client(state)?.clone();
+        db_client.rollback_all_transcation()?;
+        state.db_transactions.retain(|transaction| !Arc::ptr_eq(&transaction.client, &db_client));
         Ok(Default::default())
     }
 }
@@ -105,33 +307,30 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
 impl Cheatcode for executeCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { query, params } = self;
-        let db_client = state.db_client.as_mut().unwrap();
-        let rows_updated =
-            params.with_params(|param_refs| db_client.execute(query, param_refs).unwrap());
+        let db_client = db_client(state)?;
+        let rows_updated = db_client.execute(query, &bytes_params(params))?;
 
-        Ok(B64::from(rows_updated).abi_encode())
+        Ok(rows_updated.abi_encode())
     }
 }
 
 impl Cheatcode for executeInTransactionCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { transactionId, query, params } = self;
-        let db_client = state.db_client.as_mut().unwrap();
         let uuid = Uuid::from_u128_le(*transactionId);
-        let rows_updated = params.with_params(|param_refs| {
-            db_client.execute_in_transaction(uuid, query, param_refs).unwrap()
-        });
-        Ok(B64::from(rows_updated).abi_encode())
+        let db_client = transaction_client(state, uuid)?;
+        let rows_updated = db_client.execute_in_transaction(uuid, query, &bytes_params(params))?;
+        Ok(rows_updated.abi_encode())
     }
 }
 
 impl Cheatcode for queryOptCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { query, params, selects } = self;
-        let db_client = state.db_client.as_mut().unwrap();
-        let row = params.with_params(|param_refs| db_client.query_opt(query, param_refs).unwrap());
+        let db_client = db_client(state)?;
+        let row = db_client.query_opt(query, &bytes_params(params))?;
         if let Some(r) = row {
-            let results: Vec<Vec<u8>> = selects.iter().map(|s| r.get(s.as_str())).collect();
+            let results = select_bytes(&r, selects)?;
             return Ok(results.abi_encode());
         }
         Ok(Default::default())
@@ -141,24 +340,22 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
 impl Cheatcode for queryCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { query, params, selects } = self;
-        let db_client = state.db_client.as_mut().unwrap();
-        let rows = params.with_params(|param_refs| db_client.query(query, param_refs).unwrap());
+        let db_client = db_client(state)?;
+        let rows = db_client.query(query, &bytes_params(params))?;
         let results: Vec<Vec<Vec<u8>>> =
-            rows.iter().map(|r| selects.iter().map(|s| r.get(s.as_str())).collect()).collect();
-        return Ok(results.abi_encode());
+            rows.iter().map(|r| select_bytes(r, selects)).collect::<Result<_, _>>()?;
+        Ok(results.abi_encode())
     }
 }
 
 impl Cheatcode for queryOptInTransactionCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { transactionId, query, params, selects } = self;
-        let db_client = state.db_client.as_mut().unwrap();
         let uuid = Uuid::from_u128_le(*transactionId);
-        let row = params.with_params(|param_refs| {
-            db_client.query_opt_in_transaction(uuid, query, param_refs).unwrap()
-        });
+        let db_client = transaction_client(state, uuid)?;
+        let row = db_client.query_opt_in_transaction(uuid, query, &bytes_params(params))?;
         if let Some(r) = row {
-            let results: Vec<Vec<u8>> = selects.iter().map(|s| r.get(s.as_str())).collect();
+            let results = select_bytes(&r, selects)?;
             return Ok(results.abi_encode());
         }
         Ok(Default::default())
@@ -168,13 +365,261 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
 impl Cheatcode for queryInTransactionCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { transactionId, query, params, selects } = self;
-        let db_client = state.db_client.as_mut().unwrap();
         let uuid = Uuid::from_u128_le(*transactionId);
-        let rows = params.with_params(|param_refs| {
-            db_client.query_in_transaction(uuid, query, param_refs).unwrap()
-        });
+        let db_client = transaction_client(state, uuid)?;
+        let rows = db_client.query_in_transaction(uuid, query, &bytes_params(params))?;
         let results: Vec<Vec<Vec<u8>>> =
-            rows.iter().map(|r| selects.iter().map(|s| r.get(s.as_str())).collect()).collect();
-        return Ok(results.abi_encode());
+            rows.iter().map(|r| select_bytes(r, selects)).collect::<Result<_, _>>()?;
+        Ok(results.abi_encode())
+    }
+}
+
+impl Cheatcode for executeTypedCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { query, paramTypes, params } = self;
+        let db_client = db_client(state)?;
+        let params = decode_params(paramTypes, params)?;
+        let rows_updated = db_client.execute(query, &params)?;
+        Ok(rows_updated.abi_encode())
+    }
+}
+
+impl Cheatcode for executeTypedInTransactionCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { transactionId, query, paramTypes, params } = self;
+        let uuid = Uuid::from_u128_le(*transactionId);
+        let db_client = transaction_client(state, uuid)?;
+        let params = decode_params(paramTypes, params)?;
+        let rows_updated = db_client.execute_in_transaction(uuid, query, &params)?;
+        Ok(rows_updated.abi_encode())
+    }
+}
+
+impl Cheatcode for queryTypedCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { query, paramTypes, params, rowType } = self;
+        let db_client = db_client(state)?;
+        let params = decode_params(paramTypes, params)?;
+        let rows = db_client.query(query, &params)?;
+        Ok(encode_rows(&rows, rowType)?.abi_encode())
+    }
+}
+
+impl Cheatcode for queryOptTypedCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { query, paramTypes, params, rowType } = self;
+        let db_client = db_client(state)?;
+        let params = decode_params(paramTypes, params)?;
+        let row = db_client.query_opt(query, &params)?;
+        Ok(encode_row_opt(row, rowType)?.abi_encode())
+    }
+}
+
+impl Cheatcode for queryTypedInTransactionCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { transactionId, query, paramTypes, params, rowType } = self;
+        let uuid = Uuid::from_u128_le(*transactionId);
+        let db_client = transaction_client(state, uuid)?;
+        let params = decode_params(paramTypes, params)?;
+        let rows = db_client.query_in_transaction(uuid, query, &params)?;
+        Ok(encode_rows(&rows, rowType)?.abi_encode())
+    }
+}
+
+impl Cheatcode for queryOptTypedInTransactionCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { transactionId, query, paramTypes, params, rowType } = self;
+        let uuid = Uuid::from_u128_le(*transactionId);
+        let db_client = transaction_client(state, uuid)?;
+        let params = decode_params(paramTypes, params)?;
+        let row = db_client.query_opt_in_transaction(uuid, query, &params)?;
+        Ok(encode_row_opt(row, rowType)?.abi_encode())
+    }
+}
+
+impl Cheatcode for tryExecuteCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { query, params } = self;
+        try_db(executeCall { query: query.clone(), params: params.clone() }.apply(state))
+    }
+}
+
+impl Cheatcode for tryExecuteInTransactionCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { transactionId, query, params } = self;
+        let call = executeInTransactionCall {
+            transactionId: *transactionId,
+            query: query.clone(),
+            params: params.clone(),
+        };
+        try_db(call.apply(state))
+    }
+}
+
+impl Cheatcode for tryQueryCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { query, params, selects } = self;
+        let call =
+            queryCall { query: query.clone(), params: params.clone(), selects: selects.clone() };
+        try_db(call.apply(state))
+    }
+}
+
+impl Cheatcode for tryQueryInTransactionCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { transactionId, query, params, selects } = self;
+        let call = queryInTransactionCall {
+            transactionId: *transactionId,
+            query: query.clone(),
+            params: params.clone(),
+            selects: selects.clone(),
+        };
+        try_db(call.apply(state))
+    }
+}
+
+impl Cheatcode for tryExecuteTypedCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { query, paramTypes, params } = self;
+        let call = executeTypedCall {
+            query: query.clone(),
+            paramTypes: paramTypes.clone(),
+            params: params.clone(),
+        };
+        try_db(call.apply(state))
+    }
+}
+
+impl Cheatcode for tryExecuteTypedInTransactionCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { transactionId, query, paramTypes, params } = self;
+        let call = executeTypedInTransactionCall {
+            transactionId: *transactionId,
+            query: query.clone(),
+            paramTypes: paramTypes.clone(),
+            params: params.clone(),
+        };
+        try_db(call.apply(state))
+    }
+}
+
+impl Cheatcode for tryQueryTypedCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { query, paramTypes, params, rowType } = self;
+        let call = queryTypedCall {
+            query: query.clone(),
+            paramTypes: paramTypes.clone(),
+            params: params.clone(),
+            rowType: rowType.clone(),
+        };
+        try_db(call.apply(state))
+    }
+}
+
+impl Cheatcode for tryQueryTypedInTransactionCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { transactionId, query, paramTypes, params, rowType } = self;
+        let call = queryTypedInTransactionCall {
+            transactionId: *transactionId,
+            query: query.clone(),
+            paramTypes: paramTypes.clone(),
+            params: params.clone(),
+            rowType: rowType.clone(),
+        };
+        try_db(call.apply(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_config::{Config, DbEndpoint, DbEndpoints};
+
+    #[test]
+    fn connects_endpoints() {
+        let root = std::env::temp_dir().join(format!("foundry-db-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        let url = "sqlite:endpoint.db";
+        let config = Config {
+            root: root.clone(),
+            db_endpoints: DbEndpoints::new([
+                (
+                    "writable",
+                    DbEndpoint {
+                        url: url.into(),
+                        writers: Some(vec!["Deploy*".into()]),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "readonly",
+                    DbEndpoint { url: url.into(), writers: Some(vec![]), ..Default::default() },
+                ),
+                ("unsupported", DbEndpoint::new("mysql://localhost/db")),
+            ]),
+            ..Default::default()
+        };
+        let config =
+            CheatsConfig::new(&config, Default::default(), None, Some("DeployScript".into()), None);
+
+        let writable = connect_endpoint(&config, "writable").unwrap();
+        writable.execute("CREATE TABLE users (name TEXT)", &[]).unwrap();
+        let readonly = connect_endpoint(&config, "readonly").unwrap();
+        assert_eq!(readonly.query("SELECT name FROM users", &[]).unwrap().len(), 0);
+        let err = readonly.execute("INSERT INTO users VALUES ('alice')", &[]).unwrap_err();
+        assert_eq!(err.to_string(), "SQLite error: attempt to write a readonly database");
+
+        let err = connect_endpoint(&config, "missing").unwrap_err();
+        assert_eq!(err.to_string(), "database endpoint `missing` not found in `[db_endpoints]`");
+        assert!(connect_endpoint(&config, "unsupported").is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn loads_fixtures() {
+        let root = std::env::temp_dir().join(format!("foundry-db-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("schema.sql"), "CREATE TABLE users (id INTEGER, name TEXT);")
+            .unwrap();
+        std::fs::write(root.join("users.csv"), "id,name\n1,alice\n2,\"o'brien, jr\"\n3,\n")
+            .unwrap();
+        let config = Config {
+            root: root.clone(),
+            db_endpoints: DbEndpoints::new([(
+                "isolated",
+                DbEndpoint {
+                    url: "sqlite::memory:".into(),
+                    isolate: true,
+                    fixtures: vec!["schema.sql".into(), "users.csv".into()],
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+        let config = CheatsConfig::new(&config, Default::default(), None, None, None);
+
+        let client = connect_endpoint(&config, "isolated").unwrap();
+        let rows = client.query("SELECT id, name FROM users ORDER BY id", &[]).unwrap();
+        let users = rows
+            .iter()
+            .map(|row| (row.get("id").unwrap().clone(), row.get("name").unwrap().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            users,
+            [
+                (SqlValue::Integer(1), SqlValue::Text("alice".into())),
+                (SqlValue::Integer(2), SqlValue::Text("o'brien, jr".into())),
+                (SqlValue::Integer(3), SqlValue::Null),
+            ]
+        );
+
+        let scope = client.scope().unwrap().unwrap();
+        scope.execute("DELETE FROM users", &[]).unwrap();
+        assert_eq!(scope.query("SELECT id FROM users", &[]).unwrap().len(), 0);
+        drop(scope);
+        assert_eq!(client.query("SELECT id FROM users", &[]).unwrap().len(), 3);
+
+        std::fs::remove_dir_all(root).unwrap();
     }
 }
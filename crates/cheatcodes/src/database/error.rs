@@ -2,20 +2,34 @@
 #[derive(Debug)]
 pub enum Error {
     Postgres(DbError),
+    Sqlite(rusqlite::Error),
     NotFound(String),
+    Conversion(String),
+    TooManyRows,
+    Unsupported(String),
 }
 
 impl From<DbError> for Error {
     fn from(err: DbError) -> Self {
-        Error::Postgres(err)
+        Self::Postgres(err)
+    }
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Sqlite(err)
     }
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::Postgres(err) => write!(f, "PostgreSQL error: {}", err),
-            Error::NotFound(msg) => write!(f, "Not found: {}", msg),
+            Self::Postgres(err) => write!(f, "PostgreSQL error: {err}"),
+            Self::Sqlite(err) => write!(f, "SQLite error: {err}"),
+            Self::NotFound(msg) => write!(f, "Not found: {msg}"),
+            Self::Conversion(msg) => write!(f, "Conversion error: {msg}"),
+            Self::TooManyRows => write!(f, "Query returned more than one row"),
+            Self::Unsupported(msg) => write!(f, "Unsupported: {msg}"),
         }
     }
 }
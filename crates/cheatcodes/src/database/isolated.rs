@@ -0,0 +1,200 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use uuid::Uuid;
+
+use super::{
+    client::DatabaseClient,
+    error::Error,
+    value::{Row, SqlValue},
+};
+
+/// Opens a new connection to the database of an isolated endpoint.
+pub type Connect = dyn Fn() -> Result<Box<dyn DatabaseClient>, Error> + Send + Sync;
+
+/// A statement run through an isolated connection.
+#[derive(Clone, Debug)]
+enum Statement {
+    Batch(String),
+    Query(String, Vec<SqlValue>),
+}
+
+/// A connection whose changes are never committed.
+///
+/// The connection runs in a transaction, which is rolled back when it's dropped. Each test runs in
+/// a scope of its own: a new connection, in its own transaction, in which the statements run so
+/// far (e.g. by the fixtures and `setUp()`) are replayed. Tests running in parallel thus neither
+/// wait for nor see each other's changes.
+pub struct IsolatedClient {
+    client: Box<dyn DatabaseClient>,
+    connect: Arc<Connect>,
+    statements: Mutex<Vec<Statement>>,
+}
+
+impl fmt::Debug for IsolatedClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IsolatedClient").field("client", &self.client).finish_non_exhaustive()
+    }
+}
+
+impl IsolatedClient {
+    /// Opens a connection with `connect` and begins its transaction.
+    pub fn new(connect: Arc<Connect>) -> Result<Self, Error> {
+        let client = connect()?;
+        client.batch_execute("BEGIN")?;
+        Ok(Self { client, connect, statements: Default::default() })
+    }
+
+    fn record(&self, statement: Statement) {
+        self.statements.lock().unwrap().push(statement);
+    }
+
+    fn unsupported() -> Error {
+        Error::Unsupported(
+            "transactions of isolated databases, whose tests already run in a transaction"
+                .to_string(),
+        )
+    }
+}
+
+impl DatabaseClient for IsolatedClient {
+    fn build_transaction(&self) -> Result<Uuid, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn batch_execute(&self, sql: &str) -> Result<(), Error> {
+        self.client.batch_execute(sql)?;
+        self.record(Statement::Batch(sql.to_string()));
+        Ok(())
+    }
+
+    fn execute(&self, query: &str, params: &[SqlValue]) -> Result<u64, Error> {
+        let rows = self.client.execute(query, params)?;
+        self.record(Statement::Query(query.to_string(), params.to_vec()));
+        Ok(rows)
+    }
+
+    // Queries may write too, e.g. `INSERT ... RETURNING`, so they're replayed as well.
+    fn query(&self, query: &str, params: &[SqlValue]) -> Result<Vec<Row>, Error> {
+        let rows = self.client.query(query, params)?;
+        self.record(Statement::Query(query.to_string(), params.to_vec()));
+        Ok(rows)
+    }
+
+    fn query_opt(&self, query: &str, params: &[SqlValue]) -> Result<Option<Row>, Error> {
+        let row = self.client.query_opt(query, params)?;
+        self.record(Statement::Query(query.to_string(), params.to_vec()));
+        Ok(row)
+    }
+
+    fn execute_in_transaction(&self, _: Uuid, _: &str, _: &[SqlValue]) -> Result<u64, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn query_in_transaction(&self, _: Uuid, _: &str, _: &[SqlValue]) -> Result<Vec<Row>, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn query_opt_in_transaction(
+        &self,
+        _: Uuid,
+        _: &str,
+        _: &[SqlValue],
+    ) -> Result<Option<Row>, Error> {
+        Err(Self::unsupported())
+    }
+
+    fn commit(&self, _: Uuid) -> Result<(), Error> {
+        Err(Self::unsupported())
+    }
+
+    fn commit_all_transcation(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn rollback(&self, _: Uuid) -> Result<(), Error> {
+        Err(Self::unsupported())
+    }
+
+    fn rollback_all_transcation(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn scope(&self) -> Result<Option<Arc<dyn DatabaseClient>>, Error> {
+        let scope = Self::new(self.connect.clone())?;
+        let statements = self.statements.lock().unwrap().clone();
+        for statement in &statements {
+            match statement {
+                Statement::Batch(sql) => scope.client.batch_execute(sql)?,
+                Statement::Query(query, params) => {
+                    scope.client.query(query, params)?;
+                }
+            }
+        }
+        *scope.statements.lock().unwrap() = statements;
+        Ok(Some(Arc::new(scope)))
+    }
+}
+
+impl Drop for IsolatedClient {
+    fn drop(&mut self) {
+        if let Err(err) = self.client.batch_execute("ROLLBACK") {
+            debug!(target: "cheatcodes", %err, "failed to roll back isolated database connection");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc, thread};
+
+    use super::*;
+    use crate::database::sqlite::{SqliteClient, MEMORY};
+
+    fn open() -> IsolatedClient {
+        IsolatedClient::new(Arc::new(|| {
+            Ok(Box::new(SqliteClient::open(Path::new(MEMORY), false)?) as Box<dyn DatabaseClient>)
+        }))
+        .unwrap()
+    }
+
+    fn count(client: &dyn DatabaseClient) -> usize {
+        client.query("SELECT name FROM users", &[]).unwrap().len()
+    }
+
+    #[test]
+    fn rolls_back_scopes() {
+        let client = open();
+        client.execute("CREATE TABLE users (name TEXT)", &[]).unwrap();
+        client.execute("INSERT INTO users VALUES (?1)", &[SqlValue::Text("alice".into())]).unwrap();
+
+        for _ in 0..2 {
+            let scope = client.scope().unwrap().unwrap();
+            assert_eq!(count(&*scope), 1);
+            scope.execute("INSERT INTO users VALUES ('bob')", &[]).unwrap();
+            assert_eq!(count(&*scope), 2);
+            assert_eq!(count(&client), 1);
+        }
+        assert!(matches!(client.build_transaction(), Err(Error::Unsupported(_))));
+    }
+
+    #[test]
+    fn scopes_run_in_parallel() {
+        let client = open();
+        client.execute("CREATE TABLE users (name TEXT)", &[]).unwrap();
+
+        let scope = client.scope().unwrap().unwrap();
+        scope.execute("INSERT INTO users VALUES ('alice')", &[]).unwrap();
+
+        // Another test neither waits for the first one nor sees its changes.
+        let other = client.scope().unwrap().unwrap();
+        let other = thread::spawn(move || {
+            other.execute("INSERT INTO users VALUES ('bob'), ('carol')", &[]).unwrap();
+            count(&*other)
+        });
+        assert_eq!(other.join().unwrap(), 2);
+        assert_eq!(count(&*scope), 1);
+    }
+}
@@ -0,0 +1,443 @@
+use std::{
+    collections::HashMap,
+    error::Error as StdError,
+    fmt,
+    mem::transmute,
+    sync::{Arc, Mutex},
+};
+
+use bytes::BytesMut;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use postgres::{
+    types::{to_sql_checked, FromSql, IsNull, ToSql, Type},
+    Client, NoTls, Transaction,
+};
+use uuid::Uuid;
+
+use super::{
+    client::DatabaseClient,
+    error::Error,
+    value::{Row, SqlValue},
+};
+
+struct TransactionWrapper(Transaction<'static>);
+impl TransactionWrapper {
+    fn new(transaction: Transaction<'_>) -> Self {
+        // Safety: We're ensuring the transaction lives as long as needed through Arc<Mutex<>>
+        let transaction =
+            unsafe { transmute::<Transaction<'_>, Transaction<'static>>(transaction) };
+        Self(transaction)
+    }
+}
+
+pub struct PostgresClient {
+    client: Arc<Mutex<Client>>,
+    transactions: Arc<Mutex<HashMap<Uuid, TransactionWrapper>>>,
+}
+
+impl fmt::Debug for PostgresClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DbClient").field("client", &"Client").finish()
+    }
+}
+
+impl Clone for PostgresClient {
+    fn clone(&self) -> Self {
+        Self { client: Arc::clone(&self.client), transactions: Arc::clone(&self.transactions) }
+    }
+}
+
+impl PostgresClient {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client: Arc::new(Mutex::new(client)),
+            transactions: Arc::new(Mutex::new(HashMap::default())),
+        }
+    }
+
+    /// Connects to a PostgreSQL server.
+    pub fn connect(
+        host: &str,
+        port: u16,
+        user: &str,
+        password: &str,
+        name: &str,
+    ) -> Result<Self, Error> {
+        let conn_str =
+            format!("host={host} port={port} user={user} password={password} dbname={name}");
+        let client = Client::connect(&conn_str, NoTls)?;
+        Ok(Self::new(client))
+    }
+
+    /// Connects to a PostgreSQL server by url, with read-only transactions if `read_only`.
+    ///
+    /// The read-only mode is only the default of the session, which guards against accidental
+    /// writes, but which a statement can change. Connect as a role without write privileges to
+    /// enforce it.
+    pub fn connect_url(url: &str, read_only: bool) -> Result<Self, Error> {
+        let mut client = Client::connect(url, NoTls)?;
+        if read_only {
+            client.batch_execute("SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY")?;
+        }
+        Ok(Self::new(client))
+    }
+}
+
+impl DatabaseClient for PostgresClient {
+    fn build_transaction(&self) -> Result<Uuid, Error> {
+        let mut client = self.client.lock().unwrap();
+        let transaction = client.transaction()?;
+        let transaction_id = Uuid::new_v4();
+        self.transactions
+            .lock()
+            .unwrap()
+            .insert(transaction_id, TransactionWrapper::new(transaction));
+        Ok(transaction_id)
+    }
+
+    fn batch_execute(&self, sql: &str) -> Result<(), Error> {
+        Ok(self.client.lock().unwrap().batch_execute(sql)?)
+    }
+
+    fn execute(&self, query: &str, params: &[SqlValue]) -> Result<u64, Error> {
+        let mut client = self.client.lock().unwrap();
+        client.execute(query, &to_params(params)).map_err(Error::Postgres)
+    }
+
+    fn query(&self, query: &str, params: &[SqlValue]) -> Result<Vec<Row>, Error> {
+        let mut client = self.client.lock().unwrap();
+        let rows = client.query(query, &to_params(params))?;
+        Ok(rows.iter().map(to_row).collect())
+    }
+
+    fn query_opt(&self, query: &str, params: &[SqlValue]) -> Result<Option<Row>, Error> {
+        let mut client = self.client.lock().unwrap();
+        let row = client.query_opt(query, &to_params(params))?;
+        Ok(row.as_ref().map(to_row))
+    }
+
+    fn execute_in_transaction(
+        &self,
+        id: Uuid,
+        query: &str,
+        params: &[SqlValue],
+    ) -> Result<u64, Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        if let Some(transaction) = transactions.get_mut(&id) {
+            return transaction.0.execute(query, &to_params(params)).map_err(Error::Postgres);
+        }
+        Err(Error::NotFound("Transaction not found".to_string()))
+    }
+
+    fn query_in_transaction(
+        &self,
+        id: Uuid,
+        query: &str,
+        params: &[SqlValue],
+    ) -> Result<Vec<Row>, Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        if let Some(transaction) = transactions.get_mut(&id) {
+            let rows = transaction.0.query(query, &to_params(params))?;
+            return Ok(rows.iter().map(to_row).collect());
+        }
+        Err(Error::NotFound("Transaction not found".to_string()))
+    }
+
+    fn query_opt_in_transaction(
+        &self,
+        id: Uuid,
+        query: &str,
+        params: &[SqlValue],
+    ) -> Result<Option<Row>, Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        if let Some(transaction) = transactions.get_mut(&id) {
+            let row = transaction.0.query_opt(query, &to_params(params))?;
+            return Ok(row.as_ref().map(to_row));
+        }
+        Err(Error::NotFound("Transaction not found".to_string()))
+    }
+
+    fn commit(&self, id: Uuid) -> Result<(), Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        if let Some(transaction) = transactions.remove(&id) {
+            return transaction.0.commit().map_err(Error::Postgres);
+        }
+        Err(Error::NotFound("Transaction not found".to_string()))
+    }
+
+    fn commit_all_transcation(&self) -> Result<(), Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        for (_, transaction) in transactions.drain() {
+            transaction.0.commit().map_err(Error::Postgres)?;
+        }
+        Ok(())
+    }
+
+    fn rollback(&self, id: Uuid) -> Result<(), Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        if let Some(transaction) = transactions.remove(&id) {
+            return transaction.0.rollback().map_err(Error::Postgres);
+        }
+        Err(Error::NotFound("Transaction not found".to_string()))
+    }
+
+    fn rollback_all_transcation(&self) -> Result<(), Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        for (_, transaction) in transactions.drain() {
+            transaction.0.rollback().map_err(Error::Postgres)?;
+        }
+        Ok(())
+    }
+}
+
+fn to_params(params: &[SqlValue]) -> Vec<&(dyn ToSql + Sync)> {
+    params.iter().map(|p| p as &(dyn ToSql + Sync)).collect()
+}
+
+/// Converts the columns of a row, keeping the columns of unsupported types as errors.
+fn to_row(row: &postgres::Row) -> Row {
+    let columns = row
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let value = match *column.type_() {
+                Type::BOOL => row.try_get::<_, Option<bool>>(i).map(|v| v.map(SqlValue::Bool)),
+                Type::BYTEA => row.try_get::<_, Option<Vec<u8>>>(i).map(|v| v.map(SqlValue::Blob)),
+                Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                    row.try_get::<_, Option<String>>(i).map(|v| v.map(SqlValue::Text))
+                }
+                Type::INT2 => {
+                    row.try_get::<_, Option<i16>>(i).map(|v| v.map(|v| SqlValue::Integer(v.into())))
+                }
+                Type::INT4 => {
+                    row.try_get::<_, Option<i32>>(i).map(|v| v.map(|v| SqlValue::Integer(v.into())))
+                }
+                Type::INT8 => row.try_get::<_, Option<i64>>(i).map(|v| v.map(SqlValue::Integer)),
+                Type::NUMERIC => {
+                    row.try_get::<_, Option<Numeric>>(i).map(|v| v.map(|v| SqlValue::Numeric(v.0)))
+                }
+                Type::FLOAT4 => {
+                    row.try_get::<_, Option<f32>>(i).map(|v| v.map(|v| SqlValue::Real(v.into())))
+                }
+                Type::FLOAT8 => row.try_get::<_, Option<f64>>(i).map(|v| v.map(SqlValue::Real)),
+                Type::TIMESTAMP => row
+                    .try_get::<_, Option<NaiveDateTime>>(i)
+                    .map(|v| v.map(|v| SqlValue::Timestamp(v.and_utc().timestamp()))),
+                Type::TIMESTAMPTZ => row
+                    .try_get::<_, Option<DateTime<Utc>>>(i)
+                    .map(|v| v.map(|v| SqlValue::Timestamp(v.timestamp()))),
+                Type::DATE => row.try_get::<_, Option<NaiveDate>>(i).map(|v| {
+                    v.map(|v| SqlValue::Timestamp(v.and_time(NaiveTime::MIN).and_utc().timestamp()))
+                }),
+                ref ty => {
+                    let err = format!("column {} has unsupported type {ty}", column.name());
+                    return (column.name().to_string(), Err(err));
+                }
+            };
+            let value = value.map(|v| v.unwrap_or(SqlValue::Null)).map_err(|e| e.to_string());
+            (column.name().to_string(), value)
+        })
+        .collect();
+    Row::new(columns)
+}
+
+impl ToSql for SqlValue {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        match self {
+            Self::Null => Ok(IsNull::Yes),
+            Self::Bool(v) => v.to_sql_checked(ty, out),
+            Self::Integer(v) | Self::Timestamp(v) => match *ty {
+                Type::INT2 => i16::try_from(*v)?.to_sql_checked(ty, out),
+                Type::INT4 => i32::try_from(*v)?.to_sql_checked(ty, out),
+                Type::NUMERIC => Numeric(v.to_string()).to_sql_checked(ty, out),
+                Type::TEXT | Type::VARCHAR | Type::BPCHAR => v.to_string().to_sql_checked(ty, out),
+                Type::TIMESTAMP => timestamp(*v)?.naive_utc().to_sql_checked(ty, out),
+                Type::TIMESTAMPTZ => timestamp(*v)?.to_sql_checked(ty, out),
+                _ => v.to_sql_checked(ty, out),
+            },
+            Self::Numeric(v) => match *ty {
+                Type::INT2 | Type::INT4 | Type::INT8 => Self::Integer(v.parse()?).to_sql(ty, out),
+                Type::TEXT | Type::VARCHAR | Type::BPCHAR => v.to_sql_checked(ty, out),
+                _ => Numeric(v.clone()).to_sql_checked(ty, out),
+            },
+            Self::Real(v) => match *ty {
+                Type::FLOAT4 => (*v as f32).to_sql_checked(ty, out),
+                _ => v.to_sql_checked(ty, out),
+            },
+            Self::Text(v) => match *ty {
+                Type::NUMERIC => Numeric(v.clone()).to_sql_checked(ty, out),
+                _ => v.to_sql_checked(ty, out),
+            },
+            Self::Blob(v) => v.to_sql_checked(ty, out),
+        }
+    }
+
+    // The conversion is checked against the type of the parameter by `to_sql`.
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+fn timestamp(secs: i64) -> Result<DateTime<Utc>, Box<dyn StdError + Sync + Send>> {
+    DateTime::from_timestamp(secs, 0)
+        .ok_or_else(|| format!("timestamp {secs} is out of range").into())
+}
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+
+/// A NUMERIC value, as a decimal number.
+///
+/// NUMERIC values are sent as base 10000 digits, with the weight of the first digit and the number
+/// of decimal digits after the point.
+#[derive(Debug)]
+struct Numeric(String);
+
+impl<'a> FromSql<'a> for Numeric {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn StdError + Sync + Send>> {
+        let word = |i: usize| {
+            raw.get(i * 2..i * 2 + 2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .ok_or("invalid NUMERIC value")
+        };
+        let ndigits = word(0)? as usize;
+        let weight = word(1)? as i16 as i32;
+        let sign = word(2)?;
+        let dscale = word(3)? as usize;
+        let digits = (0..ndigits).map(|i| word(4 + i)).collect::<Result<Vec<_>, _>>()?;
+        if sign == NUMERIC_NAN {
+            return Err("NaN NUMERIC values are not supported".into());
+        }
+
+        let digit =
+            |i: i32| usize::try_from(i).ok().and_then(|i| digits.get(i)).copied().unwrap_or(0);
+        let mut s = String::new();
+        if sign == NUMERIC_NEG {
+            s.push('-');
+        }
+        if weight < 0 {
+            s.push('0');
+        } else {
+            s.push_str(&digit(0).to_string());
+            for i in 1..=weight {
+                s.push_str(&format!("{:04}", digit(i)));
+            }
+        }
+        if dscale > 0 {
+            let mut frac = String::new();
+            let mut i = weight + 1;
+            while frac.len() < dscale {
+                frac.push_str(&format!("{:04}", digit(i)));
+                i += 1;
+            }
+            frac.truncate(dscale);
+            s.push('.');
+            s.push_str(&frac);
+        }
+        Ok(Self(s))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+}
+
+impl ToSql for Numeric {
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        let s = self.0.trim();
+        let (sign, s) = match s.strip_prefix('-') {
+            Some(s) => (NUMERIC_NEG, s),
+            None => (NUMERIC_POS, s),
+        };
+        let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+        if int.is_empty() && frac.is_empty() ||
+            !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+        {
+            return Err(format!("invalid NUMERIC value {}", self.0).into());
+        }
+        let dscale = u16::try_from(frac.len())?;
+
+        // Pad the integer and fractional parts to whole base 10000 digits.
+        let int = int.trim_start_matches('0');
+        let int = format!("{}{int}", "0".repeat((4 - int.len() % 4) % 4));
+        let frac = format!("{frac}{}", "0".repeat((4 - frac.len() % 4) % 4));
+        let mut weight = i16::try_from(int.len() / 4)? - 1;
+        let mut digits = int
+            .as_bytes()
+            .chunks(4)
+            .chain(frac.as_bytes().chunks(4))
+            .map(|chunk| std::str::from_utf8(chunk).unwrap().parse::<u16>().unwrap())
+            .collect::<Vec<_>>();
+        let leading = digits.iter().take_while(|d| **d == 0).count();
+        digits.drain(..leading);
+        weight -= leading as i16;
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        let sign = if digits.is_empty() {
+            weight = 0;
+            NUMERIC_POS
+        } else {
+            sign
+        };
+
+        for word in
+            [u16::try_from(digits.len())?, weight as u16, sign, dscale].into_iter().chain(digits)
+        {
+            out.extend_from_slice(&word.to_be_bytes());
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_roundtrip() {
+        for (value, expected) in [
+            ("0", "0"),
+            ("-0.00", "0.00"),
+            ("1", "1"),
+            ("10000", "10000"),
+            ("-12345.6789", "-12345.6789"),
+            ("0.0001", "0.0001"),
+            ("0.00000001", "0.00000001"),
+            ("007.50", "7.50"),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+            ),
+        ] {
+            let mut out = BytesMut::new();
+            Numeric(value.to_string()).to_sql(&Type::NUMERIC, &mut out).unwrap();
+            assert_eq!(Numeric::from_sql(&Type::NUMERIC, &out).unwrap().0, expected, "{value}");
+        }
+
+        // 1234.5 as sent by PostgreSQL: the digits 1234 and 5000 with weight 0.
+        let raw = [0, 2, 0, 0, 0, 0, 0, 1, 0x04, 0xd2, 0x13, 0x88];
+        assert_eq!(Numeric::from_sql(&Type::NUMERIC, &raw).unwrap().0, "1234.5");
+
+        let mut out = BytesMut::new();
+        assert!(Numeric("1e5".to_string()).to_sql(&Type::NUMERIC, &mut out).is_err());
+    }
+}
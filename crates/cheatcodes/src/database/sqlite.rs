@@ -0,0 +1,297 @@
+use std::{collections::HashMap, fmt, path::Path, sync::Mutex, time::Duration};
+
+use rusqlite::{
+    backup::Backup,
+    params_from_iter,
+    types::{ToSqlOutput, ValueRef},
+    Connection, OpenFlags, ToSql,
+};
+use uuid::Uuid;
+
+use super::{
+    client::DatabaseClient,
+    error::Error,
+    value::{Row, SqlValue},
+};
+
+/// The path which opens an in-memory database.
+pub const MEMORY: &str = ":memory:";
+
+/// An embedded SQLite database.
+///
+/// SQLite has no concurrent transactions on a single connection, so each transaction runs on its
+/// own connection to the database, and is subject to SQLite's locking.
+pub struct SqliteClient {
+    uri: String,
+    flags: OpenFlags,
+    connection: Mutex<Connection>,
+    transactions: Mutex<HashMap<Uuid, Connection>>,
+}
+
+impl fmt::Debug for SqliteClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteClient").field("uri", &self.uri).finish_non_exhaustive()
+    }
+}
+
+impl SqliteClient {
+    /// Opens the database at `path`, creating it unless `read_only`, or a new in-memory database
+    /// for [`MEMORY`].
+    pub fn open(path: &Path, read_only: bool) -> Result<Self, Error> {
+        let (uri, flags) = if path == Path::new(MEMORY) {
+            // A named in-memory database is shared by the connections of the transactions, and
+            // lives as long as the main connection.
+            (
+                format!("file:foundry-{}?mode=memory&cache=shared", Uuid::new_v4()),
+                OpenFlags::default(),
+            )
+        } else if read_only {
+            let flags = OpenFlags::SQLITE_OPEN_READ_ONLY |
+                OpenFlags::SQLITE_OPEN_NO_MUTEX |
+                OpenFlags::SQLITE_OPEN_URI;
+            (path.to_string_lossy().into_owned(), flags)
+        } else {
+            (path.to_string_lossy().into_owned(), OpenFlags::default())
+        };
+        let connection = Connection::open_with_flags(&uri, flags)?;
+        Ok(Self {
+            uri,
+            flags,
+            connection: Mutex::new(connection),
+            transactions: Mutex::new(HashMap::default()),
+        })
+    }
+
+    /// Opens a new in-memory copy of the database at `path`, which is never written. A missing
+    /// database is copied as an empty one.
+    pub fn open_copy(path: &Path) -> Result<Self, Error> {
+        let client = Self::open(Path::new(MEMORY), false)?;
+        if path.exists() {
+            let source = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+            let mut connection = client.connection.lock().unwrap();
+            // The whole database is copied at once, retrying while it's locked by a writer.
+            Backup::new(&source, &mut connection)?.run_to_completion(
+                i32::MAX,
+                Duration::from_millis(10),
+                None,
+            )?;
+        }
+        Ok(client)
+    }
+
+    fn with_transaction<R>(
+        &self,
+        id: Uuid,
+        f: impl FnOnce(&Connection) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let transactions = self.transactions.lock().unwrap();
+        match transactions.get(&id) {
+            Some(connection) => f(connection),
+            None => Err(Error::NotFound("Transaction not found".to_string())),
+        }
+    }
+
+    fn end_transaction(&self, id: Uuid, statement: &str) -> Result<(), Error> {
+        let connection = self.transactions.lock().unwrap().remove(&id);
+        match connection {
+            Some(connection) => Ok(connection.execute_batch(statement)?),
+            None => Err(Error::NotFound("Transaction not found".to_string())),
+        }
+    }
+
+    fn end_all_transactions(&self, statement: &str) -> Result<(), Error> {
+        let mut transactions = self.transactions.lock().unwrap();
+        for (_, connection) in transactions.drain() {
+            connection.execute_batch(statement)?;
+        }
+        Ok(())
+    }
+}
+
+impl DatabaseClient for SqliteClient {
+    fn build_transaction(&self) -> Result<Uuid, Error> {
+        let connection = Connection::open_with_flags(&self.uri, self.flags)?;
+        connection.execute_batch("BEGIN")?;
+        let transaction_id = Uuid::new_v4();
+        self.transactions.lock().unwrap().insert(transaction_id, connection);
+        Ok(transaction_id)
+    }
+
+    fn batch_execute(&self, sql: &str) -> Result<(), Error> {
+        Ok(self.connection.lock().unwrap().execute_batch(sql)?)
+    }
+
+    fn execute(&self, query: &str, params: &[SqlValue]) -> Result<u64, Error> {
+        execute(&self.connection.lock().unwrap(), query, params)
+    }
+
+    fn query(&self, query: &str, params: &[SqlValue]) -> Result<Vec<Row>, Error> {
+        query_rows(&self.connection.lock().unwrap(), query, params)
+    }
+
+    fn query_opt(&self, query: &str, params: &[SqlValue]) -> Result<Option<Row>, Error> {
+        query_opt(&self.connection.lock().unwrap(), query, params)
+    }
+
+    fn execute_in_transaction(
+        &self,
+        id: Uuid,
+        query: &str,
+        params: &[SqlValue],
+    ) -> Result<u64, Error> {
+        self.with_transaction(id, |connection| execute(connection, query, params))
+    }
+
+    fn query_in_transaction(
+        &self,
+        id: Uuid,
+        query: &str,
+        params: &[SqlValue],
+    ) -> Result<Vec<Row>, Error> {
+        self.with_transaction(id, |connection| query_rows(connection, query, params))
+    }
+
+    fn query_opt_in_transaction(
+        &self,
+        id: Uuid,
+        query: &str,
+        params: &[SqlValue],
+    ) -> Result<Option<Row>, Error> {
+        self.with_transaction(id, |connection| query_opt(connection, query, params))
+    }
+
+    fn commit(&self, id: Uuid) -> Result<(), Error> {
+        self.end_transaction(id, "COMMIT")
+    }
+
+    fn commit_all_transcation(&self) -> Result<(), Error> {
+        self.end_all_transactions("COMMIT")
+    }
+
+    fn rollback(&self, id: Uuid) -> Result<(), Error> {
+        self.end_transaction(id, "ROLLBACK")
+    }
+
+    fn rollback_all_transcation(&self) -> Result<(), Error> {
+        self.end_all_transactions("ROLLBACK")
+    }
+}
+
+fn execute(connection: &Connection, query: &str, params: &[SqlValue]) -> Result<u64, Error> {
+    let rows = connection.execute(query, params_from_iter(params))?;
+    Ok(rows as u64)
+}
+
+fn query_rows(
+    connection: &Connection,
+    query: &str,
+    params: &[SqlValue],
+) -> Result<Vec<Row>, Error> {
+    let mut statement = connection.prepare(query)?;
+    let names: Vec<String> = statement.column_names().into_iter().map(String::from).collect();
+    let rows = statement.query_map(params_from_iter(params), |row| {
+        let columns = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| Ok((name.clone(), Ok(to_value(row.get_ref(i)?)))))
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(Row::new(columns))
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+fn query_opt(
+    connection: &Connection,
+    query: &str,
+    params: &[SqlValue],
+) -> Result<Option<Row>, Error> {
+    let mut rows = query_rows(connection, query, params)?;
+    if rows.len() > 1 {
+        return Err(Error::TooManyRows);
+    }
+    Ok(rows.pop())
+}
+
+fn to_value(value: ValueRef<'_>) -> SqlValue {
+    match value {
+        ValueRef::Null => SqlValue::Null,
+        ValueRef::Integer(v) => SqlValue::Integer(v),
+        ValueRef::Real(v) => SqlValue::Real(v),
+        ValueRef::Text(v) => SqlValue::Text(String::from_utf8_lossy(v).into_owned()),
+        ValueRef::Blob(v) => SqlValue::Blob(v.to_vec()),
+    }
+}
+
+impl ToSql for SqlValue {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let value = match self {
+            Self::Null => ValueRef::Null,
+            Self::Bool(v) => ValueRef::Integer(*v as i64),
+            Self::Integer(v) | Self::Timestamp(v) => ValueRef::Integer(*v),
+            // Bound as TEXT to keep its precision, which the affinity of the column may convert.
+            Self::Numeric(v) => ValueRef::Text(v.as_bytes()),
+            Self::Real(v) => ValueRef::Real(*v),
+            Self::Text(v) => ValueRef::Text(v.as_bytes()),
+            Self::Blob(v) => ValueRef::Blob(v),
+        };
+        Ok(ToSqlOutput::Borrowed(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(client: &SqliteClient) -> Vec<SqlValue> {
+        let rows = client.query("SELECT name FROM users ORDER BY id", &[]).unwrap();
+        rows.iter().map(|row| row.get("name").unwrap().clone()).collect()
+    }
+
+    #[test]
+    fn queries_in_transactions() {
+        let client = SqliteClient::open(Path::new(MEMORY), false).unwrap();
+        client.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)", &[]).unwrap();
+        let alice = SqlValue::Text("alice".to_string());
+        let insert = "INSERT INTO users (name) VALUES (?1)";
+        assert_eq!(client.execute(insert, std::slice::from_ref(&alice)).unwrap(), 1);
+
+        let committed = client.build_transaction().unwrap();
+        client.execute_in_transaction(committed, insert, &[SqlValue::Null]).unwrap();
+        client.commit(committed).unwrap();
+
+        let rolled_back = client.build_transaction().unwrap();
+        client.execute_in_transaction(rolled_back, insert, &[SqlValue::Integer(1)]).unwrap();
+        let rows = client.query_in_transaction(rolled_back, "SELECT id FROM users", &[]).unwrap();
+        assert_eq!(rows.len(), 3);
+        client.rollback(rolled_back).unwrap();
+
+        assert_eq!(names(&client), [alice, SqlValue::Null]);
+        assert!(matches!(client.commit(rolled_back), Err(Error::NotFound(_))));
+        assert!(matches!(client.query_opt("SELECT name FROM users", &[]), Err(Error::TooManyRows)));
+
+        let row = client.query_opt("SELECT name FROM users WHERE id = 1", &[]).unwrap().unwrap();
+        assert_eq!(row.get_bytes("name").unwrap(), b"alice");
+        assert!(matches!(row.get("id"), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn copies_databases() {
+        let path = std::env::temp_dir().join(format!("foundry-db-{}.sqlite", Uuid::new_v4()));
+        let client = SqliteClient::open(&path, false).unwrap();
+        client.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)", &[]).unwrap();
+        client.execute("INSERT INTO users (name) VALUES ('alice')", &[]).unwrap();
+
+        let copy = SqliteClient::open_copy(&path).unwrap();
+        copy.execute("INSERT INTO users (name) VALUES ('bob')", &[]).unwrap();
+        let transaction = copy.build_transaction().unwrap();
+        let rows = copy.query_in_transaction(transaction, "SELECT id FROM users", &[]).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(names(&client), [SqlValue::Text("alice".to_string())]);
+
+        let missing = SqliteClient::open_copy(&path.with_extension("missing")).unwrap();
+        assert!(missing.query("SELECT name FROM users", &[]).is_err());
+
+        drop(client);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,341 @@
+use std::str::FromStr;
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{Address, B256, I256, U256};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+use super::error::Error;
+
+/// A value bound to, or read from, an SQL statement, independently of the database backend.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    /// An arbitrary precision decimal number, such as `-12.50`.
+    Numeric(String),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+    /// A point in time, in seconds since the Unix epoch.
+    Timestamp(i64),
+}
+
+impl SqlValue {
+    /// Converts an ABI value to bind it as a parameter.
+    ///
+    /// Integers which don't fit in 64 bits are bound as NUMERIC, and addresses as their checksummed
+    /// hex TEXT.
+    pub fn from_sol(value: &DynSolValue) -> Result<Self, Error> {
+        Ok(match value {
+            DynSolValue::Bool(b) => Self::Bool(*b),
+            DynSolValue::Uint(v, _) => i64::try_from(*v)
+                .map(Self::Integer)
+                .unwrap_or_else(|_| Self::Numeric(v.to_string())),
+            DynSolValue::Int(v, _) => i64::try_from(*v)
+                .map(Self::Integer)
+                .unwrap_or_else(|_| Self::Numeric(v.to_string())),
+            DynSolValue::String(s) => Self::Text(s.clone()),
+            DynSolValue::Bytes(b) => Self::Blob(b.clone()),
+            DynSolValue::FixedBytes(word, size) => Self::Blob(word[..*size].to_vec()),
+            DynSolValue::Address(address) => Self::Text(address.to_checksum(None)),
+            value => {
+                let ty = value.sol_type_name().unwrap_or_default();
+                return Err(Error::Conversion(format!("unsupported parameter type {ty}")))
+            }
+        })
+    }
+
+    /// Converts the value of a column to the ABI type `ty`. NULL is converted to the zero value.
+    pub fn to_sol(&self, ty: &DynSolType, column: &str) -> Result<DynSolValue, Error> {
+        let err = || {
+            Error::Conversion(format!(
+                "cannot convert {self:?} of column {column} to {}",
+                ty.sol_type_name()
+            ))
+        };
+        let value = match ty {
+            DynSolType::Uint(bits) => {
+                let v = match self {
+                    Self::Null => U256::ZERO,
+                    Self::Bool(b) => U256::from(*b as u8),
+                    Self::Integer(v) | Self::Timestamp(v) => {
+                        u64::try_from(*v).map(U256::from).map_err(|_| err())?
+                    }
+                    Self::Numeric(s) => {
+                        integral(s).and_then(|s| U256::from_str(s).ok()).ok_or_else(err)?
+                    }
+                    Self::Text(s) => U256::from_str(s.trim())
+                        .ok()
+                        .or_else(|| {
+                            parse_timestamp(s).and_then(|t| u64::try_from(t).ok()).map(U256::from)
+                        })
+                        .ok_or_else(err)?,
+                    Self::Real(v) if v.fract() == 0.0 && (0.0..u64::MAX as f64).contains(v) => {
+                        U256::from(*v as u64)
+                    }
+                    Self::Blob(b) if b.len() <= 32 => U256::from_be_slice(b),
+                    _ => return Err(err()),
+                };
+                if v.bit_len() > *bits {
+                    return Err(err());
+                }
+                DynSolValue::Uint(v, *bits)
+            }
+            DynSolType::Int(bits) => {
+                let v = match self {
+                    Self::Null => I256::ZERO,
+                    Self::Bool(b) => I256::from_raw(U256::from(*b as u8)),
+                    Self::Integer(v) | Self::Timestamp(v) => {
+                        I256::try_from(*v).map_err(|_| err())?
+                    }
+                    Self::Numeric(s) => {
+                        integral(s).and_then(|s| I256::from_dec_str(s).ok()).ok_or_else(err)?
+                    }
+                    Self::Text(s) => I256::from_dec_str(s.trim())
+                        .ok()
+                        .or_else(|| parse_timestamp(s).and_then(|t| I256::try_from(t).ok()))
+                        .ok_or_else(err)?,
+                    Self::Real(v)
+                        if v.fract() == 0.0 && (i64::MIN as f64..i64::MAX as f64).contains(v) =>
+                    {
+                        I256::try_from(*v as i64).map_err(|_| err())?
+                    }
+                    _ => return Err(err()),
+                };
+                if v.bits() > *bits as u32 {
+                    return Err(err());
+                }
+                DynSolValue::Int(v, *bits)
+            }
+            DynSolType::Bool => DynSolValue::Bool(match self {
+                Self::Null => false,
+                Self::Bool(b) => *b,
+                Self::Integer(v) => *v != 0,
+                Self::Text(s) | Self::Numeric(s) => match s.trim().to_lowercase().as_str() {
+                    "true" | "t" | "1" => true,
+                    "false" | "f" | "0" => false,
+                    _ => return Err(err()),
+                },
+                _ => return Err(err()),
+            }),
+            DynSolType::String => DynSolValue::String(match self {
+                Self::Null => String::new(),
+                Self::Bool(b) => b.to_string(),
+                Self::Integer(v) => v.to_string(),
+                Self::Numeric(s) | Self::Text(s) => s.clone(),
+                Self::Real(v) => v.to_string(),
+                Self::Blob(b) => String::from_utf8(b.clone()).map_err(|_| err())?,
+                Self::Timestamp(t) => DateTime::from_timestamp(*t, 0).ok_or_else(err)?.to_rfc3339(),
+            }),
+            DynSolType::Bytes => DynSolValue::Bytes(match self {
+                Self::Null => Vec::new(),
+                Self::Blob(b) => b.clone(),
+                Self::Text(s) => s.as_bytes().to_vec(),
+                _ => return Err(err()),
+            }),
+            DynSolType::FixedBytes(size) => match self {
+                Self::Null => DynSolValue::FixedBytes(B256::ZERO, *size),
+                Self::Blob(b) if b.len() == *size => {
+                    DynSolValue::FixedBytes(B256::right_padding_from(b), *size)
+                }
+                _ => return Err(err()),
+            },
+            DynSolType::Address => DynSolValue::Address(match self {
+                Self::Null => Address::ZERO,
+                Self::Blob(b) if b.len() == 20 => Address::from_slice(b),
+                Self::Text(s) => Address::from_str(s.trim()).map_err(|_| err())?,
+                _ => return Err(err()),
+            }),
+            _ => {
+                return Err(Error::Conversion(format!(
+                    "unsupported type {} of column {column}",
+                    ty.sol_type_name()
+                )))
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// Returns the integer part of a decimal number, if it has no fractional part.
+fn integral(s: &str) -> Option<&str> {
+    match s.split_once('.') {
+        Some((int, frac)) if frac.bytes().all(|b| b == b'0') => Some(int),
+        Some(_) => None,
+        None => Some(s),
+    }
+}
+
+/// Parses an RFC 3339 or SQL timestamp, or an SQL date, into seconds since the Unix epoch.
+fn parse_timestamp(s: &str) -> Option<i64> {
+    let s = s.trim();
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Some(t.timestamp());
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(t) = NaiveDateTime::parse_from_str(s, format) {
+            return Some(t.and_utc().timestamp());
+        }
+    }
+    let date = NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp())
+}
+
+/// Decodes the ABI-encoded `params` of the tuple type `types`, such as `(uint256,string)`.
+///
+/// `null` members of `types` are bound as NULL, and have no value in `params`.
+pub fn decode_params(types: &str, params: &[u8]) -> Result<Vec<SqlValue>, Error> {
+    let inner = types
+        .trim()
+        .strip_prefix('(')
+        .and_then(|types| types.strip_suffix(')'))
+        .ok_or_else(|| Error::Conversion(format!("parameter types {types} are not a tuple")))?;
+
+    // Split the members at the top level commas.
+    let mut members = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                members.push(inner[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if !inner.trim().is_empty() {
+        members.push(inner[start..].trim());
+    }
+
+    let sol_types = members
+        .iter()
+        .filter(|member| **member != "null")
+        .map(|member| DynSolType::parse(member))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| Error::Conversion(err.to_string()))?;
+    let values = match DynSolType::Tuple(sol_types).abi_decode_params(params) {
+        Ok(DynSolValue::Tuple(values)) => values,
+        Ok(_) => unreachable!("decoded a tuple type"),
+        Err(err) => return Err(Error::Conversion(err.to_string())),
+    };
+
+    let mut values = values.iter();
+    members
+        .iter()
+        .map(|member| match *member {
+            "null" => Ok(SqlValue::Null),
+            _ => SqlValue::from_sol(values.next().expect("decoded all members")),
+        })
+        .collect()
+}
+
+/// A row returned by a query.
+///
+/// Columns whose type the backend can't convert are kept with the reason, so that they only fail
+/// the query when they're selected.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Row {
+    columns: Vec<(String, Result<SqlValue, String>)>,
+}
+
+impl Row {
+    pub fn new(columns: Vec<(String, Result<SqlValue, String>)>) -> Self {
+        Self { columns }
+    }
+
+    /// Returns the value of the column with the given name.
+    pub fn get(&self, column: &str) -> Result<&SqlValue, Error> {
+        match self.columns.iter().find(|(name, _)| name == column) {
+            Some((_, Ok(value))) => Ok(value),
+            Some((_, Err(err))) => Err(Error::Conversion(err.clone())),
+            None => Err(Error::NotFound(format!("Column {column}"))),
+        }
+    }
+
+    /// Returns the content of a BLOB or TEXT column.
+    pub fn get_bytes(&self, column: &str) -> Result<Vec<u8>, Error> {
+        match self.get(column)? {
+            SqlValue::Blob(bytes) => Ok(bytes.clone()),
+            SqlValue::Text(text) => Ok(text.as_bytes().to_vec()),
+            value => Err(Error::Conversion(format!("column {column} is not bytes: {value:?}"))),
+        }
+    }
+
+    /// ABI-encodes the columns, in the order of the query, as the tuple type `row_type`.
+    pub fn abi_encode(&self, row_type: &DynSolType) -> Result<Vec<u8>, Error> {
+        let Some(types) = row_type.as_tuple().filter(|types| types.len() == self.columns.len())
+        else {
+            return Err(Error::Conversion(format!(
+                "row type {} doesn't match the {} columns of the row",
+                row_type.sol_type_name(),
+                self.columns.len()
+            )));
+        };
+        let values = self
+            .columns
+            .iter()
+            .zip(types)
+            .map(|((name, value), ty)| match value {
+                Ok(value) => value.to_sol(ty, name),
+                Err(err) => Err(Error::Conversion(err.clone())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DynSolValue::Tuple(values).abi_encode_params())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn decodes_params() {
+        let types = "(uint256, null, string, int8, address)";
+        let encoded = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::MAX, 256),
+            DynSolValue::String("foundry".to_string()),
+            DynSolValue::Int(I256::MINUS_ONE, 8),
+            DynSolValue::Address(address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266")),
+        ])
+        .abi_encode_params();
+        assert_eq!(
+            decode_params(types, &encoded).unwrap(),
+            [
+                SqlValue::Numeric(U256::MAX.to_string()),
+                SqlValue::Null,
+                SqlValue::Text("foundry".to_string()),
+                SqlValue::Integer(-1),
+                SqlValue::Text("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string()),
+            ]
+        );
+        assert_eq!(decode_params("()", &[]).unwrap(), []);
+        assert!(decode_params("uint256", &encoded).is_err());
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let row = Row::new(vec![
+            ("amount".to_string(), Ok(SqlValue::Numeric("1000000000000000000000.00".to_string()))),
+            ("deleted".to_string(), Ok(SqlValue::Integer(0))),
+            ("created".to_string(), Ok(SqlValue::Text("2024-01-01 00:00:00".to_string()))),
+            ("note".to_string(), Ok(SqlValue::Null)),
+        ]);
+        let ty = DynSolType::parse("(uint256,bool,uint64,string)").unwrap();
+        let expected = DynSolValue::Tuple(vec![
+            DynSolValue::Uint(U256::from(10).pow(U256::from(21)), 256),
+            DynSolValue::Bool(false),
+            DynSolValue::Uint(U256::from(1704067200), 64),
+            DynSolValue::String(String::new()),
+        ]);
+        assert_eq!(row.abi_encode(&ty).unwrap(), expected.abi_encode_params());
+
+        let ty = DynSolType::parse("(uint8,bool,uint64,string)").unwrap();
+        assert!(matches!(row.abi_encode(&ty), Err(Error::Conversion(_))));
+        let ty = DynSolType::parse("(uint256,bool)").unwrap();
+        assert!(matches!(row.abi_encode(&ty), Err(Error::Conversion(_))));
+    }
+}
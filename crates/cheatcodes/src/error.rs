@@ -1,4 +1,4 @@
-use crate::Vm;
+use crate::{database::Error as SqlError, Vm};
 use alloy_primitives::{hex, Address, Bytes};
 use alloy_signer::Error as SignerError;
 use alloy_signer_local::LocalSignerError;
@@ -283,10 +283,12 @@ fn from(value: $t) -> Self {
     alloy_sol_types::Error,
     alloy_dyn_abi::Error,
     alloy_primitives::SignatureError,
+    csv::Error,
     FsPathError,
     hex::FromHexError,
     BackendError,
     DatabaseError,
+    SqlError,
     jsonpath_lib::JsonPathError,
     serde_json::Error,
     SignatureError,
@@ -28,9 +28,11 @@
 use record_debug_step::{convert_call_trace_to_debug_step, flatten_call_trace};
 use serde::Serialize;
 
+pub(crate) mod block;
 mod fork;
 pub(crate) mod mapping;
 pub(crate) mod mock;
+pub(crate) mod opcodes;
 pub(crate) mod prank;
 
 /// Records storage slots reads and writes.
@@ -0,0 +1,318 @@
+use crate::{Cheatcode, CheatcodesExecutor, CheatsCtxt, Result, Vm::*};
+use alloy_primitives::{keccak256, Address, Bytes, TxKind, B256, U256};
+use alloy_rpc_types::TransactionRequest;
+use alloy_sol_types::SolValue;
+use foundry_evm_core::backend::RevertStateSnapshotAction;
+use revm::primitives::ExecutionResult;
+
+/// The number of seconds between simulated blocks, as between the slots of Ethereum mainnet.
+const BLOCK_TIME: u64 = 12;
+
+/// A block simulated with `startBlock`.
+#[derive(Clone, Debug)]
+pub struct SimulatedBlock {
+    /// The state snapshot taken before the block, which `reorg` reverts to.
+    pub snapshot_id: U256,
+    /// The number of the block.
+    pub number: U256,
+    /// The timestamp of the block.
+    pub timestamp: U256,
+    /// The number of transactions applied to the block.
+    pub transactions: u64,
+    /// The gas used by the transactions of the block.
+    pub gas_used: u64,
+    /// The simulated hash of the block, once it has ended.
+    pub hash: Option<B256>,
+}
+
+/// The blocks simulated with `startBlock`, which can still be reorged away.
+///
+/// Only the last block can be open, i.e. not ended with `endBlock` yet.
+#[derive(Clone, Debug, Default)]
+pub struct SimulatedBlocks {
+    blocks: Vec<SimulatedBlock>,
+}
+
+impl SimulatedBlocks {
+    /// Returns the open block, if any.
+    pub fn open(&self) -> Option<&SimulatedBlock> {
+        self.blocks.last().filter(|block| block.hash.is_none())
+    }
+
+    /// Opens the block following the block with the given number and timestamp, with the state
+    /// snapshot taken by `snapshot`.
+    ///
+    /// After a simulated block has ended, the given block is the one `endBlock` moved to, which is
+    /// opened as is so that simulated blocks have consecutive numbers.
+    pub fn start(
+        &mut self,
+        number: U256,
+        timestamp: U256,
+        snapshot: impl FnOnce() -> U256,
+    ) -> Result<&SimulatedBlock> {
+        if self.open().is_some() {
+            bail!("a simulated block is already open; call `endBlock` first");
+        }
+        let number = if self.blocks.is_empty() { number + U256::from(1) } else { number };
+        self.blocks.push(SimulatedBlock {
+            snapshot_id: snapshot(),
+            number,
+            timestamp: timestamp + U256::from(BLOCK_TIME),
+            transactions: 0,
+            gas_used: 0,
+            hash: None,
+        });
+        Ok(self.blocks.last().unwrap())
+    }
+
+    /// Adds a transaction which used `gas_used` to the open block.
+    pub fn add_transaction(&mut self, gas_used: u64) -> Result<&SimulatedBlock> {
+        let block = self.open_mut()?;
+        block.transactions += 1;
+        block.gas_used += gas_used;
+        Ok(block)
+    }
+
+    /// Ends the open block, and sets its simulated hash.
+    pub fn end(&mut self) -> Result<&SimulatedBlock> {
+        let block = self.open_mut()?;
+        // The snapshot id makes the hashes of blocks simulated again after a reorg distinct.
+        block.hash = Some(keccak256(
+            (block.number, block.snapshot_id, block.transactions, block.gas_used).abi_encode(),
+        ));
+        Ok(block)
+    }
+
+    /// Removes the last `depth` blocks, including the open block, if any.
+    pub fn reorg(&mut self, depth: usize) -> Result<Vec<SimulatedBlock>> {
+        let blocks = self.blocks.len();
+        ensure!(
+            depth > 0 && depth <= blocks,
+            "cannot reorg {depth} blocks; there are {blocks} simulated blocks"
+        );
+        Ok(self.blocks.split_off(blocks - depth))
+    }
+
+    fn open_mut(&mut self) -> Result<&mut SimulatedBlock> {
+        match self.blocks.last_mut() {
+            Some(block) if block.hash.is_none() => Ok(block),
+            _ => bail!("no open simulated block; call `startBlock` first"),
+        }
+    }
+}
+
+impl Cheatcode for startBlockCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self {} = self;
+        let env = &mut ccx.ecx.env;
+        let block =
+            ccx.state.simulated_blocks.start(env.block.number, env.block.timestamp, || {
+                ccx.ecx.db.snapshot_state(&ccx.ecx.journaled_state, env)
+            })?;
+        env.block.number = block.number;
+        env.block.timestamp = block.timestamp;
+        Ok(block.number.abi_encode())
+    }
+}
+
+impl Cheatcode for blockTransactCall {
+    fn apply_full(&self, ccx: &mut CheatsCtxt, executor: &mut dyn CheatcodesExecutor) -> Result {
+        let Self { from, to, value, data } = self;
+        let Some(block) = ccx.state.simulated_blocks.open() else {
+            bail!("no open simulated block; call `startBlock` first")
+        };
+        let (index, block_gas_used) = (block.transactions, block.gas_used);
+
+        let gas_limit =
+            ccx.ecx.env.block.gas_limit.saturating_to::<u64>().saturating_sub(block_gas_used);
+        ensure!(gas_limit > 0, "no gas left in the simulated block");
+
+        let tx = TransactionRequest {
+            // Without a recipient, the transaction creates a contract.
+            to: (!to.is_zero()).then_some(TxKind::Call(*to)),
+            gas_price: Some(ccx.ecx.env.block.basefee.saturating_to()),
+            ..Default::default()
+        }
+        .from(*from)
+        .value(*value)
+        .input(data.clone().into())
+        .gas_limit(gas_limit);
+
+        let result = ccx.ecx.db.transact_from_tx(
+            &tx,
+            (*ccx.ecx.env).clone(),
+            &mut ccx.ecx.journaled_state,
+            &mut *executor.get_inspector(ccx.state),
+        )?;
+
+        let gas_used = result.gas_used();
+        let (success, contract_address, output, logs) = match result {
+            ExecutionResult::Success { output, logs, .. } => {
+                let contract_address = output.address().copied().unwrap_or_default();
+                (true, contract_address, output.into_data(), logs)
+            }
+            ExecutionResult::Revert { output, .. } => (false, Address::ZERO, output, vec![]),
+            ExecutionResult::Halt { .. } => (false, Address::ZERO, Bytes::new(), vec![]),
+        };
+
+        let block = ccx.state.simulated_blocks.add_transaction(gas_used)?;
+
+        let receipt = BlockReceipt {
+            transactionIndex: index,
+            success,
+            gasUsed: gas_used,
+            cumulativeGasUsed: block.gas_used,
+            contractAddress: contract_address,
+            output,
+            logs: logs
+                .into_iter()
+                .map(|log| Log {
+                    topics: log.data.topics().to_vec(),
+                    data: log.data.data,
+                    emitter: log.address,
+                })
+                .collect(),
+        };
+        Ok(receipt.abi_encode())
+    }
+}
+
+impl Cheatcode for endBlockCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self {} = self;
+        let block = ccx.state.simulated_blocks.end()?;
+        let hash = block.hash.unwrap_or_default();
+        ccx.ecx.db.set_blockhash(block.number, hash);
+        ccx.ecx.env.block.number = block.number + U256::from(1);
+        Ok(hash.abi_encode())
+    }
+}
+
+impl Cheatcode for reorgCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { depth } = self;
+        let reorged = ccx.state.simulated_blocks.reorg(depth.saturating_to())?;
+        // Reverting removes the snapshot of the first reorged block, but not those taken after it.
+        let Some(journaled_state) = ccx.ecx.db.revert_state(
+            reorged[0].snapshot_id,
+            &ccx.ecx.journaled_state,
+            &mut ccx.ecx.env,
+            RevertStateSnapshotAction::RevertRemove,
+        ) else {
+            bail!("the state snapshot of simulated block {} no longer exists", reorged[0].number)
+        };
+        ccx.ecx.journaled_state = journaled_state;
+
+        for block in &reorged[1..] {
+            ccx.ecx.db.delete_state_snapshot(block.snapshot_id);
+        }
+        for block in &reorged {
+            ccx.ecx.db.set_blockhash(block.number, B256::ZERO);
+        }
+        Ok(reorged
+            .iter()
+            .map(|block| block.hash.unwrap_or_default())
+            .collect::<Vec<_>>()
+            .abi_encode())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(blocks: &mut SimulatedBlocks, snapshot_id: u64) -> Result<SimulatedBlock> {
+        let (number, timestamp) =
+            blocks.blocks.last().map_or((U256::from(1), U256::from(1)), |block| {
+                (block.number + U256::from(1), block.timestamp)
+            });
+        blocks.start(number, timestamp, || U256::from(snapshot_id)).cloned()
+    }
+
+    #[test]
+    fn start_moves_to_next_block() {
+        let mut blocks = SimulatedBlocks::default();
+        let block = blocks.start(U256::from(10), U256::from(100), || U256::from(7)).unwrap();
+        assert_eq!(block.number, U256::from(11));
+        assert_eq!(block.timestamp, U256::from(100 + BLOCK_TIME));
+        assert_eq!(block.snapshot_id, U256::from(7));
+        assert!(block.hash.is_none());
+    }
+
+    #[test]
+    fn ended_blocks_are_consecutive() {
+        let mut blocks = SimulatedBlocks::default();
+        let first = blocks.start(U256::from(10), U256::from(100), || U256::ZERO).unwrap().number;
+        let ended = blocks.end().unwrap().number;
+        assert_eq!(first, U256::from(11));
+        assert_eq!(ended, first);
+
+        // `endBlock` moves to the block after the ended one.
+        let second =
+            blocks.start(first + U256::from(1), U256::from(112), || U256::from(1)).unwrap();
+        assert_eq!(second.number, first + U256::from(1));
+        assert_eq!(second.timestamp, U256::from(112 + BLOCK_TIME));
+    }
+
+    #[test]
+    fn only_one_block_is_open() {
+        let mut blocks = SimulatedBlocks::default();
+        start(&mut blocks, 0).unwrap();
+        let err = start(&mut blocks, 1).unwrap_err();
+        assert_eq!(err.to_string(), "a simulated block is already open; call `endBlock` first");
+
+        blocks.end().unwrap();
+        assert!(blocks.open().is_none());
+        let err = blocks.end().unwrap_err();
+        assert_eq!(err.to_string(), "no open simulated block; call `startBlock` first");
+        let err = blocks.add_transaction(21_000).unwrap_err();
+        assert_eq!(err.to_string(), "no open simulated block; call `startBlock` first");
+    }
+
+    #[test]
+    fn transactions_accumulate_gas() {
+        let mut blocks = SimulatedBlocks::default();
+        start(&mut blocks, 0).unwrap();
+        blocks.add_transaction(21_000).unwrap();
+        let block = blocks.add_transaction(50_000).unwrap();
+        assert_eq!(block.transactions, 2);
+        assert_eq!(block.gas_used, 71_000);
+    }
+
+    #[test]
+    fn hash_depends_on_snapshot() {
+        let mut blocks = SimulatedBlocks::default();
+        start(&mut blocks, 0).unwrap();
+        let first = blocks.end().unwrap().hash.unwrap();
+
+        blocks.reorg(1).unwrap();
+        start(&mut blocks, 1).unwrap();
+        let second = blocks.end().unwrap().hash.unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn reorg_removes_last_blocks() {
+        let mut blocks = SimulatedBlocks::default();
+        for snapshot_id in 0..3 {
+            start(&mut blocks, snapshot_id).unwrap();
+            blocks.end().unwrap();
+        }
+        start(&mut blocks, 3).unwrap();
+
+        let reorged = blocks.reorg(2).unwrap();
+        assert_eq!(
+            reorged.iter().map(|block| block.snapshot_id).collect::<Vec<_>>(),
+            [U256::from(2), U256::from(3)]
+        );
+        assert!(reorged[0].hash.is_some());
+        assert!(reorged[1].hash.is_none());
+        assert_eq!(blocks.blocks.len(), 2);
+        assert!(blocks.open().is_none());
+
+        let err = blocks.reorg(3).unwrap_err();
+        assert_eq!(err.to_string(), "cannot reorg 3 blocks; there are 2 simulated blocks");
+        let err = blocks.reorg(0).unwrap_err();
+        assert_eq!(err.to_string(), "cannot reorg 0 blocks; there are 2 simulated blocks");
+    }
+}
@@ -2,13 +2,15 @@
     json::json_value_to_token, Cheatcode, Cheatcodes, CheatcodesExecutor, CheatsCtxt, DatabaseExt,
     Result, Vm::*,
 };
-use alloy_dyn_abi::DynSolValue;
-use alloy_primitives::{B256, U256};
+use alloy_dyn_abi::{DynSolValue, EventExt};
+use alloy_primitives::{keccak256, B256, U256};
 use alloy_provider::Provider;
-use alloy_rpc_types::{eth, Filter};
+use alloy_rpc_types::{Filter, Log};
 use alloy_sol_types::SolValue;
-use foundry_common::provider::ProviderBuilder;
-use foundry_evm_core::fork::CreateFork;
+use alloy_transport::TransportError;
+use foundry_common::{abi::get_event, fs, provider::ProviderBuilder};
+use foundry_config::Config;
+use foundry_evm_core::fork::{CreateFork, ForkId};
 
 impl Cheatcode for activeForkCall {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
@@ -231,44 +233,78 @@ fn apply(&self, state: &mut Cheatcodes) -> Result {
     }
 }
 
-impl Cheatcode for eth_getLogsCall {
+impl Cheatcode for eth_getLogs_0Call {
     fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
         let Self { fromBlock, toBlock, target, topics } = self;
-        let (Ok(from_block), Ok(to_block)) = (u64::try_from(fromBlock), u64::try_from(toBlock))
-        else {
-            bail!("blocks in block range must be less than 2^64 - 1")
-        };
-
         if topics.len() > 4 {
             bail!("topics array must contain at most 4 elements");
         }
 
-        let url =
-            ccx.ecx.db.active_fork_url().ok_or_else(|| fmt_err!("no active fork URL found"))?;
-        let provider = ProviderBuilder::new(&url).build()?;
-        let mut filter = Filter::new().address(*target).from_block(from_block).to_block(to_block);
+        let mut filter = Filter::new().address(*target);
         for (i, &topic) in topics.iter().enumerate() {
             filter.topics[i] = topic.into();
         }
+        let logs = get_logs(ccx, fromBlock, toBlock, filter)?;
+        Ok(logs.into_iter().map(eth_log).collect::<Vec<_>>().abi_encode())
+    }
+}
 
-        let logs = foundry_common::block_on(provider.get_logs(&filter))
-            .map_err(|e| fmt_err!("failed to get logs: {e}"))?;
+impl Cheatcode for eth_getLogs_1Call {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { fromBlock, toBlock, targets, topics } = self;
+        if topics.len() > 4 {
+            bail!("topics array must contain at most 4 elements");
+        }
 
-        let eth_logs = logs
+        let mut filter = Filter::new().address(targets.clone());
+        for (i, topic) in topics.iter().enumerate() {
+            filter.topics[i] = topic.clone().into();
+        }
+        let logs = get_logs(ccx, fromBlock, toBlock, filter)?;
+        Ok(logs.into_iter().map(eth_log).collect::<Vec<_>>().abi_encode())
+    }
+}
+
+impl Cheatcode for eth_getLogs_2Call {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self { fromBlock, toBlock, targets, eventSignature } = self;
+        let event = get_event(eventSignature)?;
+
+        let mut filter = Filter::new().address(targets.clone());
+        if !event.anonymous {
+            filter = filter.event_signature(event.selector());
+        }
+        let logs = get_logs(ccx, fromBlock, toBlock, filter)?;
+
+        let decoded_logs = logs
             .into_iter()
-            .map(|log| EthGetLogs {
-                emitter: log.address(),
-                topics: log.topics().to_vec(),
-                data: log.inner.data.data,
-                blockHash: log.block_hash.unwrap_or_default(),
-                blockNumber: log.block_number.unwrap_or_default(),
-                transactionHash: log.transaction_hash.unwrap_or_default(),
-                transactionIndex: log.transaction_index.unwrap_or_default(),
-                logIndex: U256::from(log.log_index.unwrap_or_default()),
-                removed: log.removed,
+            .map(|log| {
+                let decoded = event
+                    .decode_log_parts(log.topics().iter().copied(), &log.inner.data.data, false)
+                    .map_err(|e| fmt_err!("failed to decode log: {e}"))?;
+                // Interleave the indexed and non-indexed parameters in the order of the signature.
+                let (mut indexed, mut body) =
+                    (decoded.indexed.into_iter(), decoded.body.into_iter());
+                let args = event
+                    .inputs
+                    .iter()
+                    .map(|input| if input.indexed { indexed.next() } else { body.next() })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| fmt_err!("failed to decode log: missing parameters"))?;
+                let log = eth_log(log);
+                Ok(EthDecodedLog {
+                    emitter: log.emitter,
+                    args: DynSolValue::Tuple(args).abi_encode_params().into(),
+                    blockHash: log.blockHash,
+                    blockNumber: log.blockNumber,
+                    transactionHash: log.transactionHash,
+                    transactionIndex: log.transactionIndex,
+                    logIndex: log.logIndex,
+                    removed: log.removed,
+                })
             })
-            .collect::<Vec<_>>();
-        Ok(eth_logs.abi_encode())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(decoded_logs.abi_encode())
     }
 }
 
@@ -376,6 +412,111 @@ fn persist_caller(ccx: &mut CheatsCtxt) {
     ccx.ecx.db.add_persistent_account(ccx.caller);
 }
 
+/// Gets the logs matching `filter` in the block range from the active fork.
+///
+/// Ranges rejected by the provider because they span too many blocks or logs are split in halves
+/// until they're accepted; any other error is returned. If storage caching is enabled for the fork,
+/// the logs of ranges up to the block the fork is pinned to are cached in the RPC cache of its
+/// chain.
+fn get_logs(
+    ccx: &mut CheatsCtxt,
+    from_block: &U256,
+    to_block: &U256,
+    filter: Filter,
+) -> Result<Vec<Log>> {
+    let (Ok(from_block), Ok(to_block)) = (u64::try_from(from_block), u64::try_from(to_block))
+    else {
+        bail!("blocks in block range must be less than 2^64 - 1")
+    };
+
+    let url = ccx.ecx.db.active_fork_url().ok_or_else(|| fmt_err!("no active fork URL found"))?;
+    let provider = ProviderBuilder::new(&url).build()?;
+
+    let chain_id = ccx.ecx.env.cfg.chain_id;
+    // The block of the fork, unlike the block of the environment, isn't changed by `roll`.
+    let fork_block = ccx
+        .ecx
+        .db
+        .active_fork_id()
+        .and_then(|id| ccx.ecx.db.ensure_fork_id(id).ok())
+        .and_then(ForkId::block_number);
+    let cache_dir = (!ccx.state.config.no_storage_caching &&
+        ccx.state.config.rpc_storage_caching.enable_for_endpoint(&url) &&
+        ccx.state.config.rpc_storage_caching.enable_for_chain_id(chain_id))
+    .then(|| Config::foundry_chain_cache_dir(chain_id))
+    .flatten()
+    .map(|dir| dir.join("logs"));
+
+    let mut logs = Vec::new();
+    let mut ranges = vec![(from_block, to_block)];
+    while let Some((from, to)) = ranges.pop() {
+        let filter = filter.clone().from_block(from).to_block(to);
+        let cache_path = match &cache_dir {
+            Some(dir) if fork_block.is_some_and(|block| to <= block) => {
+                Some(dir.join(format!("{}.json", keccak256(serde_json::to_vec(&filter)?))))
+            }
+            _ => None,
+        };
+        if let Some(cached) =
+            cache_path.as_deref().and_then(|path| fs::read_json_file::<Vec<Log>>(path).ok())
+        {
+            logs.extend(cached);
+            continue;
+        }
+
+        match foundry_common::block_on(provider.get_logs(&filter)) {
+            Ok(range_logs) => {
+                if let Some(path) = cache_path {
+                    let cached = fs::create_dir_all(path.parent().unwrap())
+                        .and_then(|()| fs::write_json_file(&path, &range_logs));
+                    if let Err(err) = cached {
+                        debug!(%err, "failed to cache logs");
+                    }
+                }
+                logs.extend(range_logs);
+            }
+            Err(err) if from < to && is_log_range_error(&err) => {
+                debug!(%err, from, to, "splitting block range of logs");
+                let mid = from + (to - from) / 2;
+                // The first half is fetched first, to keep the logs in order.
+                ranges.push((mid + 1, to));
+                ranges.push((from, mid));
+            }
+            Err(err) => bail!("failed to get logs: {err}"),
+        }
+    }
+    Ok(logs)
+}
+
+/// Returns whether the provider rejected an `eth_getLogs` request because its block range or its
+/// number of results exceeded a limit, in which case the request is retried with a smaller range.
+fn is_log_range_error(err: &TransportError) -> bool {
+    let Some(payload) = err.as_error_resp() else { return false };
+    let message = payload.message.to_lowercase();
+    // Rate limits may also mention exceeded limits, but a smaller range doesn't help.
+    let rate_limited = payload.code == 429 ||
+        ["rate limit", "too many requests", "capacity"]
+            .iter()
+            .any(|pattern| message.contains(pattern));
+    !rate_limited &&
+        ["range", "results", "response size"].iter().any(|pattern| message.contains(pattern))
+}
+
+/// Converts an RPC log to the `EthGetLogs` struct.
+fn eth_log(log: Log) -> EthGetLogs {
+    EthGetLogs {
+        emitter: log.address(),
+        topics: log.topics().to_vec(),
+        data: log.inner.data.data,
+        blockHash: log.block_hash.unwrap_or_default(),
+        blockNumber: log.block_number.unwrap_or_default(),
+        transactionHash: log.transaction_hash.unwrap_or_default(),
+        transactionIndex: log.transaction_index.unwrap_or_default(),
+        logIndex: U256::from(log.log_index.unwrap_or_default()),
+        removed: log.removed,
+    }
+}
+
 /// Performs an Ethereum JSON-RPC request to the given endpoint.
 fn rpc_call(url: &str, method: &str, params: &str) -> Result {
     let provider = ProviderBuilder::new(url).build()?;
@@ -405,3 +546,36 @@ fn convert_to_bytes(token: &DynSolValue) -> DynSolValue {
         val => val.clone(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::ErrorPayload;
+    use alloy_transport::TransportErrorKind;
+
+    fn error_resp(code: i64, message: &'static str) -> TransportError {
+        TransportError::ErrorResp(ErrorPayload { code, message: message.into(), data: None })
+    }
+
+    #[test]
+    fn log_range_errors() {
+        for message in [
+            "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range and no limit on the response size, or you can request any block range with a cap of 10K logs in the response.",
+            "query returned more than 10000 results",
+            "exceed maximum block range: 5000",
+            "eth_getLogs is limited to a 10,000 range",
+        ] {
+            assert!(is_log_range_error(&error_resp(-32005, message)), "{message}");
+        }
+
+        for err in [
+            error_resp(429, "Your app has exceeded its compute units per second capacity"),
+            error_resp(-32005, "daily request count exceeded, request rate limited"),
+            error_resp(-32005, "too many requests, results are limited"),
+            error_resp(-32000, "header not found"),
+            TransportErrorKind::backend_gone(),
+        ] {
+            assert!(!is_log_range_error(&err), "{err}");
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{Cheatcode, Cheatcodes, CheatsCtxt, Result, Vm::*};
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::SolValue;
+use foundry_evm_core::constants::{CHEATCODE_ADDRESS, HARDHAT_CONSOLE_ADDRESS};
+use revm::{
+    interpreter::{opcode as op, Interpreter, OpCode},
+    primitives::AccountStatus,
+    JournaledState,
+};
+use std::collections::BTreeMap;
+
+/// Opcodes recorded with `startOpcodeRecording`.
+#[derive(Clone, Debug, Default)]
+pub struct OpcodeRecorder {
+    /// The depth of the call frame that started the recording. Only opcodes of deeper call frames
+    /// are recorded.
+    pub depth: u64,
+    /// The number of times each opcode was executed.
+    pub counts: BTreeMap<u8, u64>,
+    /// The number of calls, excluding calls to the cheatcode and console addresses.
+    pub external_calls: u64,
+    /// The number of accesses to cold accounts and storage slots.
+    pub cold_accesses: u64,
+    /// The number of accesses to warm accounts and storage slots.
+    pub warm_accesses: u64,
+    /// The slots written by `SSTORE`s, in execution order.
+    pub storage_writes: Vec<(Address, B256)>,
+}
+
+impl OpcodeRecorder {
+    /// Records the opcode about to be executed by the interpreter.
+    pub fn step(&mut self, interpreter: &Interpreter, journaled_state: &JournaledState) {
+        if journaled_state.depth() <= self.depth {
+            return;
+        }
+
+        let opcode = interpreter.current_opcode();
+        *self.counts.entry(opcode).or_default() += 1;
+
+        let target = interpreter.contract().target_address;
+        let warm = match opcode {
+            op::SLOAD | op::SSTORE => {
+                let Ok(key) = interpreter.stack().peek(0) else { return };
+                if opcode == op::SSTORE {
+                    self.storage_writes.push((target, key.into()));
+                }
+                is_warm_slot(journaled_state, target, key)
+            }
+            op::BALANCE |
+            op::EXTCODESIZE |
+            op::EXTCODECOPY |
+            op::EXTCODEHASH |
+            op::SELFDESTRUCT => {
+                let Ok(address) = interpreter.stack().peek(0) else { return };
+                is_warm_account(journaled_state, Address::from_word(address.into()))
+            }
+            op::CALL | op::CALLCODE | op::DELEGATECALL | op::STATICCALL => {
+                let Ok(address) = interpreter.stack().peek(1) else { return };
+                let address = Address::from_word(address.into());
+                if address != CHEATCODE_ADDRESS && address != HARDHAT_CONSOLE_ADDRESS {
+                    self.external_calls += 1;
+                }
+                is_warm_account(journaled_state, address)
+            }
+            _ => return,
+        };
+        if warm {
+            self.warm_accesses += 1;
+        } else {
+            self.cold_accesses += 1;
+        }
+    }
+
+    fn into_record(self) -> OpcodeRecord {
+        OpcodeRecord {
+            opcodes: self
+                .counts
+                .into_iter()
+                .map(|(opcode, count)| OpcodeCount { opcode, count })
+                .collect(),
+            externalCalls: self.external_calls,
+            coldAccesses: self.cold_accesses,
+            warmAccesses: self.warm_accesses,
+            storageWrites: self
+                .storage_writes
+                .into_iter()
+                .map(|(account, slot)| StorageSlot { account, slot })
+                .collect(),
+        }
+    }
+}
+
+/// Returns whether the account was already accessed in the transaction.
+fn is_warm_account(journaled_state: &JournaledState, address: Address) -> bool {
+    match journaled_state.state.get(&address) {
+        Some(account) => !account.status.contains(AccountStatus::Cold),
+        None => journaled_state.warm_preloaded_addresses.contains(&address),
+    }
+}
+
+/// Returns whether the storage slot was already accessed in the transaction.
+fn is_warm_slot(journaled_state: &JournaledState, address: Address, key: U256) -> bool {
+    journaled_state
+        .state
+        .get(&address)
+        .and_then(|account| account.storage.get(&key))
+        .is_some_and(|slot| !slot.is_cold)
+}
+
+fn opcode_name(opcode: u8) -> String {
+    OpCode::new(opcode).map_or_else(|| format!("{opcode:#04x}"), |op| op.as_str().to_string())
+}
+
+impl Cheatcode for startOpcodeRecordingCall {
+    fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
+        let Self {} = self;
+        ccx.state.recorded_opcodes =
+            Some(OpcodeRecorder { depth: ccx.ecx.journaled_state.depth(), ..Default::default() });
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for stopAndReturnOpcodesCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self {} = self;
+        let Some(recorder) = state.recorded_opcodes.take() else {
+            bail!("opcode recording was not started; call `startOpcodeRecording` first")
+        };
+        Ok(recorder.into_record().abi_encode())
+    }
+}
+
+impl Cheatcode for assertNoOpcodeCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { record, opcode } = self;
+        if let Some(count) = record.opcodes.iter().find(|c| c.opcode == *opcode).map(|c| c.count) {
+            ensure!(count == 0, "{} was executed {count} times", opcode_name(*opcode));
+        }
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for assertMaxExternalCallsCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { record, maxCalls } = self;
+        let calls = record.externalCalls;
+        ensure!(
+            U256::from(calls) <= *maxCalls,
+            "{calls} external calls were made, more than the maximum of {maxCalls}"
+        );
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for assertNoStorageWriteCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { record, account, slot } = self;
+        let writes = record
+            .storageWrites
+            .iter()
+            .filter(|write| write.account == *account && write.slot == *slot)
+            .count();
+        ensure!(writes == 0, "slot {slot} of {account} was written {writes} times");
+        Ok(Default::default())
+    }
+}
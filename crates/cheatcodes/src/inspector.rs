@@ -1,10 +1,12 @@
 //! Cheatcode EVM inspector.
 
 use crate::{
-    database::PostgresClient,
+    database::{DatabaseClient, DbTransaction},
     evm::{
+        block::SimulatedBlocks,
         mapping::{self, MappingSlots},
         mock::{MockCallDataContext, MockCallReturnData},
+        opcodes::OpcodeRecorder,
         prank::Prank,
         DealRecord, GasRecord, RecordAccess,
     },
@@ -65,6 +67,9 @@
 
 mod utils;
 
+/// The selector of the `setUp()` function of test contracts.
+const SET_UP_SELECTOR: [u8; 4] = hex!("0a9254e4");
+
 pub type Ecx<'a, 'b, 'c> = &'a mut EvmContext<&'b mut (dyn DatabaseExt + 'c)>;
 pub type InnerEcx<'a, 'b, 'c> = &'a mut InnerEvmContext<&'b mut (dyn DatabaseExt + 'c)>;
 
@@ -457,6 +462,9 @@ pub struct Cheatcodes {
     /// All recorded ETH `deal`s.
     pub eth_deals: Vec<DealRecord>,
 
+    /// Blocks simulated with `startBlock`, which can still be reorged away.
+    pub simulated_blocks: SimulatedBlocks,
+
     /// Gas metering state.
     pub gas_metering: GasMetering,
 
@@ -467,6 +475,9 @@ pub struct Cheatcodes {
     /// Mapping slots.
     pub mapping_slots: Option<AddressHashMap<MappingSlots>>,
 
+    /// Opcodes recorded with `startOpcodeRecording`.
+    pub recorded_opcodes: Option<OpcodeRecorder>,
+
     /// The current program counter.
     pub pc: usize,
     /// Breakpoints supplied by the `breakpoint` cheatcode.
@@ -488,7 +499,17 @@ pub struct Cheatcodes {
     /// Unlocked wallets used in scripts and testing of scripts.
     pub wallets: Option<Wallets>,
 
-    pub db_client: Option<PostgresClient>,
+    /// The database client of the database cheatcodes.
+    pub db_client: Option<Arc<dyn DatabaseClient>>,
+    /// The named connections of the database cheatcodes, by alias.
+    pub db_clients: HashMap<String, Arc<dyn DatabaseClient>>,
+    /// The open transactions of the database cheatcodes. They are rolled back when the call that
+    /// opened them reverts, or when the test or `setUp()` ends.
+    pub db_transactions: Vec<DbTransaction>,
+    /// Whether the connections of the database cheatcodes were replaced with the isolation scopes
+    /// of the test, which happens once, at its first call after `setUp()`. Scripts set it so that
+    /// they don't run in isolation scopes.
+    pub db_scoped: bool,
 }
 
 // This is not derived because calling this in `fn new` with `..Default::default()` creates a second
@@ -528,9 +549,11 @@ pub fn new(config: Arc<CheatsConfig>) -> Self {
             context: Default::default(),
             serialized_jsons: Default::default(),
             eth_deals: Default::default(),
+            simulated_blocks: Default::default(),
             gas_metering: Default::default(),
             gas_snapshots: Default::default(),
             mapping_slots: Default::default(),
+            recorded_opcodes: Default::default(),
             pc: Default::default(),
             breakpoints: Default::default(),
             test_runner: Default::default(),
@@ -539,6 +562,9 @@ pub fn new(config: Arc<CheatsConfig>) -> Self {
             deprecated: Default::default(),
             wallets: Default::default(),
             db_client: Default::default(),
+            db_clients: Default::default(),
+            db_transactions: Default::default(),
+            db_scoped: false,
         }
     }
 
@@ -552,11 +578,49 @@ pub fn set_wallets(&mut self, wallets: Wallets) {
         self.wallets = Some(wallets);
     }
 
-    /// Set the db client.
-    pub fn set_db_client(&mut self, client: PostgresClient) {
+    /// Set the db client of the current database connection.
+    pub fn set_db_client(&mut self, client: Arc<dyn DatabaseClient>) {
         self.db_client = Some(client);
     }
 
+    /// Replaces the isolated connections of the database cheatcodes with the isolation scopes of
+    /// a test.
+    ///
+    /// The scopes are shared by the clones of the cheatcodes of the test (e.g. by the calls of an
+    /// invariant run), and rolled back when the last of them is dropped.
+    fn begin_db_scopes(&mut self) -> Result<()> {
+        self.db_scoped = true;
+        let mut scopes = Vec::<(Arc<dyn DatabaseClient>, Arc<dyn DatabaseClient>)>::new();
+        let clients = self.db_clients.values_mut().chain(&mut self.db_client);
+        for client in clients {
+            if let Some((_, scope)) = scopes.iter().find(|(c, _)| Arc::ptr_eq(c, client)) {
+                *client = scope.clone();
+            } else if let Some(scope) = client.scope()? {
+                scopes.push((std::mem::replace(client, scope.clone()), scope));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rolls back the open transactions of the database cheatcodes.
+    pub fn rollback_db_transactions(&mut self) {
+        self.rollback_db_transactions_above(0);
+    }
+
+    /// Rolls back the open transactions of the database cheatcodes opened above `depth`.
+    fn rollback_db_transactions_above(&mut self, depth: u64) {
+        self.db_transactions.retain(|transaction| {
+            if transaction.depth <= depth {
+                return true;
+            }
+            let id = transaction.id;
+            if let Err(err) = transaction.client.rollback(id) {
+                debug!(target: "cheatcodes", %id, %err, "failed to roll back database transaction");
+            }
+            false
+        });
+    }
+
     /// Decodes the input data and applies the cheatcode.
     fn apply_cheatcode(
         &mut self,
@@ -873,6 +937,21 @@ pub fn call_with_executor(
             account.info.nonce = prev.saturating_sub(1);
 
             trace!(target: "cheatcodes", %sender, nonce=account.info.nonce, prev, "corrected nonce");
+
+            // Tests (but not `setUp()`) run in the isolation scopes of the connections of the
+            // database cheatcodes.
+            if !self.db_scoped && !call.input.starts_with(&SET_UP_SELECTOR) {
+                if let Err(err) = self.begin_db_scopes() {
+                    return Some(CallOutcome {
+                        result: InterpreterResult {
+                            result: InstructionResult::Revert,
+                            output: err.abi_encode().into(),
+                            gas,
+                        },
+                        memory_offset: call.return_memory_offset.clone(),
+                    });
+                }
+            }
         }
 
         if call.target_address == CHEATCODE_ADDRESS {
@@ -1218,6 +1297,11 @@ fn step(&mut self, interpreter: &mut Interpreter, ecx: Ecx) {
             mapping::step(mapping_slots, interpreter);
         }
 
+        // `startOpcodeRecording`: record executed opcodes and accesses.
+        if let Some(recorder) = &mut self.recorded_opcodes {
+            recorder.step(interpreter, &ecx.journaled_state);
+        }
+
         // `snapshotGas*`: take a snapshot of the current gas.
         if self.gas_metering.recording {
             self.meter_gas_record(interpreter, ecx);
@@ -1264,6 +1348,17 @@ fn call_end(&mut self, ecx: Ecx, call: &CallInputs, mut outcome: CallOutcome) ->
         let cheatcode_call = call.target_address == CHEATCODE_ADDRESS ||
             call.target_address == HARDHAT_CONSOLE_ADDRESS;
 
+        // Roll back the database transactions opened by a call that reverts, and all of them when
+        // the test or `setUp()` ends.
+        if !cheatcode_call && !self.db_transactions.is_empty() {
+            let depth = ecx.journaled_state.depth();
+            if depth == 0 {
+                self.rollback_db_transactions();
+            } else if !outcome.result.is_ok() {
+                self.rollback_db_transactions_above(depth);
+            }
+        }
+
         // Clean up pranks/broadcasts if it's not a cheatcode call end. We shouldn't do
         // it for cheatcode calls because they are not applied for cheatcodes in the `call` hook.
         // This should be placed before the revert handling, because we might exit early there
@@ -61,6 +61,7 @@
 mod utils;
 
 mod database;
+pub use database::{connect_db_endpoint, DatabaseClient, SqlValue, SqliteClient};
 
 /// Cheatcode implementation.
 pub(crate) trait Cheatcode: CheatcodeDef + DynCheatcode {
@@ -1,13 +1,25 @@
 //! Implementations of [`Utilities`](spec::Group::Utilities) cheatcodes.
 
 use crate::{Cheatcode, Cheatcodes, CheatcodesExecutor, CheatsCtxt, Result, Vm::*};
-use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_dyn_abi::{eip712::TypedData, DynSolType, DynSolValue, Resolver};
 use alloy_primitives::{aliases::B32, map::HashMap, B64, U256};
 use alloy_sol_types::SolValue;
-use foundry_common::ens::namehash;
+use foundry_common::{eip712, ens::namehash};
 use foundry_evm_core::constants::DEFAULT_CREATE2_DEPLOYER;
+use parking_lot::Mutex;
 use proptest::prelude::Strategy;
 use rand::{Rng, RngCore};
+use std::{
+    path::PathBuf,
+    sync::{Arc, LazyLock},
+};
+
+/// Resolvers of the EIP-712 types of the structs in the project sources, by project root.
+///
+/// The project is compiled for the first type name resolved, and shared by all tests afterwards.
+/// Entries are never invalidated, so later changes to the sources are not picked up.
+static PROJECT_EIP712_RESOLVERS: LazyLock<Mutex<HashMap<PathBuf, Arc<eip712::Resolver>>>> =
+    LazyLock::new(Default::default);
 
 /// Contains locations of traces ignored via cheatcodes.
 ///
@@ -225,6 +237,71 @@ fn apply_stateful(&self, ccx: &mut CheatsCtxt) -> Result {
     }
 }
 
+impl Cheatcode for eip712HashStructCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { typeNameOrDefinition, abiEncodedData } = self;
+        let encode_type = resolve_eip712_type(state, typeNameOrDefinition)?;
+        let mut resolver = Resolver::default();
+        resolver.ingest_string(&encode_type)?;
+
+        // The primary type comes first in the `encodeType`.
+        let primary_type = encode_type.split('(').next().unwrap_or_default();
+        let value = resolver.resolve(primary_type)?.abi_decode(abiEncodedData)?;
+        Ok(resolver.eip712_data_word(&value)?.abi_encode())
+    }
+}
+
+impl Cheatcode for eip712HashTypedDataCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { jsonData } = self;
+        let typed_data = parse_typed_data(state, jsonData)?;
+        Ok(typed_data.eip712_signing_hash()?.abi_encode())
+    }
+}
+
+/// Parses an EIP-712 typed data JSON payload.
+///
+/// If `types` does not define the primary type, its definition is resolved from the project
+/// sources.
+pub(crate) fn parse_typed_data(state: &Cheatcodes, json: &str) -> Result<TypedData> {
+    let mut value: serde_json::Value = serde_json::from_str(json)?;
+    if let Some(object) = value.as_object_mut() {
+        object.entry("types").or_insert_with(|| serde_json::json!({}));
+    }
+    let mut typed_data: TypedData = serde_json::from_value(value)?;
+    if !typed_data.resolver.contains_type_name(&typed_data.primary_type) {
+        let encode_type = resolve_eip712_type(state, &typed_data.primary_type)?;
+        typed_data.resolver.ingest_string(encode_type)?;
+    }
+    Ok(typed_data)
+}
+
+/// Returns the EIP-712 `encodeType` of the given type definition, or of the struct with the given
+/// name in the project sources.
+fn resolve_eip712_type(state: &Cheatcodes, type_name_or_definition: &str) -> Result<String> {
+    if type_name_or_definition.contains('(') {
+        return Ok(type_name_or_definition.to_string())
+    }
+
+    let resolver = {
+        let mut resolvers = PROJECT_EIP712_RESOLVERS.lock();
+        match resolvers.get(&state.config.root) {
+            Some(resolver) => resolver.clone(),
+            None => {
+                let resolver = Arc::new(
+                    eip712::project_resolver(state.config.paths.clone())
+                        .map_err(|err| fmt_err!("failed to compile the project sources: {err}"))?,
+                );
+                resolvers.insert(state.config.root.clone(), resolver.clone());
+                resolver
+            }
+        }
+    };
+    resolver.resolve_struct_eip712_by_name(type_name_or_definition)?.ok_or_else(|| {
+        fmt_err!("no struct `{type_name_or_definition}` supported by EIP-712 found in the project")
+    })
+}
+
 /// Helper to generate a random `uint` value (with given bits or bounded if specified)
 /// from type strategy.
 fn random_uint(state: &mut Cheatcodes, bits: Option<U256>, bounds: Option<(U256, U256)>) -> Result {
@@ -17,7 +17,8 @@
         debug::{ContractSources, DebugTraceIdentifier},
         decode_trace_arena,
         identifier::{CachedSignatures, SignaturesIdentifier, TraceIdentifiers},
-        render_trace_arena_inner, CallTraceDecoder, CallTraceDecoderBuilder, TraceKind, Traces,
+        render_trace_arena_inner, CallTraceDecoder, CallTraceDecoderBuilder, SparsedTraceArena,
+        TraceKind, Traces,
     },
 };
 use std::{
@@ -377,6 +378,48 @@ pub async fn handle_traces(
     debug: bool,
     decode_internal: bool,
 ) -> Result<()> {
+    let (mut decoder, sources) = build_trace_decoder(
+        result.traces.iter().flatten().map(|(_, arena)| arena),
+        config,
+        chain,
+        &labels,
+        with_local_artifacts,
+        decode_internal || debug,
+    )
+    .await?;
+
+    if debug {
+        let mut debugger = Debugger::builder()
+            .traces(result.traces.expect("missing traces"))
+            .decoder(&decoder)
+            .sources(sources)
+            .build();
+        debugger.try_run_tui()?;
+        return Ok(())
+    }
+
+    if decode_internal {
+        decoder.debug_identifier = Some(DebugTraceIdentifier::new(sources));
+    }
+
+    print_traces(&mut result, &decoder, shell::verbosity() > 0, shell::verbosity() > 4).await?;
+
+    Ok(())
+}
+
+/// Builds a decoder for the given traces, identifying their contracts with the configured labels,
+/// Etherscan and, if `with_local_artifacts` is set, the artifacts of the local project.
+///
+/// If `with_sources` is set, the sources of the identified contracts are also returned, for
+/// debugging or decoding internal functions.
+pub async fn build_trace_decoder<'a>(
+    traces: impl IntoIterator<Item = &'a SparsedTraceArena>,
+    config: &Config,
+    chain: Option<Chain>,
+    labels: &[String],
+    with_local_artifacts: bool,
+    with_sources: bool,
+) -> Result<(CallTraceDecoder, ContractSources)> {
     let (known_contracts, mut sources) = if with_local_artifacts {
         let _ = sh_println!("Compiling project to generate artifacts");
         let project = config.project()?;
@@ -418,31 +461,17 @@ pub async fn handle_traces(
 
     let mut decoder = builder.build();
 
-    for (_, trace) in result.traces.as_deref_mut().unwrap_or_default() {
+    for trace in traces {
         decoder.identify(trace, &mut identifier);
     }
 
-    if decode_internal || debug {
+    if with_sources {
         if let Some(ref etherscan_identifier) = identifier.etherscan {
             sources.merge(etherscan_identifier.get_compiled_contracts().await?);
         }
-
-        if debug {
-            let mut debugger = Debugger::builder()
-                .traces(result.traces.expect("missing traces"))
-                .decoder(&decoder)
-                .sources(sources)
-                .build();
-            debugger.try_run_tui()?;
-            return Ok(())
-        }
-
-        decoder.debug_identifier = Some(DebugTraceIdentifier::new(sources));
     }
 
-    print_traces(&mut result, &decoder, shell::verbosity() > 0, shell::verbosity() > 4).await?;
-
-    Ok(())
+    Ok((decoder, sources))
 }
 
 pub async fn print_traces(
@@ -0,0 +1,367 @@
+//! EIP-712 `encodeType` resolution for the structs of a project.
+
+use crate::compile::ProjectCompiler;
+use eyre::{OptionExt, Result};
+use foundry_compilers::{
+    artifacts::{
+        output_selection::OutputSelection,
+        visitor::{Visitor, Walk},
+        ContractDefinition, EnumDefinition, SourceUnit, StructDefinition, TypeDescriptions,
+        TypeName,
+    },
+    multi::MultiCompiler,
+    solc::SolcCompiler,
+    Project, ProjectCompileOutput, ProjectPathsConfig,
+};
+use std::{collections::BTreeMap, fmt::Write, path::PathBuf};
+
+/// Collects the ASTs of the sources in the compiler output into strongly typed [SourceUnit]s.
+pub fn collect_asts(output: ProjectCompileOutput) -> Result<BTreeMap<PathBuf, SourceUnit>> {
+    output
+        .into_output()
+        .sources
+        .into_iter()
+        .filter_map(|(path, mut sources)| Some((path, sources.swap_remove(0).source_file.ast?)))
+        .map(|(path, ast)| {
+            Ok((path, serde_json::from_str::<SourceUnit>(&serde_json::to_string(&ast)?)?))
+        })
+        .collect()
+}
+
+/// Compiles the sources of the project with the given paths for their ASTs, and returns a
+/// [Resolver] for the structs defined in them.
+///
+/// No artifacts or cache files are written.
+pub fn project_resolver(paths: ProjectPathsConfig) -> Result<Resolver> {
+    let compiler = MultiCompiler::new(Some(SolcCompiler::AutoDetect), None)?;
+    let mut project = Project::builder().paths(paths).ephemeral().no_artifacts().build(compiler)?;
+    project.update_output_selection(|selection| {
+        *selection = OutputSelection::ast_output_selection();
+    });
+    // `ProjectCompiler` exits the process if there is nothing to compile.
+    if !project.paths.has_input_files() {
+        return Ok(Resolver::new(&BTreeMap::new()))
+    }
+    let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+    Ok(Resolver::new(&collect_asts(output)?))
+}
+
+/// AST [Visitor] used for collecting struct definitions.
+#[derive(Debug, Clone, Default)]
+pub struct StructCollector(pub BTreeMap<usize, StructDefinition>);
+
+impl Visitor for StructCollector {
+    fn visit_struct_definition(&mut self, def: &StructDefinition) {
+        self.0.insert(def.id, def.clone());
+    }
+}
+
+/// Collects mapping from AST id of type definition to representation of this type for EIP-712
+/// encoding.
+///
+/// For now, maps contract definitions to `address` and enums to `uint8`.
+#[derive(Debug, Clone, Default)]
+struct SimpleCustomTypesCollector(BTreeMap<usize, String>);
+
+impl Visitor for SimpleCustomTypesCollector {
+    fn visit_contract_definition(&mut self, def: &ContractDefinition) {
+        self.0.insert(def.id, "address".to_string());
+    }
+
+    fn visit_enum_definition(&mut self, def: &EnumDefinition) {
+        self.0.insert(def.id, "uint8".to_string());
+    }
+}
+
+pub struct Resolver {
+    simple_types: BTreeMap<usize, String>,
+    structs: BTreeMap<usize, StructDefinition>,
+}
+
+impl Resolver {
+    pub fn new(asts: &BTreeMap<PathBuf, SourceUnit>) -> Self {
+        let simple_types = {
+            let mut collector = SimpleCustomTypesCollector::default();
+            asts.values().for_each(|ast| ast.walk(&mut collector));
+
+            collector.0
+        };
+
+        let structs = {
+            let mut collector = StructCollector::default();
+            asts.values().for_each(|ast| ast.walk(&mut collector));
+            collector.0
+        };
+
+        Self { simple_types, structs }
+    }
+
+    /// Converts a given struct definition into EIP-712 `encodeType` representation.
+    ///
+    /// Returns `None` if struct contains any fields that are not supported by EIP-712 (e.g.
+    /// mappings or function pointers).
+    pub fn resolve_struct_eip712(&self, id: usize) -> Result<Option<String>> {
+        let mut subtypes = BTreeMap::new();
+        subtypes.insert(self.structs[&id].name.clone(), id);
+        self.resolve_eip712_inner(id, &mut subtypes, true, None)
+    }
+
+    /// Converts the struct with the given name, or canonical name (`Contract.Struct`), into
+    /// EIP-712 `encodeType` representation.
+    ///
+    /// Returns `None` if there is no such struct supported by EIP-712, and an error if the name
+    /// refers to several different structs.
+    pub fn resolve_struct_eip712_by_name(&self, name: &str) -> Result<Option<String>> {
+        let mut resolved = None;
+        for def in
+            self.structs.values().filter(|def| def.name == name || def.canonical_name == name)
+        {
+            let Some(encoded) = self.resolve_struct_eip712(def.id)? else { continue };
+            if resolved.as_ref().is_some_and(|resolved| *resolved != encoded) {
+                eyre::bail!(
+                    "multiple structs named `{name}` found; use the canonical name `Contract.{name}`"
+                )
+            }
+            resolved = Some(encoded);
+        }
+        Ok(resolved)
+    }
+
+    fn resolve_eip712_inner(
+        &self,
+        id: usize,
+        subtypes: &mut BTreeMap<String, usize>,
+        append_subtypes: bool,
+        rename: Option<&str>,
+    ) -> Result<Option<String>> {
+        let def = &self.structs[&id];
+        let mut result = format!("{}(", rename.unwrap_or(&def.name));
+
+        for (idx, member) in def.members.iter().enumerate() {
+            let Some(ty) = self.resolve_type(
+                member.type_name.as_ref().ok_or_eyre("missing type name")?,
+                subtypes,
+            )?
+            else {
+                return Ok(None)
+            };
+
+            write!(result, "{ty} {name}", name = member.name)?;
+
+            if idx < def.members.len() - 1 {
+                result.push(',');
+            }
+        }
+
+        result.push(')');
+
+        if !append_subtypes {
+            return Ok(Some(result))
+        }
+
+        for (subtype_name, subtype_id) in
+            subtypes.iter().map(|(name, id)| (name.clone(), *id)).collect::<Vec<_>>()
+        {
+            if subtype_id == id {
+                continue
+            }
+            let Some(encoded_subtype) =
+                self.resolve_eip712_inner(subtype_id, subtypes, false, Some(&subtype_name))?
+            else {
+                return Ok(None)
+            };
+            result.push_str(&encoded_subtype);
+        }
+
+        Ok(Some(result))
+    }
+
+    /// Converts given [TypeName] into a type which can be converted to [DynSolType].
+    ///
+    /// Returns `None` if the type is not supported for EIP712 encoding.
+    pub fn resolve_type(
+        &self,
+        type_name: &TypeName,
+        subtypes: &mut BTreeMap<String, usize>,
+    ) -> Result<Option<String>> {
+        match type_name {
+            TypeName::FunctionTypeName(_) | TypeName::Mapping(_) => Ok(None),
+            TypeName::ElementaryTypeName(ty) => Ok(Some(ty.name.clone())),
+            TypeName::ArrayTypeName(ty) => {
+                let Some(inner) = self.resolve_type(&ty.base_type, subtypes)? else {
+                    return Ok(None)
+                };
+                let len = parse_array_length(&ty.type_descriptions)?;
+
+                Ok(Some(format!("{inner}[{}]", len.unwrap_or(""))))
+            }
+            TypeName::UserDefinedTypeName(ty) => {
+                if let Some(name) = self.simple_types.get(&(ty.referenced_declaration as usize)) {
+                    Ok(Some(name.clone()))
+                } else if let Some(def) = self.structs.get(&(ty.referenced_declaration as usize)) {
+                    let name =
+                        // If we've already seen struct with this ID, just use assigned name.
+                        if let Some((name, _)) = subtypes.iter().find(|(_, id)| **id == def.id) {
+                            name.clone()
+                        } else {
+                            // Otherwise, assign new name.
+                            let mut i = 0;
+                            let mut name = def.name.clone();
+                            while subtypes.contains_key(&name) {
+                                i += 1;
+                                name = format!("{}_{i}", def.name);
+                            }
+
+                            subtypes.insert(name.clone(), def.id);
+
+                            // iterate over members to check if they are resolvable and to populate subtypes
+                            for member in &def.members {
+                                if self.resolve_type(
+                                    member.type_name.as_ref().ok_or_eyre("missing type name")?,
+                                    subtypes,
+                                )?
+                                .is_none()
+                                {
+                                    return Ok(None)
+                                }
+                            }
+                            name
+                        };
+
+                    Ok(Some(name))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
+fn parse_array_length(type_description: &TypeDescriptions) -> Result<Option<&str>> {
+    let type_string =
+        type_description.type_string.as_ref().ok_or_eyre("missing typeString for array type")?;
+    let Some(inside_brackets) =
+        type_string.rsplit_once("[").and_then(|(_, right)| right.split("]").next())
+    else {
+        eyre::bail!("failed to parse array type string: {type_string}")
+    };
+
+    if inside_brackets.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(inside_brackets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn member(id: usize, name: &str, ty: &str) -> Value {
+        json!({
+            "id": id,
+            "src": "0:0:0",
+            "name": name,
+            "scope": 0,
+            "storageLocation": "default",
+            "typeDescriptions": {},
+            "typeName": {
+                "nodeType": "ElementaryTypeName",
+                "id": id + 1,
+                "src": "0:0:0",
+                "name": ty,
+                "typeDescriptions": {},
+            },
+            "visibility": "internal",
+        })
+    }
+
+    fn struct_def(id: usize, name: &str, canonical_name: &str, members: Vec<Value>) -> Value {
+        json!({
+            "nodeType": "StructDefinition",
+            "id": id,
+            "src": "0:0:0",
+            "name": name,
+            "canonicalName": canonical_name,
+            "members": members,
+            "scope": 0,
+            "visibility": "public",
+        })
+    }
+
+    fn contract(id: usize, name: &str, nodes: Vec<Value>) -> Value {
+        json!({
+            "nodeType": "ContractDefinition",
+            "id": id,
+            "src": "0:0:0",
+            "name": name,
+            "baseContracts": [],
+            "contractDependencies": [],
+            "contractKind": "contract",
+            "fullyImplemented": true,
+            "linearizedBaseContracts": [id],
+            "nodes": nodes,
+            "scope": 0,
+        })
+    }
+
+    /// A source with a free `Transfer` struct, and a `Mail` struct in each of the contracts `A` and
+    /// `B`, with different members.
+    fn resolver() -> Resolver {
+        let ast = json!({
+            "id": 0,
+            "src": "0:0:0",
+            "absolutePath": "src/Mail.sol",
+            "nodes": [
+                struct_def(1, "Transfer", "Transfer", vec![member(2, "amount", "uint256")]),
+                contract(10, "A", vec![struct_def(
+                    11,
+                    "Mail",
+                    "A.Mail",
+                    vec![member(12, "from", "address"), member(14, "contents", "string")],
+                )]),
+                contract(20, "B", vec![struct_def(
+                    21,
+                    "Mail",
+                    "B.Mail",
+                    vec![member(22, "amount", "uint256")],
+                )]),
+            ],
+        });
+        let ast: SourceUnit = serde_json::from_value(ast).unwrap();
+        Resolver::new(&BTreeMap::from([(PathBuf::from("src/Mail.sol"), ast)]))
+    }
+
+    #[test]
+    fn resolves_struct_by_name() {
+        let resolver = resolver();
+        assert_eq!(
+            resolver.resolve_struct_eip712_by_name("Transfer").unwrap().as_deref(),
+            Some("Transfer(uint256 amount)")
+        );
+        assert_eq!(resolver.resolve_struct_eip712_by_name("Missing").unwrap(), None);
+    }
+
+    #[test]
+    fn resolves_struct_by_canonical_name() {
+        let resolver = resolver();
+        assert_eq!(
+            resolver.resolve_struct_eip712_by_name("A.Mail").unwrap().as_deref(),
+            Some("Mail(address from,string contents)")
+        );
+        assert_eq!(
+            resolver.resolve_struct_eip712_by_name("B.Mail").unwrap().as_deref(),
+            Some("Mail(uint256 amount)")
+        );
+    }
+
+    #[test]
+    fn rejects_ambiguous_struct_name() {
+        let err = resolver().resolve_struct_eip712_by_name("Mail").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "multiple structs named `Mail` found; use the canonical name `Contract.Mail`"
+        );
+    }
+}
@@ -21,6 +21,7 @@
 pub mod compile;
 pub mod constants;
 pub mod contracts;
+pub mod eip712;
 pub mod ens;
 pub mod errors;
 pub mod evm;
@@ -29,7 +29,7 @@ fn is_setup(&self) -> bool {
         self.test_function_kind().is_setup()
     }
 
-    /// Returns `true` if this function is a unit, fuzz, or invariant test.
+    /// Returns `true` if this function is a unit, fuzz, invariant or differential test.
     fn is_any_test(&self) -> bool {
         self.test_function_kind().is_any_test()
     }
@@ -59,6 +59,11 @@ fn is_invariant_test(&self) -> bool {
         self.test_function_kind().is_invariant_test()
     }
 
+    /// Returns `true` if this function is a differential test.
+    fn is_differential_test(&self) -> bool {
+        self.test_function_kind().is_differential_test()
+    }
+
     /// Returns `true` if this function is an `afterInvariant` function.
     fn is_after_invariant(&self) -> bool {
         self.test_function_kind().is_after_invariant()
@@ -69,6 +74,11 @@ fn is_fixture(&self) -> bool {
         self.test_function_kind().is_fixture()
     }
 
+    /// Returns `true` if this function is a `gen` function.
+    fn is_generator(&self) -> bool {
+        self.test_function_kind().is_generator()
+    }
+
     fn is_iterate_blocks(&self) -> bool {
         self.test_function_kind().is_iterate_blocks()
     }
@@ -124,10 +134,14 @@ pub enum TestFunctionKind {
     FuzzTest { should_fail: bool },
     /// `invariant*` or `statefulFuzz*`.
     InvariantTest,
+    /// `differential*`.
+    DifferentialTest,
     /// `afterInvariant`.
     AfterInvariant,
     /// `fixture*`.
     Fixture,
+    /// `gen_*` or `genX*`, with arguments.
+    Generator,
     /// loop event logs
     IterateEventLogs,
     /// loop blocks
@@ -152,9 +166,11 @@ pub fn classify(name: &str, has_inputs: bool) -> Self {
             _ if name.starts_with("invariant") || name.starts_with("statefulFuzz") => {
                 Self::InvariantTest
             }
+            _ if name.starts_with("differential") => Self::DifferentialTest,
             _ if name.eq_ignore_ascii_case("setup") => Self::Setup,
             _ if name.eq_ignore_ascii_case("afterinvariant") => Self::AfterInvariant,
             _ if name.starts_with("fixture") => Self::Fixture,
+            _ if has_inputs && is_generator_name(name) => Self::Generator,
             _ if name.starts_with("iterEventLogs") => Self::IterateEventLogs,
             _ if name.starts_with("iterBlocks") => Self::IterateBlocks,
 
@@ -171,8 +187,10 @@ pub const fn name(&self) -> &'static str {
             Self::FuzzTest { should_fail: false } => "fuzz",
             Self::FuzzTest { should_fail: true } => "fuzz fail",
             Self::InvariantTest => "invariant",
+            Self::DifferentialTest => "differential",
             Self::AfterInvariant => "afterInvariant",
             Self::Fixture => "fixture",
+            Self::Generator => "generator",
             Self::IterateEventLogs => "iterEventLogs",
             Self::IterateBlocks => "iterBlocks",
             Self::Unknown => "unknown",
@@ -185,10 +203,16 @@ pub const fn is_setup(&self) -> bool {
         matches!(self, Self::Setup)
     }
 
-    /// Returns `true` if this function is a unit, fuzz, or invariant test.
+    /// Returns `true` if this function is a unit, fuzz, invariant or differential test.
     #[inline]
     pub const fn is_any_test(&self) -> bool {
-        matches!(self, Self::UnitTest { .. } | Self::FuzzTest { .. } | Self::InvariantTest)
+        matches!(
+            self,
+            Self::UnitTest { .. } |
+                Self::FuzzTest { .. } |
+                Self::InvariantTest |
+                Self::DifferentialTest
+        )
     }
 
     /// Returns `true` if this function is a test that should fail.
@@ -215,6 +239,12 @@ pub const fn is_invariant_test(&self) -> bool {
         matches!(self, Self::InvariantTest)
     }
 
+    /// Returns `true` if this function is a differential test.
+    #[inline]
+    pub const fn is_differential_test(&self) -> bool {
+        matches!(self, Self::DifferentialTest)
+    }
+
     /// Returns `true` if this function is an `afterInvariant` function.
     #[inline]
     pub const fn is_after_invariant(&self) -> bool {
@@ -227,6 +257,12 @@ pub const fn is_fixture(&self) -> bool {
         matches!(self, Self::Fixture)
     }
 
+    /// Returns `true` if this function is a `gen` function.
+    #[inline]
+    pub const fn is_generator(&self) -> bool {
+        matches!(self, Self::Generator)
+    }
+
     /// Returns `true` if this function is a `iterEventLogs` function.
     #[inline]
     pub const fn is_iterate_event_logs(&self) -> bool {
@@ -252,6 +288,14 @@ pub const fn is_unknown(&self) -> bool {
     }
 }
 
+/// Returns `true` if `name` is a generator name, i.e. `gen_*` or `gen` followed by an uppercase
+/// letter, so that helpers like `generate` are not picked up.
+fn is_generator_name(name: &str) -> bool {
+    name.strip_prefix("gen")
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c == '_' || c.is_ascii_uppercase())
+}
+
 impl fmt::Display for TestFunctionKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.name().fmt(f)
@@ -30,6 +30,12 @@ pub struct FuzzConfig {
     pub show_logs: bool,
     /// Optional timeout (in seconds) for each property test
     pub timeout: Option<u32>,
+    /// Whether differential tests compare the storage written by both implementations, defaults
+    /// to `true`. Disable when implementations use different storage layouts.
+    pub differential_storage: bool,
+    /// The fuzz campaign configuration
+    #[serde(flatten)]
+    pub campaign: FuzzCampaignConfig,
 }
 
 impl Default for FuzzConfig {
@@ -44,6 +50,8 @@ fn default() -> Self {
             failure_persist_file: None,
             show_logs: false,
             timeout: None,
+            differential_storage: true,
+            campaign: FuzzCampaignConfig::default(),
         }
     }
 }
@@ -69,6 +77,9 @@ pub struct FuzzDictionaryConfig {
     pub include_storage: bool,
     /// The flag indicating whether to include push bytes values
     pub include_push_bytes: bool,
+    /// The flag indicating whether to include values compared against fuzzed inputs, traced from
+    /// `EQ`, `LT`, `GT` and `SUB` operands
+    pub include_comparisons: bool,
     /// How many addresses to record at most.
     /// Once the fuzzer exceeds this limit, it will start evicting random entries
     ///
@@ -87,6 +98,7 @@ fn default() -> Self {
             dictionary_weight: 40,
             include_storage: true,
             include_push_bytes: true,
+            include_comparisons: false,
             // limit this to 300MB
             max_fuzz_dictionary_addresses: (300 * 1024 * 1024) / 20,
             // limit this to 200MB
@@ -94,3 +106,31 @@ fn default() -> Self {
         }
     }
 }
+
+/// Contains the configuration of time-budgeted fuzz campaigns.
+///
+/// A campaign is enabled by setting a `campaign_budget`: each property test then ignores the
+/// configured number of runs and keeps executing cases on `campaign_workers` threads until the
+/// budget is spent or a counterexample is found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzCampaignConfig {
+    /// Wall-clock budget (in seconds) of each property test.
+    pub campaign_budget: Option<u32>,
+    /// Number of worker threads sharing a campaign, defaults to the available parallelism.
+    pub campaign_workers: Option<u32>,
+}
+
+impl FuzzCampaignConfig {
+    /// Returns `true` if property tests should run as time-budgeted campaigns.
+    pub fn is_enabled(&self) -> bool {
+        self.campaign_budget.is_some()
+    }
+
+    /// Returns the number of worker threads to spawn for a campaign.
+    pub fn workers(&self) -> usize {
+        match self.campaign_workers {
+            Some(workers) => workers.max(1) as usize,
+            None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
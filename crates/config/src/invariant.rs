@@ -1,6 +1,6 @@
 //! Configuration for invariant testing
 
-use crate::fuzz::FuzzDictionaryConfig;
+use crate::fuzz::{FuzzCampaignConfig, FuzzDictionaryConfig};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -32,6 +32,9 @@ pub struct InvariantConfig {
     pub show_metrics: bool,
     /// Optional timeout (in seconds) for each invariant test.
     pub timeout: Option<u32>,
+    /// The fuzz campaign configuration
+    #[serde(flatten)]
+    pub campaign: FuzzCampaignConfig,
 }
 
 impl Default for InvariantConfig {
@@ -48,6 +51,7 @@ fn default() -> Self {
             failure_persist_dir: None,
             show_metrics: false,
             timeout: None,
+            campaign: FuzzCampaignConfig::default(),
         }
     }
 }
@@ -67,6 +71,7 @@ pub fn new(cache_dir: PathBuf) -> Self {
             failure_persist_dir: Some(cache_dir),
             show_metrics: false,
             timeout: None,
+            campaign: FuzzCampaignConfig::default(),
         }
     }
 
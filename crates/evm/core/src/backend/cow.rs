@@ -16,8 +16,8 @@
 use revm::{
     db::DatabaseRef,
     primitives::{
-        Account, AccountInfo, Bytecode, Env, EnvWithHandlerCfg, HashMap as Map, ResultAndState,
-        SpecId,
+        Account, AccountInfo, Bytecode, Env, EnvWithHandlerCfg, ExecutionResult, HashMap as Map,
+        ResultAndState, SpecId,
     },
     Database, DatabaseCommit, JournaledState,
 };
@@ -197,7 +197,7 @@ fn transact_from_tx(
         env: Env,
         journaled_state: &mut JournaledState,
         inspector: &mut dyn InspectorExt,
-    ) -> eyre::Result<()> {
+    ) -> eyre::Result<ExecutionResult> {
         self.backend_mut(&env).transact_from_tx(transaction, env, journaled_state, inspector)
     }
 
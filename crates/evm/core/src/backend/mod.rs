@@ -20,7 +20,7 @@
     precompile::{PrecompileSpecId, Precompiles},
     primitives::{
         Account, AccountInfo, BlobExcessGasAndPrice, Bytecode, Env, EnvWithHandlerCfg, EvmState,
-        EvmStorageSlot, HashMap as Map, Log, ResultAndState, SpecId, KECCAK_EMPTY,
+        EvmStorageSlot, ExecutionResult, HashMap as Map, Log, ResultAndState, SpecId, KECCAK_EMPTY,
     },
     Database, DatabaseCommit, JournaledState,
 };
@@ -203,14 +203,15 @@ fn transact(
         inspector: &mut dyn InspectorExt,
     ) -> eyre::Result<()>;
 
-    /// Executes a given TransactionRequest, commits the new state to the DB
+    /// Executes a given TransactionRequest, commits the new state to the DB and returns the result
+    /// of the execution
     fn transact_from_tx(
         &mut self,
         transaction: &TransactionRequest,
         env: Env,
         journaled_state: &mut JournaledState,
         inspector: &mut dyn InspectorExt,
-    ) -> eyre::Result<()>;
+    ) -> eyre::Result<ExecutionResult>;
 
     /// Returns the `ForkId` that's currently used in the database, if fork mode is on
     fn active_fork_id(&self) -> Option<LocalForkId>;
@@ -1275,7 +1276,7 @@ fn transact_from_tx(
         mut env: Env,
         journaled_state: &mut JournaledState,
         inspector: &mut dyn InspectorExt,
-    ) -> eyre::Result<()> {
+    ) -> eyre::Result<ExecutionResult> {
         trace!(?tx, "execute signed transaction");
 
         self.commit(journaled_state.state.clone());
@@ -1293,7 +1294,7 @@ fn transact_from_tx(
         self.commit(res.state);
         update_state(&mut journaled_state.state, self, None)?;
 
-        Ok(())
+        Ok(res.result)
     }
 
     fn active_fork_id(&self) -> Option<LocalForkId> {
@@ -0,0 +1,378 @@
+//! Differential fuzzing of two implementations of the same interface.
+
+use super::{FuzzTestData, FuzzedExecutor};
+use crate::executors::{FuzzTestTimer, RawCallResult};
+use alloy_dyn_abi::JsonAbiExt;
+use alloy_json_abi::Function;
+use alloy_primitives::{Address, Bytes, U256};
+use foundry_evm_core::constants::TEST_TIMEOUT;
+use foundry_evm_fuzz::{
+    BaseCounterExample, CounterExample, FuzzCase, FuzzFixtures, FuzzTestResult,
+};
+use indicatif::ProgressBar;
+use proptest::{
+    strategy::{Strategy, Union},
+    test_runner::{TestCaseError, TestError},
+};
+use std::{cell::RefCell, collections::BTreeMap, fmt};
+
+/// Two implementations compared by a differential fuzz test.
+#[derive(Clone, Debug)]
+pub struct DifferentialTargets {
+    /// The address of the reference implementation.
+    pub reference: Address,
+    /// The address of the candidate implementation.
+    pub candidate: Address,
+    /// The name of the reference implementation, if identified.
+    pub name: Option<String>,
+    /// The functions of the interface shared by both implementations.
+    pub functions: Vec<Function>,
+}
+
+/// A difference observed between the executions of the same call on both implementations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Divergence {
+    /// Only one of the implementations reverted.
+    Revert { reference: bool, candidate: bool },
+    /// Both implementations returned different data.
+    ReturnData { reference: Bytes, candidate: Bytes },
+    /// Both implementations emitted different logs, starting at `index`.
+    Logs { index: usize },
+    /// Both implementations wrote different values to `slot`.
+    Storage { slot: U256, reference: Option<U256>, candidate: Option<U256> },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reverted = |reverted: &bool| if *reverted { "reverted" } else { "succeeded" };
+        let value = |value: &Option<U256>| value.map_or("unchanged".to_string(), |v| v.to_string());
+        match self {
+            Self::Revert { reference, candidate } => write!(
+                f,
+                "revert status diverged: reference {}, candidate {}",
+                reverted(reference),
+                reverted(candidate)
+            ),
+            Self::ReturnData { reference, candidate } => {
+                write!(f, "return data diverged: reference {reference}, candidate {candidate}")
+            }
+            Self::Logs { index } => write!(f, "emitted logs diverged at index {index}"),
+            Self::Storage { slot, reference, candidate } => write!(
+                f,
+                "storage diverged at slot {slot}: reference {}, candidate {}",
+                value(reference),
+                value(candidate)
+            ),
+        }
+    }
+}
+
+impl Divergence {
+    /// Compares the results of the same call executed on the reference and the candidate
+    /// implementations, returning the first divergence found.
+    ///
+    /// Logs are compared on topics and data only, since implementations live at different
+    /// addresses. Storage is compared on the slots written by each implementation to its own
+    /// account, if `compare_storage` is set.
+    pub fn find(
+        targets: &DifferentialTargets,
+        reference: &RawCallResult,
+        candidate: &RawCallResult,
+        compare_storage: bool,
+    ) -> Option<Self> {
+        if reference.reverted != candidate.reverted {
+            return Some(Self::Revert {
+                reference: reference.reverted,
+                candidate: candidate.reverted,
+            });
+        }
+
+        if reference.result != candidate.result {
+            return Some(Self::ReturnData {
+                reference: reference.result.clone(),
+                candidate: candidate.result.clone(),
+            });
+        }
+
+        let (reference_logs, candidate_logs) = (&reference.logs, &candidate.logs);
+        if let Some(index) =
+            reference_logs.iter().zip(candidate_logs).position(|(a, b)| a.data != b.data).or_else(
+                || {
+                    (reference_logs.len() != candidate_logs.len())
+                        .then(|| reference_logs.len().min(candidate_logs.len()))
+                },
+            )
+        {
+            return Some(Self::Logs { index });
+        }
+
+        if compare_storage {
+            let reference_writes = storage_writes(reference, targets.reference);
+            let candidate_writes = storage_writes(candidate, targets.candidate);
+            if let Some((slot, reference, candidate)) = reference_writes
+                .keys()
+                .chain(candidate_writes.keys())
+                .map(|slot| (*slot, reference_writes.get(slot), candidate_writes.get(slot)))
+                .find(|(_, reference, candidate)| reference != candidate)
+            {
+                return Some(Self::Storage {
+                    slot,
+                    reference: reference.copied(),
+                    candidate: candidate.copied(),
+                });
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the storage slots changed by a call in the account at `address`.
+fn storage_writes(call: &RawCallResult, address: Address) -> BTreeMap<U256, U256> {
+    call.state_changeset
+        .get(&address)
+        .map(|account| {
+            account
+                .storage
+                .iter()
+                .filter(|(_, slot)| slot.is_changed())
+                .map(|(key, slot)| (*key, slot.present_value))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl FuzzedExecutor {
+    /// Fuzzes the functions of the interface shared by two implementations, executing every input
+    /// on both and failing on the first divergence in revert status, return data, emitted logs or
+    /// written storage.
+    ///
+    /// Every input is executed on the state left by `setUp`. Diverging inputs are shrunk by the
+    /// test runner, and the minimal one is reported as counterexample, with the traces of the
+    /// reference call as test traces and the traces of the candidate call as counterexample
+    /// traces.
+    pub fn fuzz_differential(
+        &self,
+        targets: &DifferentialTargets,
+        fuzz_fixtures: &FuzzFixtures,
+        deployed_libs: &[Address],
+        progress: Option<&ProgressBar>,
+    ) -> FuzzTestResult {
+        let state = self.build_fuzz_state(deployed_libs);
+        let max_traces_to_collect = std::cmp::max(1, self.config.gas_report_samples) as usize;
+        let strategy = Union::new(
+            targets.functions.iter().map(|func| self.strategy(func, fuzz_fixtures, &state).boxed()),
+        );
+
+        let execution_data = RefCell::new(FuzzTestData::default());
+        // The divergence and the candidate call of the last failing input, if it diverged.
+        let divergence = RefCell::new(None::<(Divergence, RawCallResult)>);
+        let timer = FuzzTestTimer::new(self.config.timeout);
+
+        let run_result = self.runner.clone().run(&strategy, |calldata| {
+            if timer.is_timed_out() {
+                return Err(TestCaseError::fail(TEST_TIMEOUT));
+            }
+
+            let call = |address| {
+                self.executor.call_raw(self.sender, address, calldata.clone(), U256::ZERO).map_err(
+                    |e| {
+                        // The last failure comes from the executor, not from a divergence.
+                        divergence.replace(None);
+                        TestCaseError::fail(e.to_string())
+                    },
+                )
+            };
+            let reference = call(targets.reference)?;
+            let candidate = call(targets.candidate)?;
+
+            if let Some(progress) = progress {
+                progress.inc(1);
+            }
+
+            match Divergence::find(
+                targets,
+                &reference,
+                &candidate,
+                self.config.differential_storage,
+            ) {
+                None => {
+                    let mut data = execution_data.borrow_mut();
+                    data.gas_by_case.push((reference.gas_used, reference.stipend));
+                    if data.first_case.is_none() {
+                        data.first_case.replace(FuzzCase {
+                            calldata,
+                            gas: reference.gas_used,
+                            stipend: reference.stipend,
+                        });
+                    }
+                    if let Some(traces) = reference.traces {
+                        if data.traces.len() == max_traces_to_collect {
+                            data.traces.pop();
+                        }
+                        data.traces.push(traces);
+                    }
+                    Ok(())
+                }
+                Some(found) => {
+                    let reason = found.to_string();
+                    execution_data.borrow_mut().counterexample = (calldata, reference);
+                    divergence.replace(Some((found, candidate)));
+                    Err(TestCaseError::fail(reason))
+                }
+            }
+        });
+
+        let mut execution_data = execution_data.into_inner();
+        let (calldata, reference) = std::mem::take(&mut execution_data.counterexample);
+        let mut result = FuzzTestResult {
+            first_case: execution_data.first_case.unwrap_or_default(),
+            gas_by_case: execution_data.gas_by_case,
+            success: run_result.is_ok(),
+            skipped: false,
+            reason: None,
+            counterexample: None,
+            logs: vec![],
+            labeled_addresses: reference.labels,
+            traces: None,
+            breakpoints: None,
+            gas_report_traces: vec![],
+            coverage: None,
+            deprecated_cheatcodes: Default::default(),
+            campaign: None,
+        };
+
+        match run_result {
+            Ok(()) => {
+                result.traces = execution_data.traces.pop();
+                result.gas_report_traces =
+                    execution_data.traces.into_iter().map(|a| a.arena).collect();
+            }
+            Err(TestError::Abort(reason)) => {
+                result.reason = Some(reason.message().to_string());
+            }
+            Err(TestError::Fail(reason, _)) => {
+                let reason = reason.to_string();
+                if reason == TEST_TIMEOUT {
+                    result.success = true;
+                } else {
+                    // The last failure may also come from the executor, in which case there's no
+                    // diverging input to report.
+                    if let Some((_, candidate)) = divergence.into_inner() {
+                        result.traces = reference.traces;
+                        result.counterexample = Some(CounterExample::Single(counterexample(
+                            targets, calldata, candidate,
+                        )));
+                    }
+                    result.reason = Some(reason);
+                }
+            }
+        }
+
+        state.log_stats();
+
+        result
+    }
+}
+
+/// Creates the counterexample of a diverging call.
+fn counterexample(
+    targets: &DifferentialTargets,
+    calldata: Bytes,
+    candidate: RawCallResult,
+) -> BaseCounterExample {
+    let func = calldata
+        .get(..4)
+        .and_then(|selector| targets.functions.iter().find(|f| f.selector() == selector));
+    let args = func.and_then(|func| func.abi_decode_input(&calldata[4..], false).ok());
+    BaseCounterExample {
+        sender: None,
+        addr: Some(targets.reference),
+        calldata,
+        contract_name: targets.name.clone(),
+        signature: func.map(|func| func.signature()),
+        args: args
+            .map(|args| foundry_common::fmt::format_tokens(&args).collect::<Vec<_>>().join(", ")),
+        traces: candidate.traces,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes, Log, B256};
+
+    fn targets() -> DifferentialTargets {
+        DifferentialTargets {
+            reference: address!("00000000000000000000000000000000000000aa"),
+            candidate: address!("00000000000000000000000000000000000000bb"),
+            name: None,
+            functions: vec![],
+        }
+    }
+
+    fn call(reverted: bool, result: Bytes, logs: Vec<Log>) -> RawCallResult {
+        RawCallResult { reverted, result, logs, ..Default::default() }
+    }
+
+    fn log(emitter: Address, topic: u8, data: Bytes) -> Log {
+        Log::new_unchecked(emitter, vec![B256::with_last_byte(topic)], data)
+    }
+
+    #[test]
+    fn same_results_do_not_diverge() {
+        let targets = targets();
+        let reference = call(false, bytes!("01"), vec![log(targets.reference, 1, bytes!("02"))]);
+        let candidate = call(false, bytes!("01"), vec![log(targets.candidate, 1, bytes!("02"))]);
+        assert_eq!(Divergence::find(&targets, &reference, &candidate, true), None);
+    }
+
+    #[test]
+    fn diverges_on_revert() {
+        let reference = call(false, bytes!("01"), vec![]);
+        let candidate = call(true, bytes!("02"), vec![]);
+        assert_eq!(
+            Divergence::find(&targets(), &reference, &candidate, false),
+            Some(Divergence::Revert { reference: false, candidate: true })
+        );
+    }
+
+    #[test]
+    fn diverges_on_return_data() {
+        let reference = call(true, bytes!("01"), vec![]);
+        let candidate = call(true, bytes!("02"), vec![]);
+        assert_eq!(
+            Divergence::find(&targets(), &reference, &candidate, false),
+            Some(Divergence::ReturnData { reference: bytes!("01"), candidate: bytes!("02") })
+        );
+    }
+
+    #[test]
+    fn diverges_on_logs() {
+        let targets = targets();
+        let first = |emitter| log(emitter, 1, bytes!("01"));
+        let reference = call(false, Bytes::new(), vec![first(targets.reference)]);
+
+        let extra = call(
+            false,
+            Bytes::new(),
+            vec![first(targets.candidate), log(targets.candidate, 2, bytes!("02"))],
+        );
+        let different_topics =
+            call(false, Bytes::new(), vec![log(targets.candidate, 2, bytes!("01"))]);
+        let missing = call(false, Bytes::new(), vec![]);
+
+        assert_eq!(
+            Divergence::find(&targets, &reference, &extra, false),
+            Some(Divergence::Logs { index: 1 })
+        );
+        assert_eq!(
+            Divergence::find(&targets, &reference, &different_topics, false),
+            Some(Divergence::Logs { index: 0 })
+        );
+        assert_eq!(
+            Divergence::find(&targets, &reference, &missing, false),
+            Some(Divergence::Logs { index: 0 })
+        );
+    }
+}
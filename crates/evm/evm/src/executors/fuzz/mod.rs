@@ -1,4 +1,7 @@
-use crate::executors::{Executor, FuzzTestTimer, RawCallResult};
+use crate::{
+    executors::{Executor, FuzzTestTimer, RawCallResult},
+    inspectors::CmpLog,
+};
 use alloy_dyn_abi::JsonAbiExt;
 use alloy_json_abi::Function;
 use alloy_primitives::{map::HashMap, Address, Bytes, Log, U256};
@@ -12,12 +15,25 @@
 use foundry_evm_coverage::HitMaps;
 use foundry_evm_fuzz::{
     strategies::{fuzz_calldata, fuzz_calldata_from_state, EvmFuzzState},
-    BaseCounterExample, CounterExample, FuzzCase, FuzzError, FuzzFixtures, FuzzTestResult,
+    BaseCounterExample, CounterExample, FuzzCampaignStats, FuzzCase, FuzzCorpus, FuzzError,
+    FuzzFixtures, FuzzTestResult,
 };
 use foundry_evm_traces::SparsedTraceArena;
 use indicatif::ProgressBar;
-use proptest::test_runner::{TestCaseError, TestError, TestRunner};
-use std::{cell::RefCell, collections::BTreeMap};
+use parking_lot::Mutex;
+use proptest::{
+    strategy::{Strategy, ValueTree},
+    test_runner::{TestCaseError, TestError, TestRunner},
+};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+    time::Instant,
+};
+
+mod differential;
+pub use differential::{DifferentialTargets, Divergence};
 
 mod types;
 pub use types::{CaseOutcome, CounterExampleOutcome, FuzzOutcome};
@@ -45,6 +61,52 @@ pub struct FuzzTestData {
     pub deprecated_cheatcodes: HashMap<&'static str, Option<&'static str>>,
 }
 
+impl FuzzTestData {
+    /// Records the outcome of a successful fuzz case.
+    fn record_case(&mut self, case: CaseOutcome, max_traces_to_collect: usize, show_logs: bool) {
+        self.gas_by_case.push((case.case.gas, case.case.stipend));
+
+        if self.first_case.is_none() {
+            self.first_case.replace(case.case);
+        }
+
+        if let Some(call_traces) = case.traces {
+            if self.traces.len() == max_traces_to_collect {
+                self.traces.pop();
+            }
+            self.traces.push(call_traces);
+            self.breakpoints.replace(case.breakpoints);
+        }
+
+        if show_logs {
+            self.logs.extend(case.logs);
+        }
+
+        HitMaps::merge_opt(&mut self.coverage, case.coverage);
+
+        self.deprecated_cheatcodes = case.deprecated_cheatcodes;
+    }
+
+    /// Merges the data collected by another campaign worker.
+    fn merge(&mut self, other: Self, max_traces_to_collect: usize) {
+        if self.first_case.is_none() {
+            self.first_case = other.first_case;
+        }
+        self.gas_by_case.extend(other.gas_by_case);
+        self.traces.extend(other.traces);
+        if self.traces.len() > max_traces_to_collect {
+            self.traces.drain(..self.traces.len() - max_traces_to_collect);
+        }
+        if other.breakpoints.is_some() {
+            self.breakpoints = other.breakpoints;
+        }
+        HitMaps::merge_opt(&mut self.coverage, other.coverage);
+        self.logs.extend(other.logs);
+        self.gas_snapshots.extend(other.gas_snapshots);
+        self.deprecated_cheatcodes.extend(other.deprecated_cheatcodes);
+    }
+}
+
 /// Wrapper around an [`Executor`] which provides fuzzing support using [`proptest`].
 ///
 /// After instantiation, calling `fuzz` will proceed to hammer the deployed smart contract with
@@ -76,10 +138,13 @@ pub fn new(
     /// If `should_fail` is set to `true`, then it will stop only when there's a success
     /// test case.
     ///
+    /// If a campaign budget is configured, runs are spread across multiple workers until the
+    /// budget is spent, see [`FuzzedExecutor::fuzz_campaign`].
+    ///
     /// Returns a list of all the consumed gas and calldata of every fuzz case
     #[allow(clippy::too_many_arguments)]
     pub fn fuzz(
-        &self,
+        &mut self,
         func: &Function,
         fuzz_fixtures: &FuzzFixtures,
         deployed_libs: &[Address],
@@ -88,80 +153,38 @@ pub fn fuzz(
         rd: &RevertDecoder,
         progress: Option<&ProgressBar>,
     ) -> FuzzTestResult {
-        // Stores the fuzz test execution data.
-        let execution_data = RefCell::new(FuzzTestData::default());
         let state = self.build_fuzz_state(deployed_libs);
-        let dictionary_weight = self.config.dictionary.dictionary_weight.min(100);
-        let strategy = proptest::prop_oneof![
-            100 - dictionary_weight => fuzz_calldata(func.clone(), fuzz_fixtures),
-            dictionary_weight => fuzz_calldata_from_state(func.clone(), &state),
-        ];
+        if self.config.dictionary.include_comparisons {
+            self.executor.inspector_mut().set_cmp_log(CmpLog::new(state.clone()));
+        }
         // We want to collect at least one trace which will be displayed to user.
         let max_traces_to_collect = std::cmp::max(1, self.config.gas_report_samples) as usize;
-        let show_logs = self.config.show_logs;
 
-        // Start timer for this fuzz test.
-        let timer = FuzzTestTimer::new(self.config.timeout);
-
-        let run_result = self.runner.clone().run(&strategy, |calldata| {
-            // Check if the timeout has been reached.
-            if timer.is_timed_out() {
-                return Err(TestCaseError::fail(TEST_TIMEOUT));
-            }
-
-            let fuzz_res = self.single_fuzz(address, should_fail, calldata)?;
-
-            // If running with progress then increment current run.
-            if let Some(progress) = progress {
-                progress.inc(1);
-            };
-
-            match fuzz_res {
-                FuzzOutcome::Case(case) => {
-                    let mut data = execution_data.borrow_mut();
-                    data.gas_by_case.push((case.case.gas, case.case.stipend));
-
-                    if data.first_case.is_none() {
-                        data.first_case.replace(case.case);
-                    }
-
-                    if let Some(call_traces) = case.traces {
-                        if data.traces.len() == max_traces_to_collect {
-                            data.traces.pop();
-                        }
-                        data.traces.push(call_traces);
-                        data.breakpoints.replace(case.breakpoints);
-                    }
-
-                    if show_logs {
-                        data.logs.extend(case.logs);
-                    }
-
-                    HitMaps::merge_opt(&mut data.coverage, case.coverage);
-
-                    data.deprecated_cheatcodes = case.deprecated_cheatcodes;
-
-                    Ok(())
-                }
-                FuzzOutcome::CounterExample(CounterExampleOutcome {
-                    exit_reason: status,
-                    counterexample: outcome,
-                    ..
-                }) => {
-                    // We cannot use the calldata returned by the test runner in `TestError::Fail`,
-                    // since that input represents the last run case, which may not correspond with
-                    // our failure - when a fuzz case fails, proptest will try to run at least one
-                    // more case to find a minimal failure case.
-                    let reason = rd.maybe_decode(&outcome.1.result, Some(status));
-                    execution_data.borrow_mut().logs.extend(outcome.1.logs.clone());
-                    execution_data.borrow_mut().counterexample = outcome;
-                    // HACK: we have to use an empty string here to denote `None`.
-                    Err(TestCaseError::fail(reason.unwrap_or_default()))
-                }
-            }
-        });
+        let (fuzz_result, run_result, campaign) = if self.config.campaign.is_enabled() {
+            let (fuzz_result, run_result, campaign) = self.fuzz_campaign(
+                func,
+                fuzz_fixtures,
+                &state,
+                address,
+                should_fail,
+                rd,
+                max_traces_to_collect,
+                progress,
+            );
+            (fuzz_result, run_result, Some(campaign))
+        } else {
+            let strategy = self.strategy(func, fuzz_fixtures, &state);
+            let (fuzz_result, run_result) = self.fuzz_runs(
+                &strategy,
+                address,
+                should_fail,
+                rd,
+                max_traces_to_collect,
+                progress,
+            );
+            (fuzz_result, run_result, None)
+        };
 
-        let fuzz_result = execution_data.into_inner();
         let (calldata, call) = fuzz_result.counterexample;
 
         let mut traces = fuzz_result.traces;
@@ -185,6 +208,7 @@ pub fn fuzz(
             gas_report_traces: traces.into_iter().map(|a| a.arena).collect(),
             coverage: fuzz_result.coverage,
             deprecated_cheatcodes: fuzz_result.deprecated_cheatcodes,
+            campaign,
         };
 
         match run_result {
@@ -233,6 +257,263 @@ pub fn fuzz(
         result
     }
 
+    /// Returns the calldata strategy of the fuzzed function, drawing values from the fuzz
+    /// dictionary according to the configured weight.
+    fn strategy(
+        &self,
+        func: &Function,
+        fuzz_fixtures: &FuzzFixtures,
+        state: &EvmFuzzState,
+    ) -> impl Strategy<Value = Bytes> {
+        let dictionary_weight = self.config.dictionary.dictionary_weight.min(100);
+        proptest::prop_oneof![
+            100 - dictionary_weight => fuzz_calldata(func.clone(), fuzz_fixtures),
+            dictionary_weight => fuzz_calldata_from_state(func.clone(), state, fuzz_fixtures),
+        ]
+    }
+
+    /// Runs the configured number of fuzz cases on the current thread.
+    fn fuzz_runs(
+        &self,
+        strategy: &impl Strategy<Value = Bytes>,
+        address: Address,
+        should_fail: bool,
+        rd: &RevertDecoder,
+        max_traces_to_collect: usize,
+        progress: Option<&ProgressBar>,
+    ) -> (FuzzTestData, Result<(), TestError<Bytes>>) {
+        // Stores the fuzz test execution data.
+        let execution_data = RefCell::new(FuzzTestData::default());
+        let show_logs = self.config.show_logs;
+
+        // Start timer for this fuzz test.
+        let timer = FuzzTestTimer::new(self.config.timeout);
+
+        let run_result = self.runner.clone().run(strategy, |calldata| {
+            // Check if the timeout has been reached.
+            if timer.is_timed_out() {
+                return Err(TestCaseError::fail(TEST_TIMEOUT));
+            }
+
+            let fuzz_res = self.single_fuzz(address, should_fail, calldata)?;
+
+            // If running with progress then increment current run.
+            if let Some(progress) = progress {
+                progress.inc(1);
+            };
+
+            match fuzz_res {
+                FuzzOutcome::Case(case) => {
+                    execution_data.borrow_mut().record_case(case, max_traces_to_collect, show_logs);
+                    Ok(())
+                }
+                FuzzOutcome::CounterExample(CounterExampleOutcome {
+                    exit_reason: status,
+                    counterexample: outcome,
+                    ..
+                }) => {
+                    // We cannot use the calldata returned by the test runner in `TestError::Fail`,
+                    // since that input represents the last run case, which may not correspond with
+                    // our failure - when a fuzz case fails, proptest will try to run at least one
+                    // more case to find a minimal failure case.
+                    let reason = rd.maybe_decode(&outcome.1.result, Some(status));
+                    execution_data.borrow_mut().logs.extend(outcome.1.logs.clone());
+                    execution_data.borrow_mut().counterexample = outcome;
+                    // HACK: we have to use an empty string here to denote `None`.
+                    Err(TestCaseError::fail(reason.unwrap_or_default()))
+                }
+            }
+        });
+
+        (execution_data.into_inner(), run_result)
+    }
+
+    /// Runs fuzz cases as a time-budgeted campaign.
+    ///
+    /// Cases are executed on `campaign_workers` threads until the campaign budget is spent or a
+    /// counterexample is found. Each worker owns a clone of the executor and a test runner forked
+    /// from the configured one, while the fuzz dictionary and the corpus of inputs reaching new
+    /// execution fingerprints are shared between workers. Counterexamples are shrunk like the
+    /// ones found by the proptest runner.
+    #[allow(clippy::too_many_arguments)]
+    fn fuzz_campaign(
+        &self,
+        func: &Function,
+        fuzz_fixtures: &FuzzFixtures,
+        state: &EvmFuzzState,
+        address: Address,
+        should_fail: bool,
+        rd: &RevertDecoder,
+        max_traces_to_collect: usize,
+        progress: Option<&ProgressBar>,
+    ) -> (FuzzTestData, Result<(), TestError<Bytes>>, FuzzCampaignStats) {
+        let workers = self.config.campaign.workers();
+        let timer = FuzzTestTimer::new(self.config.campaign.campaign_budget);
+        let start = Instant::now();
+        let corpus = FuzzCorpus::new();
+        let runs = AtomicUsize::new(0);
+        let rejects = AtomicU32::new(0);
+        let stop = AtomicBool::new(false);
+        // The first failure of the campaign, with the counterexample that triggered it.
+        let failure = Mutex::new(None::<(TestError<Bytes>, (Bytes, RawCallResult))>);
+        let fail = |error: TestError<Bytes>, counterexample: (Bytes, RawCallResult)| {
+            failure.lock().get_or_insert((error, counterexample));
+            stop.store(true, Ordering::Relaxed);
+        };
+
+        let mut root_runner = self.runner.clone();
+        let runners = (0..workers)
+            .map(|_| TestRunner::new_with_rng(root_runner.config().clone(), root_runner.new_rng()))
+            .collect::<Vec<_>>();
+        let max_global_rejects = root_runner.config().max_global_rejects;
+
+        let worker_data = std::thread::scope(|scope| {
+            let handles = runners
+                .into_iter()
+                .map(|mut runner| {
+                    let (corpus, runs, rejects, stop, fail) =
+                        (&corpus, &runs, &rejects, &stop, &fail);
+                    let timer = &timer;
+                    scope.spawn(move || {
+                        let executor = self.executor.clone();
+                        let strategy = self.strategy(func, fuzz_fixtures, state);
+                        let mut data = FuzzTestData::default();
+                        while !stop.load(Ordering::Relaxed) && !timer.is_timed_out() {
+                            let mut tree = match strategy.new_tree(&mut runner) {
+                                Ok(tree) => tree,
+                                Err(reason) => {
+                                    fail(TestError::Abort(reason), Default::default());
+                                    break;
+                                }
+                            };
+                            let calldata = tree.current();
+
+                            match self.fuzz_call(&executor, address, should_fail, calldata.clone())
+                            {
+                                Ok(FuzzOutcome::Case(case)) => {
+                                    runs.fetch_add(1, Ordering::Relaxed);
+                                    if let Some(progress) = progress {
+                                        progress.inc(1);
+                                    }
+                                    corpus.record(
+                                        state,
+                                        func,
+                                        &case.case.calldata,
+                                        (&case.result, &case.logs),
+                                        self.config.dictionary.max_fuzz_dictionary_values as u32,
+                                    );
+                                    data.record_case(
+                                        case,
+                                        max_traces_to_collect,
+                                        self.config.show_logs,
+                                    );
+                                }
+                                Ok(FuzzOutcome::CounterExample(counterexample)) => {
+                                    runs.fetch_add(1, Ordering::Relaxed);
+                                    let CounterExampleOutcome {
+                                        exit_reason: status,
+                                        counterexample: outcome,
+                                        ..
+                                    } = self.shrink(
+                                        &executor,
+                                        runner.config(),
+                                        &mut tree,
+                                        address,
+                                        should_fail,
+                                        counterexample,
+                                    );
+                                    let reason = rd.maybe_decode(&outcome.1.result, Some(status));
+                                    data.logs.extend(outcome.1.logs.clone());
+                                    // HACK: we have to use an empty string here to denote `None`.
+                                    fail(
+                                        TestError::Fail(
+                                            reason.unwrap_or_default().into(),
+                                            outcome.0.clone(),
+                                        ),
+                                        outcome,
+                                    );
+                                    break;
+                                }
+                                Err(TestCaseError::Reject(_)) => {
+                                    if rejects.fetch_add(1, Ordering::Relaxed) + 1 >
+                                        max_global_rejects
+                                    {
+                                        fail(
+                                            TestError::Abort("Too many global rejects".into()),
+                                            Default::default(),
+                                        );
+                                        break;
+                                    }
+                                }
+                                Err(TestCaseError::Fail(reason)) => {
+                                    fail(TestError::Fail(reason, calldata), Default::default());
+                                    break;
+                                }
+                            }
+                        }
+                        data
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        let mut execution_data = FuzzTestData::default();
+        for data in worker_data {
+            execution_data.merge(data, max_traces_to_collect);
+        }
+
+        let run_result = match failure.into_inner() {
+            Some((error, counterexample)) => {
+                execution_data.counterexample = counterexample;
+                Err(error)
+            }
+            None => Ok(()),
+        };
+
+        let stats = FuzzCampaignStats {
+            workers,
+            runs: runs.into_inner(),
+            elapsed: start.elapsed(),
+            corpus_size: corpus.len(),
+        };
+        (execution_data, run_result, stats)
+    }
+
+    /// Shrinks the input of a failing campaign case, returning the outcome of the simplest input
+    /// of `tree` which still fails.
+    ///
+    /// Follows the shrinking of the proptest runner: the input is simplified while it fails, and
+    /// complicated back when it passes, for at most `max_shrink_iters` iterations.
+    fn shrink(
+        &self,
+        executor: &Executor,
+        config: &proptest::test_runner::Config,
+        tree: &mut impl ValueTree<Value = Bytes>,
+        address: Address,
+        should_fail: bool,
+        mut counterexample: CounterExampleOutcome,
+    ) -> CounterExampleOutcome {
+        let mut iterations = 0;
+        if !tree.simplify() {
+            return counterexample;
+        }
+        while iterations < config.max_shrink_iters {
+            iterations += 1;
+            let shrunk = match self.fuzz_call(executor, address, should_fail, tree.current()) {
+                Ok(FuzzOutcome::CounterExample(outcome)) => {
+                    counterexample = outcome;
+                    tree.simplify()
+                }
+                _ => tree.complicate(),
+            };
+            if !shrunk {
+                break;
+            }
+        }
+        counterexample
+    }
+
     /// Granular and single-step function that runs only one fuzz and returns either a `CaseOutcome`
     /// or a `CounterExampleOutcome`
     pub fn single_fuzz(
@@ -241,8 +522,18 @@ pub fn single_fuzz(
         should_fail: bool,
         calldata: alloy_primitives::Bytes,
     ) -> Result<FuzzOutcome, TestCaseError> {
-        let mut call = self
-            .executor
+        self.fuzz_call(&self.executor, address, should_fail, calldata)
+    }
+
+    /// Runs a single fuzz case on `executor`, see [`FuzzedExecutor::single_fuzz`].
+    fn fuzz_call(
+        &self,
+        executor: &Executor,
+        address: Address,
+        should_fail: bool,
+        calldata: Bytes,
+    ) -> Result<FuzzOutcome, TestCaseError> {
+        let mut call = executor
             .call_raw(self.sender, address, calldata.clone(), U256::ZERO)
             .map_err(|e| TestCaseError::fail(e.to_string()))?;
 
@@ -256,10 +547,11 @@ pub fn single_fuzz(
                 (cheats.breakpoints.clone(), cheats.deprecated.clone())
             });
 
-        let success = self.executor.is_raw_call_mut_success(address, &mut call, should_fail);
+        let success = executor.is_raw_call_mut_success(address, &mut call, should_fail);
         if success {
             Ok(FuzzOutcome::Case(CaseOutcome {
                 case: FuzzCase { calldata, gas: call.gas_used, stipend: call.stipend },
+                result: call.result,
                 traces: call.traces,
                 coverage: call.coverage,
                 breakpoints,
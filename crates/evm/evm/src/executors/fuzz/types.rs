@@ -11,6 +11,8 @@
 pub struct CaseOutcome {
     /// Data of a single fuzz test case.
     pub case: FuzzCase,
+    /// The return data of the call.
+    pub result: Bytes,
     /// The traces of the call.
     pub traces: Option<SparsedTraceArena>,
     /// The coverage info collected during the call.
@@ -1,6 +1,6 @@
 use crate::{
     executors::{Executor, RawCallResult},
-    inspectors::Fuzzer,
+    inspectors::{CmpLog, Fuzzer},
 };
 use alloy_primitives::{Address, Bytes, FixedBytes, Selector, U256};
 use alloy_sol_types::{sol, SolCall};
@@ -20,7 +20,7 @@
         RandomCallGenerator, SenderFilters, TargetedContract, TargetedContracts,
     },
     strategies::{invariant_strat, override_call_strat, EvmFuzzState},
-    FuzzCase, FuzzFixtures, FuzzedCases,
+    FuzzCampaignStats, FuzzCase, FuzzCorpus, FuzzFixtures, FuzzedCases,
 };
 use foundry_evm_traces::{CallTraceArena, SparsedTraceArena};
 use indicatif::ProgressBar;
@@ -35,7 +35,11 @@
 use std::{
     cell::RefCell,
     collections::{btree_map::Entry, HashMap as Map},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
 };
 
 mod error;
@@ -67,6 +71,13 @@ struct FuzzArtifactSelector {
             bytes4[] selectors;
         }
 
+        #[derive(Default)]
+        struct FuzzRole {
+            string name;
+            address[] senders;
+            FuzzSelector[] selectors;
+        }
+
         #[derive(Default)]
         struct FuzzInterface {
             address addr;
@@ -104,6 +115,9 @@ struct FuzzInterface {
 
         #[derive(Default)]
         function targetInterfaces() public view returns (FuzzInterface[] memory targetedInterfaces);
+
+        #[derive(Default)]
+        function targetRoles() public view returns (FuzzRole[] memory targetedRoles);
     }
 }
 
@@ -142,6 +156,34 @@ pub struct InvariantTestData {
     pub branch_runner: TestRunner,
 }
 
+impl InvariantTestData {
+    /// Merges the data collected by a campaign worker.
+    fn merge(&mut self, other: Self, gas_samples: usize) {
+        self.fuzz_cases.extend(other.fuzz_cases);
+        self.failures.reverts += other.failures.reverts;
+        if other.failures.revert_reason.is_some() {
+            self.failures.revert_reason = other.failures.revert_reason;
+        }
+        // The last run of the worker which broke the invariant is the one reproducing the failure.
+        if self.failures.error.is_none() && other.failures.error.is_some() {
+            self.failures.error = other.failures.error;
+            self.last_run_inputs = other.last_run_inputs;
+            self.last_call_results = other.last_call_results;
+        } else if self.failures.error.is_none() && self.last_run_inputs.is_empty() {
+            self.last_run_inputs = other.last_run_inputs;
+        }
+        let remaining = gas_samples.saturating_sub(self.gas_report_traces.len());
+        self.gas_report_traces.extend(other.gas_report_traces.into_iter().take(remaining));
+        HitMaps::merge_opt(&mut self.coverage, other.coverage);
+        for (key, metrics) in other.metrics {
+            let entry = self.metrics.entry(key).or_default();
+            entry.calls += metrics.calls;
+            entry.reverts += metrics.reverts;
+            entry.discards += metrics.discards;
+        }
+    }
+}
+
 /// Contains invariant test data.
 pub struct InvariantTest {
     // Fuzz state of invariant test.
@@ -178,6 +220,26 @@ pub fn new(
         Self { fuzz_state, targeted_contracts, execution_data }
     }
 
+    /// Creates an invariant test for a campaign worker, sharing the fuzz state of this test while
+    /// keeping its own copy of the targeted contracts and its own execution data.
+    pub fn fork(&self, branch_runner: TestRunner) -> Self {
+        let execution_data = RefCell::new(InvariantTestData {
+            fuzz_cases: vec![],
+            failures: InvariantFailures::new(),
+            last_run_inputs: vec![],
+            gas_report_traces: vec![],
+            last_call_results: None,
+            coverage: None,
+            metrics: Map::default(),
+            branch_runner,
+        });
+        Self {
+            fuzz_state: self.fuzz_state.clone(),
+            targeted_contracts: self.targeted_contracts.fork(),
+            execution_data,
+        }
+    }
+
     /// Returns number of invariant test reverts.
     pub fn reverts(&self) -> usize {
         self.execution_data.borrow().failures.reverts
@@ -331,173 +393,315 @@ pub fn invariant_fuzz(
             return Err(eyre!("Invariant test function should have no inputs"))
         }
 
-        let (invariant_test, invariant_strategy) =
+        let (invariant_test, targeted_senders) =
             self.prepare_test(&invariant_contract, fuzz_fixtures, deployed_libs)?;
 
-        // Start timer for this invariant test.
-        let timer = FuzzTestTimer::new(self.config.timeout);
-
-        let _ = self.runner.run(&invariant_strategy, |first_input| {
-            // Create current invariant run data.
-            let mut current_run = InvariantTestRun::new(
-                first_input,
-                // Before each run, we must reset the backend state.
-                self.executor.clone(),
-                self.config.depth as usize,
+        let (invariant_test, campaign) = if self.config.campaign.is_enabled() {
+            let (invariant_test, campaign) = self.invariant_campaign(
+                &invariant_contract,
+                invariant_test,
+                &targeted_senders,
+                fuzz_fixtures,
+                progress,
             );
+            (invariant_test, Some(campaign))
+        } else {
+            let invariant_strategy =
+                self.invariant_strategy(&invariant_test, targeted_senders, fuzz_fixtures);
 
-            // We stop the run immediately if we have reverted, and `fail_on_revert` is set.
-            if self.config.fail_on_revert && invariant_test.reverts() > 0 {
-                return Err(TestCaseError::fail("Revert occurred."))
-            }
+            // Start timer for this invariant test.
+            let timer = FuzzTestTimer::new(self.config.timeout);
 
-            while current_run.depth < self.config.depth {
-                // Check if the timeout has been reached.
-                if timer.is_timed_out() {
-                    // Since we never record a revert here the test is still considered
-                    // successful even though it timed out. We *want*
-                    // this behavior for now, so that's ok, but
-                    // future developers should be aware of this.
-                    return Err(TestCaseError::fail(TEST_TIMEOUT));
-                }
+            let _ = self.runner.clone().run(&invariant_strategy, |first_input| {
+                self.execute_run(
+                    &self.executor,
+                    &invariant_contract,
+                    &invariant_test,
+                    &invariant_strategy,
+                    &timer,
+                    first_input,
+                    None,
+                    progress,
+                )
+            });
+            (invariant_test, None)
+        };
 
-                let tx = current_run.inputs.last().ok_or_else(|| {
-                    TestCaseError::fail("No input generated to call fuzzed target.")
-                })?;
+        trace!(?fuzz_fixtures);
+        invariant_test.fuzz_state.log_stats();
 
-                // Execute call from the randomly generated sequence without committing state.
-                // State is committed only if call is not a magic assume.
-                let mut call_result = current_run
-                    .executor
-                    .call_raw(
-                        tx.sender,
-                        tx.call_details.target,
-                        tx.call_details.calldata.clone(),
-                        U256::ZERO,
-                    )
-                    .map_err(|e| {
-                        TestCaseError::fail(format!("Could not make raw evm call: {e}"))
-                    })?;
+        let result = invariant_test.execution_data.into_inner();
+        Ok(InvariantFuzzTestResult {
+            error: result.failures.error,
+            cases: result.fuzz_cases,
+            reverts: result.failures.reverts,
+            last_run_inputs: result.last_run_inputs,
+            gas_report_traces: result.gas_report_traces,
+            coverage: result.coverage,
+            metrics: result.metrics,
+            campaign,
+        })
+    }
 
-                let discarded = call_result.result.as_ref() == MAGIC_ASSUME;
-                if self.config.show_metrics {
-                    invariant_test.record_metrics(tx, call_result.reverted, discarded);
-                }
+    /// Executes a single invariant run, starting with `first_input` and generating the next calls
+    /// of the sequence from the evolving fuzz dictionary.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_run(
+        &self,
+        executor: &Executor,
+        invariant_contract: &InvariantContract<'_>,
+        invariant_test: &InvariantTest,
+        invariant_strategy: &impl Strategy<Value = BasicTxDetails>,
+        timer: &FuzzTestTimer,
+        first_input: BasicTxDetails,
+        corpus: Option<&FuzzCorpus>,
+        progress: Option<&ProgressBar>,
+    ) -> Result<(), TestCaseError> {
+        // Create current invariant run data.
+        let mut current_run = InvariantTestRun::new(
+            first_input,
+            // Before each run, we must reset the backend state.
+            executor.clone(),
+            self.config.depth as usize,
+        );
 
-                // Collect coverage from last fuzzed call.
-                invariant_test.merge_coverage(call_result.coverage.clone());
-
-                if discarded {
-                    current_run.inputs.pop();
-                    current_run.assume_rejects_counter += 1;
-                    if current_run.assume_rejects_counter > self.config.max_assume_rejects {
-                        invariant_test.set_error(InvariantFuzzError::MaxAssumeRejects(
-                            self.config.max_assume_rejects,
-                        ));
-                        return Err(TestCaseError::fail("Max number of vm.assume rejects reached."))
-                    }
-                } else {
-                    // Commit executed call result.
-                    current_run.executor.commit(&mut call_result);
-
-                    // Collect data for fuzzing from the state changeset.
-                    let mut state_changeset = call_result.state_changeset.clone();
-                    if !call_result.reverted {
-                        collect_data(
-                            &invariant_test,
-                            &mut state_changeset,
-                            tx,
-                            &call_result,
-                            self.config.depth,
-                        );
-                    }
+        // We stop the run immediately if we have reverted, and `fail_on_revert` is set.
+        if self.config.fail_on_revert && invariant_test.reverts() > 0 {
+            return Err(TestCaseError::fail("Revert occurred."))
+        }
 
-                    // Collect created contracts and add to fuzz targets only if targeted contracts
-                    // are updatable.
-                    if let Err(error) =
-                        &invariant_test.targeted_contracts.collect_created_contracts(
-                            &state_changeset,
-                            self.project_contracts,
-                            self.setup_contracts,
-                            &self.artifact_filters,
-                            &mut current_run.created_contracts,
-                        )
-                    {
-                        warn!(target: "forge::test", "{error}");
-                    }
-                    current_run.fuzz_runs.push(FuzzCase {
-                        calldata: tx.call_details.calldata.clone(),
-                        gas: call_result.gas_used,
-                        stipend: call_result.stipend,
-                    });
-
-                    // Determine if test can continue or should exit.
-                    let result = can_continue(
-                        &invariant_contract,
-                        &invariant_test,
-                        &mut current_run,
-                        &self.config,
-                        call_result,
-                        &state_changeset,
-                    )
-                    .map_err(|e| TestCaseError::fail(e.to_string()))?;
-                    if !result.can_continue || current_run.depth == self.config.depth - 1 {
-                        invariant_test.set_last_run_inputs(&current_run.inputs);
-                    }
-                    // If test cannot continue then stop current run and exit test suite.
-                    if !result.can_continue {
-                        return Err(TestCaseError::fail("Test cannot continue."))
-                    }
+        while current_run.depth < self.config.depth {
+            // Check if the timeout has been reached.
+            if timer.is_timed_out() {
+                // Since we never record a revert here the test is still considered
+                // successful even though it timed out. We *want*
+                // this behavior for now, so that's ok, but
+                // future developers should be aware of this.
+                return Err(TestCaseError::fail(TEST_TIMEOUT));
+            }
 
-                    invariant_test.set_last_call_results(result.call_result);
-                    current_run.depth += 1;
-                }
+            let tx = current_run
+                .inputs
+                .last()
+                .ok_or_else(|| TestCaseError::fail("No input generated to call fuzzed target."))?;
+
+            // Execute call from the randomly generated sequence without committing state.
+            // State is committed only if call is not a magic assume.
+            let mut call_result = current_run
+                .executor
+                .call_raw(
+                    tx.sender,
+                    tx.call_details.target,
+                    tx.call_details.calldata.clone(),
+                    U256::ZERO,
+                )
+                .map_err(|e| TestCaseError::fail(format!("Could not make raw evm call: {e}")))?;
 
-                // Generates the next call from the run using the recently updated
-                // dictionary.
-                current_run.inputs.push(
-                    invariant_strategy
-                        .new_tree(&mut invariant_test.execution_data.borrow_mut().branch_runner)
-                        .map_err(|_| TestCaseError::Fail("Could not generate case".into()))?
-                        .current(),
-                );
+            let discarded = call_result.result.as_ref() == MAGIC_ASSUME;
+            if self.config.show_metrics {
+                invariant_test.record_metrics(tx, call_result.reverted, discarded);
             }
 
-            // Call `afterInvariant` only if it is declared and test didn't fail already.
-            if invariant_contract.call_after_invariant && !invariant_test.has_errors() {
-                assert_after_invariant(
-                    &invariant_contract,
-                    &invariant_test,
-                    &current_run,
+            // Collect coverage from last fuzzed call.
+            invariant_test.merge_coverage(call_result.coverage.clone());
+
+            if discarded {
+                current_run.inputs.pop();
+                current_run.assume_rejects_counter += 1;
+                if current_run.assume_rejects_counter > self.config.max_assume_rejects {
+                    invariant_test.set_error(InvariantFuzzError::MaxAssumeRejects(
+                        self.config.max_assume_rejects,
+                    ));
+                    return Err(TestCaseError::fail("Max number of vm.assume rejects reached."))
+                }
+            } else {
+                // Commit executed call result.
+                current_run.executor.commit(&mut call_result);
+
+                // Collect data for fuzzing from the state changeset.
+                let mut state_changeset = call_result.state_changeset.clone();
+                if !call_result.reverted {
+                    collect_data(
+                        invariant_test,
+                        &mut state_changeset,
+                        tx,
+                        &call_result,
+                        self.config.depth,
+                    );
+                    if let Some(corpus) = corpus {
+                        record_corpus(invariant_test, corpus, tx, &call_result, self.config.depth);
+                    }
+                }
+
+                // Collect created contracts and add to fuzz targets only if targeted contracts
+                // are updatable.
+                if let Err(error) = &invariant_test.targeted_contracts.collect_created_contracts(
+                    &state_changeset,
+                    self.project_contracts,
+                    self.setup_contracts,
+                    &self.artifact_filters,
+                    &mut current_run.created_contracts,
+                ) {
+                    warn!(target: "forge::test", "{error}");
+                }
+                current_run.fuzz_runs.push(FuzzCase {
+                    calldata: tx.call_details.calldata.clone(),
+                    gas: call_result.gas_used,
+                    stipend: call_result.stipend,
+                });
+
+                // Determine if test can continue or should exit.
+                let result = can_continue(
+                    invariant_contract,
+                    invariant_test,
+                    &mut current_run,
                     &self.config,
+                    call_result,
+                    &state_changeset,
                 )
-                .map_err(|_| TestCaseError::Fail("Failed to call afterInvariant".into()))?;
+                .map_err(|e| TestCaseError::fail(e.to_string()))?;
+                if !result.can_continue || current_run.depth == self.config.depth - 1 {
+                    invariant_test.set_last_run_inputs(&current_run.inputs);
+                }
+                // If test cannot continue then stop current run and exit test suite.
+                if !result.can_continue {
+                    return Err(TestCaseError::fail("Test cannot continue."))
+                }
+
+                invariant_test.set_last_call_results(result.call_result);
+                current_run.depth += 1;
             }
 
-            // End current invariant test run.
-            invariant_test.end_run(current_run, self.config.gas_report_samples as usize);
+            // Generates the next call from the run using the recently updated
+            // dictionary.
+            current_run.inputs.push(
+                invariant_strategy
+                    .new_tree(&mut invariant_test.execution_data.borrow_mut().branch_runner)
+                    .map_err(|_| TestCaseError::Fail("Could not generate case".into()))?
+                    .current(),
+            );
+        }
+
+        // Call `afterInvariant` only if it is declared and test didn't fail already.
+        if invariant_contract.call_after_invariant && !invariant_test.has_errors() {
+            assert_after_invariant(invariant_contract, invariant_test, &current_run, &self.config)
+                .map_err(|_| TestCaseError::Fail("Failed to call afterInvariant".into()))?;
+        }
+
+        // End current invariant test run.
+        invariant_test.end_run(current_run, self.config.gas_report_samples as usize);
 
-            // If running with progress then increment completed runs.
-            if let Some(progress) = progress {
-                progress.inc(1);
-            }
+        // If running with progress then increment completed runs.
+        if let Some(progress) = progress {
+            progress.inc(1);
+        }
 
-            Ok(())
+        Ok(())
+    }
+
+    /// Runs invariant runs as a time-budgeted campaign.
+    ///
+    /// Runs are executed on `campaign_workers` threads until the campaign budget is spent or an
+    /// invariant is broken. Each worker runs on its own [`InvariantTest`] forked from
+    /// `invariant_test`. The fuzz dictionary and the corpus are shared, so values discovered by a
+    /// worker are used by all of them, while the targeted contracts and the executor are per
+    /// worker, as the contracts created by a run only exist in the state of its worker. Values
+    /// collected during a run are dropped from the shared dictionary whenever a run ends. The
+    /// worker results are merged once all workers are done.
+    fn invariant_campaign(
+        &self,
+        invariant_contract: &InvariantContract<'_>,
+        invariant_test: InvariantTest,
+        targeted_senders: &SenderFilters,
+        fuzz_fixtures: &FuzzFixtures,
+        progress: Option<&ProgressBar>,
+    ) -> (InvariantTest, FuzzCampaignStats) {
+        let workers = self.config.campaign.workers();
+        let timer = FuzzTestTimer::new(self.config.campaign.campaign_budget);
+        let start = Instant::now();
+        let corpus = FuzzCorpus::new();
+        let runs = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+
+        let mut root_runner = self.runner.clone();
+        let runners = (0..workers)
+            .map(|_| TestRunner::new_with_rng(root_runner.config().clone(), root_runner.new_rng()))
+            .collect::<Vec<_>>();
+        let worker_tests = (0..workers)
+            .map(|_| {
+                invariant_test.fork(TestRunner::new_with_rng(
+                    root_runner.config().clone(),
+                    root_runner.new_rng(),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let worker_data = std::thread::scope(|scope| {
+            let handles = runners
+                .into_iter()
+                .zip(worker_tests)
+                .map(|(mut runner, worker_test)| {
+                    let (corpus, runs, stop, timer) = (&corpus, &runs, &stop, &timer);
+                    scope.spawn(move || {
+                        let executor = self.fuzzing_executor(
+                            invariant_contract.address,
+                            &worker_test.fuzz_state,
+                            &worker_test.targeted_contracts,
+                            fuzz_fixtures,
+                        );
+                        let invariant_strategy = self.invariant_strategy(
+                            &worker_test,
+                            targeted_senders.clone(),
+                            fuzz_fixtures,
+                        );
+                        while !stop.load(Ordering::Relaxed) && !timer.is_timed_out() {
+                            let Ok(tree) = invariant_strategy.new_tree(&mut runner) else { break };
+                            match self.execute_run(
+                                &executor,
+                                invariant_contract,
+                                &worker_test,
+                                &invariant_strategy,
+                                timer,
+                                tree.current(),
+                                Some(corpus),
+                                progress,
+                            ) {
+                                Ok(()) => {
+                                    runs.fetch_add(1, Ordering::Relaxed);
+                                }
+                                Err(TestCaseError::Fail(reason))
+                                    if reason.message() == TEST_TIMEOUT =>
+                                {
+                                    break
+                                }
+                                Err(_) => {
+                                    runs.fetch_add(1, Ordering::Relaxed);
+                                    stop.store(true, Ordering::Relaxed);
+                                    break
+                                }
+                            }
+                        }
+                        worker_test.execution_data.into_inner()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
         });
 
-        trace!(?fuzz_fixtures);
-        invariant_test.fuzz_state.log_stats();
+        {
+            let mut execution_data = invariant_test.execution_data.borrow_mut();
+            for data in worker_data {
+                execution_data.merge(data, self.config.gas_report_samples as usize);
+            }
+        }
 
-        let result = invariant_test.execution_data.into_inner();
-        Ok(InvariantFuzzTestResult {
-            error: result.failures.error,
-            cases: result.fuzz_cases,
-            reverts: result.failures.reverts,
-            last_run_inputs: result.last_run_inputs,
-            gas_report_traces: result.gas_report_traces,
-            coverage: result.coverage,
-            metrics: result.metrics,
-        })
+        let stats = FuzzCampaignStats {
+            workers,
+            runs: runs.into_inner(),
+            elapsed: start.elapsed(),
+            corpus_size: corpus.len(),
+        };
+        (invariant_test, stats)
     }
 
     /// Prepares certain structures to execute the invariant tests:
@@ -508,7 +712,7 @@ fn prepare_test(
         invariant_contract: &InvariantContract<'_>,
         fuzz_fixtures: &FuzzFixtures,
         deployed_libs: &[Address],
-    ) -> Result<(InvariantTest, impl Strategy<Value = BasicTxDetails>)> {
+    ) -> Result<(InvariantTest, SenderFilters)> {
         // Finds out the chosen deployed contracts and/or senders.
         self.select_contract_artifacts(invariant_contract.address)?;
         let (targeted_senders, targeted_contracts) =
@@ -521,37 +725,12 @@ fn prepare_test(
             deployed_libs,
         );
 
-        // Creates the invariant strategy.
-        let strategy = invariant_strat(
-            fuzz_state.clone(),
-            targeted_senders,
-            targeted_contracts.clone(),
-            self.config.dictionary.dictionary_weight,
-            fuzz_fixtures.clone(),
-        )
-        .no_shrink();
-
-        // Allows `override_call_strat` to use the address given by the Fuzzer inspector during
-        // EVM execution.
-        let mut call_generator = None;
-        if self.config.call_override {
-            let target_contract_ref = Arc::new(RwLock::new(Address::ZERO));
-
-            call_generator = Some(RandomCallGenerator::new(
-                invariant_contract.address,
-                self.runner.clone(),
-                override_call_strat(
-                    fuzz_state.clone(),
-                    targeted_contracts.clone(),
-                    target_contract_ref.clone(),
-                    fuzz_fixtures.clone(),
-                ),
-                target_contract_ref,
-            ));
-        }
-
-        self.executor.inspector_mut().fuzzer =
-            Some(Fuzzer { call_generator, fuzz_state: fuzz_state.clone(), collect: true });
+        self.executor = self.fuzzing_executor(
+            invariant_contract.address,
+            &fuzz_state,
+            &targeted_contracts,
+            fuzz_fixtures,
+        );
 
         // Let's make sure the invariant is sound before actually starting the run:
         // We'll assert the invariant in its initial state, and if it fails, we'll
@@ -578,10 +757,65 @@ fn prepare_test(
                 last_call_results,
                 self.runner.clone(),
             ),
-            strategy,
+            targeted_senders,
         ))
     }
 
+    /// Creates the strategy generating the calls of the runs of `invariant_test`.
+    fn invariant_strategy(
+        &self,
+        invariant_test: &InvariantTest,
+        targeted_senders: SenderFilters,
+        fuzz_fixtures: &FuzzFixtures,
+    ) -> impl Strategy<Value = BasicTxDetails> {
+        invariant_strat(
+            invariant_test.fuzz_state.clone(),
+            targeted_senders,
+            invariant_test.targeted_contracts.clone(),
+            self.config.dictionary.dictionary_weight,
+            fuzz_fixtures.clone(),
+        )
+        .no_shrink()
+    }
+
+    /// Returns a copy of the executor whose inspectors collect into `fuzz_state` and override
+    /// calls with `targeted_contracts`.
+    fn fuzzing_executor(
+        &self,
+        invariant_address: Address,
+        fuzz_state: &EvmFuzzState,
+        targeted_contracts: &FuzzRunIdentifiedContracts,
+        fuzz_fixtures: &FuzzFixtures,
+    ) -> Executor {
+        let mut executor = self.executor.clone();
+
+        // Allows `override_call_strat` to use the address given by the Fuzzer inspector during
+        // EVM execution.
+        let mut call_generator = None;
+        if self.config.call_override {
+            let target_contract_ref = Arc::new(RwLock::new(Address::ZERO));
+
+            call_generator = Some(RandomCallGenerator::new(
+                invariant_address,
+                self.runner.clone(),
+                override_call_strat(
+                    fuzz_state.clone(),
+                    targeted_contracts.clone(),
+                    target_contract_ref.clone(),
+                    fuzz_fixtures.clone(),
+                ),
+                target_contract_ref,
+            ));
+        }
+
+        executor.inspector_mut().fuzzer =
+            Some(Fuzzer { call_generator, fuzz_state: fuzz_state.clone(), collect: true });
+        if self.config.dictionary.include_comparisons {
+            executor.inspector_mut().set_cmp_log(CmpLog::new(fuzz_state.clone()));
+        }
+        executor
+    }
+
     /// Fills the `InvariantExecutor` with the artifact identifier filters (in `path:name` string
     /// format). They will be used to filter contracts after the `setUp`, and more importantly,
     /// during the runs.
@@ -679,7 +913,8 @@ fn validate_selected_contract(
     }
 
     /// Selects senders and contracts based on the contract methods `targetSenders() -> address[]`,
-    /// `targetContracts() -> address[]` and `excludeContracts() -> address[]`.
+    /// `targetContracts() -> address[]`, `excludeContracts() -> address[]` and
+    /// `targetRoles() -> (string, address[], (address, bytes4[])[])[]`.
     pub fn select_contracts_and_senders(
         &self,
         to: Address,
@@ -700,7 +935,8 @@ pub fn select_contracts_and_senders(
         ]);
         // Extend with precompiles - https://github.com/foundry-rs/foundry/issues/4287
         excluded_senders.extend(PRECOMPILES);
-        let sender_filters = SenderFilters::new(targeted_senders, excluded_senders);
+        let mut sender_filters = SenderFilters::new(targeted_senders, excluded_senders);
+        self.select_roles(to, &mut sender_filters)?;
 
         let selected = self
             .executor
@@ -740,6 +976,22 @@ pub fn select_contracts_and_senders(
         Ok((sender_filters, FuzzRunIdentifiedContracts::new(contracts, selected.is_empty())))
     }
 
+    /// Restricts the senders of functions based on the contract method `targetRoles()`, which maps
+    /// role-specific senders (such as admin, user or liquidator) to the functions they can call.
+    pub fn select_roles(&self, address: Address, sender_filters: &mut SenderFilters) -> Result<()> {
+        let roles = self.executor.call_sol_default(address, &IInvariantTest::targetRolesCall {});
+        for IInvariantTest::FuzzRole { name, senders, selectors } in roles.targetedRoles {
+            let functions =
+                selectors.iter().flat_map(|IInvariantTest::FuzzSelector { addr, selectors }| {
+                    selectors.iter().map(|selector| (*addr, *selector))
+                });
+            if !sender_filters.add_role(&senders, functions) {
+                eyre::bail!("[targetRoles] role {name:?} has no allowed senders");
+            }
+        }
+        Ok(())
+    }
+
     /// Extends the contracts and selectors to fuzz with the addresses and ABIs specified in
     /// `targetInterfaces() -> (address, string[])[]`. Enables targeting of addresses that are
     /// not deployed during `setUp` such as when fuzzing in a forked environment. Also enables
@@ -892,6 +1144,28 @@ fn collect_data(
     }
 }
 
+/// Records the calldata of a fuzzed call in the campaign corpus if it reached an execution
+/// fingerprint not seen before by any worker.
+fn record_corpus(
+    invariant_test: &InvariantTest,
+    corpus: &FuzzCorpus,
+    tx: &BasicTxDetails,
+    call_result: &RawCallResult,
+    run_depth: u32,
+) {
+    // Release the targets lock before recording, the dictionary lock is always acquired first.
+    let func = invariant_test.targeted_contracts.targets.lock().fuzzed_artifacts(tx).1.cloned();
+    if let Some(func) = func {
+        corpus.record(
+            &invariant_test.fuzz_state,
+            &func,
+            &tx.call_details.calldata,
+            (tx.call_details.target, &call_result.result, &call_result.logs),
+            run_depth,
+        );
+    }
+}
+
 /// Calls the `afterInvariant()` function on a contract.
 /// Returns call result and if call succeeded.
 /// The state after the call is not persisted.
@@ -10,7 +10,7 @@
 use foundry_evm_coverage::HitMaps;
 use foundry_evm_fuzz::{
     invariant::{BasicTxDetails, FuzzRunIdentifiedContracts, InvariantContract},
-    FuzzedCases,
+    FuzzCampaignStats, FuzzedCases,
 };
 use revm_inspectors::tracing::CallTraceArena;
 use std::{borrow::Cow, collections::HashMap};
@@ -32,6 +32,8 @@ pub struct InvariantFuzzTestResult {
     pub coverage: Option<HitMaps>,
     /// Fuzzed selectors metrics collected during the invariant test runs.
     pub metrics: HashMap<String, InvariantMetrics>,
+    /// Statistics of the campaign, if the test ran as a time-budgeted campaign.
+    pub campaign: Option<FuzzCampaignStats>,
 }
 
 /// Enriched results of an invariant run check.
@@ -1,5 +1,5 @@
 use super::{
-    Cheatcodes, CheatsConfig, ChiselState, CoverageCollector, Fuzzer, LogCollector,
+    Cheatcodes, CheatsConfig, ChiselState, CmpLog, CoverageCollector, Fuzzer, LogCollector,
     TracingInspector,
 };
 use alloy_primitives::{map::AddressHashMap, Address, Bytes, Log, TxKind, U256};
@@ -41,6 +41,8 @@ pub struct InspectorStackBuilder {
     pub cheatcodes: Option<Arc<CheatsConfig>>,
     /// The fuzzer inspector and its state, if it exists.
     pub fuzzer: Option<Fuzzer>,
+    /// The comparison tracing inspector, if it exists.
+    pub cmp_log: Option<CmpLog>,
     /// Whether to enable tracing.
     pub trace_mode: TraceMode,
     /// Whether logs should be collected.
@@ -105,6 +107,13 @@ pub fn fuzzer(mut self, fuzzer: Fuzzer) -> Self {
         self
     }
 
+    /// Set the comparison tracing inspector.
+    #[inline]
+    pub fn cmp_log(mut self, cmp_log: CmpLog) -> Self {
+        self.cmp_log = Some(cmp_log);
+        self
+    }
+
     /// Set the Chisel inspector.
     #[inline]
     pub fn chisel_state(mut self, final_pc: usize) -> Self {
@@ -171,6 +180,7 @@ pub fn build(self) -> InspectorStack {
             gas_price,
             cheatcodes,
             fuzzer,
+            cmp_log,
             trace_mode,
             logs,
             coverage,
@@ -196,6 +206,9 @@ pub fn build(self) -> InspectorStack {
         if let Some(fuzzer) = fuzzer {
             stack.set_fuzzer(fuzzer);
         }
+        if let Some(cmp_log) = cmp_log {
+            stack.set_cmp_log(cmp_log);
+        }
         if let Some(chisel_state) = chisel_state {
             stack.set_chisel(chisel_state);
         }
@@ -287,6 +300,7 @@ pub struct InspectorStackInner {
     pub chisel_state: Option<ChiselState>,
     pub coverage: Option<CoverageCollector>,
     pub fuzzer: Option<Fuzzer>,
+    pub cmp_log: Option<CmpLog>,
     pub log_collector: Option<LogCollector>,
     pub printer: Option<CustomPrintTracer>,
     pub tracer: Option<TracingInspector>,
@@ -342,7 +356,16 @@ macro_rules! push {
                     )*
                 };
             }
-            push!(cheatcodes, chisel_state, coverage, fuzzer, log_collector, printer, tracer);
+            push!(
+                cheatcodes,
+                chisel_state,
+                coverage,
+                fuzzer,
+                cmp_log,
+                log_collector,
+                printer,
+                tracer
+            );
             if self.enable_isolation {
                 enabled.push("isolation");
             }
@@ -385,6 +408,12 @@ pub fn set_fuzzer(&mut self, fuzzer: Fuzzer) {
         self.fuzzer = Some(fuzzer);
     }
 
+    /// Set the comparison tracing inspector.
+    #[inline]
+    pub fn set_cmp_log(&mut self, cmp_log: CmpLog) {
+        self.cmp_log = Some(cmp_log);
+    }
+
     /// Set the Chisel inspector.
     #[inline]
     pub fn set_chisel(&mut self, final_pc: usize) {
@@ -502,7 +531,13 @@ fn do_call_end(
         let result = outcome.result.result;
         call_inspectors!(
             #[ret]
-            [&mut self.fuzzer, &mut self.tracer, &mut self.cheatcodes, &mut self.printer],
+            [
+                &mut self.cmp_log,
+                &mut self.fuzzer,
+                &mut self.tracer,
+                &mut self.cheatcodes,
+                &mut self.printer,
+            ],
             |inspector| {
                 let new_outcome = inspector.call_end(ecx, inputs, outcome.clone());
 
@@ -763,6 +798,7 @@ fn step(&mut self, interpreter: &mut Interpreter, ecx: &mut EvmContext<&mut dyn
         call_inspectors!(
             [
                 &mut self.fuzzer,
+                &mut self.cmp_log,
                 &mut self.tracer,
                 &mut self.coverage,
                 &mut self.cheatcodes,
@@ -811,7 +847,13 @@ fn call(
 
         call_inspectors!(
             #[ret]
-            [&mut self.fuzzer, &mut self.tracer, &mut self.log_collector, &mut self.printer],
+            [
+                &mut self.fuzzer,
+                &mut self.cmp_log,
+                &mut self.tracer,
+                &mut self.log_collector,
+                &mut self.printer,
+            ],
             |inspector| {
                 let mut out = None;
                 if let Some(output) = inspector.call(ecx, call) {
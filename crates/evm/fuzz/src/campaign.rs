@@ -0,0 +1,162 @@
+//! Types shared by the workers of a time-budgeted fuzz campaign.
+
+use crate::strategies::EvmFuzzState;
+use alloy_dyn_abi::JsonAbiExt;
+use alloy_json_abi::Function;
+use alloy_primitives::{
+    map::{DefaultHashBuilder, HashSet},
+    Bytes,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    hash::{BuildHasher, Hash},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Inputs that reached a previously unseen execution fingerprint during a fuzz campaign.
+///
+/// Wrapped in a shareable container, so every worker of a campaign records into the same corpus.
+/// The arguments of recorded inputs are fed back into the shared fuzz dictionary as sample values,
+/// which makes them available to all workers for the rest of the campaign.
+#[derive(Clone, Debug, Default)]
+pub struct FuzzCorpus {
+    inner: Arc<RwLock<CorpusInner>>,
+}
+
+#[derive(Debug, Default)]
+struct CorpusInner {
+    /// Hashes the fingerprints. Kept for the lifetime of the corpus, as its seed is random.
+    hasher: DefaultHashBuilder,
+    /// Fingerprints of the recorded inputs.
+    fingerprints: HashSet<u64>,
+    /// Recorded inputs, in insertion order.
+    inputs: Vec<Bytes>,
+}
+
+impl FuzzCorpus {
+    /// Creates a new, empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the calldata of a call to `func` if its execution `fingerprint` hasn't been seen
+    /// before, inserting the decoded arguments into the dictionary of `state`.
+    ///
+    /// Returns `true` if the input was added to the corpus.
+    pub fn record(
+        &self,
+        state: &EvmFuzzState,
+        func: &Function,
+        calldata: &Bytes,
+        fingerprint: impl Hash,
+        sample_limit: u32,
+    ) -> bool {
+        {
+            let mut inner = self.inner.write();
+            let fingerprint = inner.hasher.hash_one(fingerprint);
+            if !inner.fingerprints.insert(fingerprint) {
+                return false;
+            }
+            inner.inputs.push(calldata.clone());
+        }
+
+        if let Some(args) =
+            calldata.get(4..).and_then(|data| func.abi_decode_input(data, false).ok())
+        {
+            state.collect_sample_values(args, sample_limit);
+        }
+        true
+    }
+
+    /// Returns the number of recorded inputs.
+    pub fn len(&self) -> usize {
+        self.inner.read().inputs.len()
+    }
+
+    /// Returns `true` if no input has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the recorded inputs.
+    pub fn inputs(&self) -> Vec<Bytes> {
+        self.inner.read().inputs.clone()
+    }
+}
+
+/// Statistics of a time-budgeted fuzz campaign.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzCampaignStats {
+    /// Number of worker threads that executed the campaign.
+    pub workers: usize,
+    /// Number of runs executed by all workers.
+    pub runs: usize,
+    /// Wall-clock duration of the campaign.
+    pub elapsed: Duration,
+    /// Number of inputs in the shared corpus at the end of the campaign.
+    pub corpus_size: usize,
+}
+
+impl FuzzCampaignStats {
+    /// Returns the number of runs executed per second.
+    pub fn runs_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.runs as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+impl fmt::Display for FuzzCampaignStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "(workers: {}, runs: {}, elapsed: {:.2?}, runs/s: {:.0}, corpus: {})",
+            self.workers,
+            self.runs,
+            self.elapsed,
+            self.runs_per_sec(),
+            self.corpus_size
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_dyn_abi::DynSolValue;
+    use alloy_primitives::U256;
+    use revm::db::{CacheDB, EmptyDB};
+
+    #[test]
+    fn runs_per_sec() {
+        let stats = FuzzCampaignStats {
+            workers: 4,
+            runs: 1000,
+            elapsed: Duration::from_millis(500),
+            corpus_size: 0,
+        };
+        assert_eq!(stats.runs_per_sec(), 2000.0);
+        assert_eq!(FuzzCampaignStats::default().runs_per_sec(), 0.0);
+    }
+
+    #[test]
+    fn records_new_fingerprints() {
+        let state = EvmFuzzState::new(&CacheDB::new(EmptyDB::default()), Default::default(), &[]);
+        let func = Function::parse("f(uint256 x)").unwrap();
+        let calldata = |x: u64| -> Bytes {
+            func.abi_encode_input(&[DynSolValue::from(U256::from(x))]).unwrap().into()
+        };
+
+        let corpus = FuzzCorpus::new();
+        assert!(corpus.record(&state, &func, &calldata(1), "reverted", 10));
+        assert!(!corpus.record(&state, &func, &calldata(2), "reverted", 10));
+        assert!(corpus.record(&state, &func, &calldata(3), "returned", 10));
+        assert_eq!(corpus.inputs(), [calldata(1), calldata(3)]);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::strategies::EvmFuzzState;
+use alloy_primitives::{map::HashSet, B256, U256};
+use revm::{
+    interpreter::{opcode, CallInputs, CallOutcome, Interpreter},
+    Database, EvmContext, Inspector,
+};
+
+/// The maximum number of values recorded per top-level call, to avoid flooding the dictionary.
+const MAX_VALUES_PER_CALL: usize = 256;
+
+/// A CmpLog-style inspector that traces the operands of comparisons against fuzzed inputs.
+///
+/// Random inputs rarely satisfy equality checks on hashes or magic constants. When one operand of
+/// an `EQ`, `LT`, `GT`, `SLT`, `SGT` or `SUB` opcode is a word of the calldata of the top-level
+/// call, the other operand is recorded (along with its neighbours for ordering comparisons) and
+/// inserted in the fuzz dictionary once the call ends, so that next runs can draw it.
+#[derive(Clone, Debug)]
+pub struct CmpLog {
+    /// The fuzz dictionary recorded values are inserted into.
+    fuzz_state: EvmFuzzState,
+    /// Non-zero words of the calldata of the current top-level call.
+    inputs: HashSet<U256>,
+    /// Values compared against inputs during the current top-level call.
+    values: HashSet<B256>,
+}
+
+impl CmpLog {
+    /// Creates a new inspector recording into the dictionary of `fuzz_state`.
+    pub fn new(fuzz_state: EvmFuzzState) -> Self {
+        Self { fuzz_state, inputs: Default::default(), values: Default::default() }
+    }
+
+    /// Records the operand compared against an input.
+    fn record(&mut self, value: U256, ordering: bool) {
+        if self.values.len() >= MAX_VALUES_PER_CALL {
+            return;
+        }
+        self.values.insert(value.into());
+        if ordering {
+            self.values.insert(value.wrapping_add(U256::from(1)).into());
+            self.values.insert(value.wrapping_sub(U256::from(1)).into());
+        }
+    }
+
+    /// Inserts the recorded values into the fuzz dictionary.
+    fn flush(&mut self) {
+        if !self.values.is_empty() {
+            self.fuzz_state.collect_persistent_values(self.values.drain());
+        }
+        self.inputs.clear();
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CmpLog {
+    #[inline]
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if self.inputs.is_empty() {
+            return;
+        }
+
+        let ordering = match interp.current_opcode() {
+            opcode::EQ | opcode::SUB => false,
+            opcode::LT | opcode::GT | opcode::SLT | opcode::SGT => true,
+            _ => return,
+        };
+        let (Ok(a), Ok(b)) = (interp.stack().peek(0), interp.stack().peek(1)) else { return };
+        if a == b {
+            return;
+        }
+        if self.inputs.contains(&a) {
+            self.record(b, ordering);
+        }
+        if self.inputs.contains(&b) {
+            self.record(a, ordering);
+        }
+    }
+
+    #[inline]
+    fn call(&mut self, ecx: &mut EvmContext<DB>, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        if ecx.journaled_state.depth() == 0 {
+            self.flush();
+            if let Some(args) = inputs.input.get(4..) {
+                self.inputs = args
+                    .chunks(32)
+                    .map(U256::from_be_slice)
+                    .filter(|word| !word.is_zero())
+                    .collect();
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn call_end(
+        &mut self,
+        ecx: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        if ecx.journaled_state.depth() == 0 {
+            self.flush();
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_config::FuzzDictionaryConfig;
+    use revm::db::{CacheDB, EmptyDB};
+
+    #[test]
+    fn records_counterparts_of_inputs() {
+        let db = CacheDB::new(EmptyDB::default());
+        let state = EvmFuzzState::new(&db, FuzzDictionaryConfig::default(), &[]);
+        let mut cmplog = CmpLog::new(state.clone());
+        cmplog.inputs.insert(U256::from(42));
+
+        let magic = U256::from(0xdeadbeefu64);
+        cmplog.record(magic, true);
+        cmplog.flush();
+
+        let dictionary = state.dictionary_read();
+        for value in [magic, magic + U256::from(1), magic - U256::from(1)] {
+            assert!(dictionary.values().contains(&B256::from(value)));
+        }
+
+        // Values are persisted across runs.
+        drop(dictionary);
+        state.revert();
+        assert!(state.dictionary_read().values().contains(&B256::from(magic)));
+    }
+}
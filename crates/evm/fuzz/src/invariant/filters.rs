@@ -1,5 +1,5 @@
 use alloy_json_abi::{Function, JsonAbi};
-use alloy_primitives::{Address, Selector};
+use alloy_primitives::{map::HashMap, Address, Selector};
 use foundry_compilers::ArtifactId;
 use foundry_evm_core::utils::get_function;
 use std::collections::BTreeMap;
@@ -54,11 +54,16 @@ pub fn get_targeted_functions(
 /// Filter for acceptable senders to use for invariant testing. Exclusion takes priority if
 /// clashing.
 ///
+/// Functions can be restricted to the senders of one or more roles, in which case they are only
+/// called by these senders.
+///
 /// `address(0)` is excluded by default.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SenderFilters {
     pub targeted: Vec<Address>,
     pub excluded: Vec<Address>,
+    /// Senders of the functions restricted to roles, keyed by target address and selector.
+    pub roles: HashMap<(Address, Selector), Vec<Address>>,
 }
 
 impl SenderFilters {
@@ -68,6 +73,40 @@ pub fn new(mut targeted: Vec<Address>, mut excluded: Vec<Address>) -> Self {
             excluded.push(addr_0);
         }
         targeted.retain(|addr| !excluded.contains(addr));
-        Self { targeted, excluded }
+        Self { targeted, excluded, roles: HashMap::default() }
+    }
+
+    /// Restricts the given functions to the senders of a role. Functions belonging to multiple
+    /// roles can be called by the senders of any of them.
+    ///
+    /// Returns `false` if all the role senders are excluded.
+    pub fn add_role(
+        &mut self,
+        senders: &[Address],
+        functions: impl IntoIterator<Item = (Address, Selector)>,
+    ) -> bool {
+        let senders = senders
+            .iter()
+            .filter(|addr| !self.excluded.contains(addr))
+            .copied()
+            .collect::<Vec<_>>();
+        if senders.is_empty() {
+            return false
+        }
+        for function in functions {
+            let role_senders = self.roles.entry(function).or_default();
+            for sender in &senders {
+                if !role_senders.contains(sender) {
+                    role_senders.push(*sender);
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the senders allowed to call the `selector` function of `target`, if restricted to
+    /// roles.
+    pub fn role_senders(&self, target: Address, selector: Selector) -> Option<&[Address]> {
+        self.roles.get(&(target, selector)).map(Vec::as_slice)
     }
 }
@@ -30,6 +30,15 @@ pub fn new(targets: TargetedContracts, is_updatable: bool) -> Self {
         Self { targets: Arc::new(Mutex::new(targets)), is_updatable }
     }
 
+    /// Returns a copy whose targets are no longer shared with this collection, e.g. for a worker
+    /// of an invariant campaign.
+    pub fn fork(&self) -> Self {
+        Self {
+            targets: Arc::new(Mutex::new(self.targets.lock().clone())),
+            is_updatable: self.is_updatable,
+        }
+    }
+
     /// If targets are updatable, collect all contracts created during an invariant run (which
     /// haven't been discovered yet).
     pub fn collect_created_contracts(
@@ -8,10 +8,10 @@
 #[macro_use]
 extern crate tracing;
 
-use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_dyn_abi::{DynSolType, DynSolValue, JsonAbiExt};
 use alloy_primitives::{
     map::{AddressHashMap, HashMap},
-    Address, Bytes, Log,
+    Address, Bytes, Log, U256,
 };
 use foundry_common::{calc, contracts::ContractsByAddress, evm::Breakpoints};
 use foundry_evm_coverage::HitMaps;
@@ -22,6 +22,12 @@
 
 pub use proptest::test_runner::{Config as FuzzConfig, Reason};
 
+mod cmplog;
+pub use cmplog::CmpLog;
+
+mod campaign;
+pub use campaign::{FuzzCampaignStats, FuzzCorpus};
+
 mod error;
 pub use error::FuzzError;
 
@@ -189,6 +195,9 @@ pub struct FuzzTestResult {
 
     // Deprecated cheatcodes mapped to their replacements.
     pub deprecated_cheatcodes: HashMap<&'static str, Option<&'static str>>,
+
+    /// Statistics of the campaign, if the test ran as a time-budgeted campaign.
+    pub campaign: Option<FuzzCampaignStats>,
 }
 
 impl FuzzTestResult {
@@ -315,11 +324,18 @@ pub fn lowest_gas(&self) -> u64 {
 #[derive(Clone, Default, Debug)]
 pub struct FuzzFixtures {
     inner: Arc<HashMap<String, DynSolValue>>,
+    generators: Arc<HashMap<String, FuzzGenerator>>,
 }
 
 impl FuzzFixtures {
     pub fn new(fixtures: HashMap<String, DynSolValue>) -> Self {
-        Self { inner: Arc::new(fixtures) }
+        Self { inner: Arc::new(fixtures), generators: Default::default() }
+    }
+
+    /// Sets the generators declared in the test contract, keyed by fuzzed parameter name.
+    pub fn with_generators(mut self, generators: HashMap<String, FuzzGenerator>) -> Self {
+        self.generators = Arc::new(generators);
+        self
     }
 
     /// Returns configured fixtures for `param_name` fuzzed parameter.
@@ -330,6 +346,51 @@ pub fn param_fixtures(&self, param_name: &str) -> Option<&[DynSolValue]> {
             None
         }
     }
+
+    /// Returns the generator declared for `param_name` fuzzed parameter, if any.
+    pub fn param_generator(&self, param_name: &str) -> Option<&FuzzGenerator> {
+        self.generators.get(&normalize_fixture(param_name))
+    }
+}
+
+/// A user-defined generator of values for a fuzzed parameter.
+///
+/// Generators are declared in the test contract as functions prefixed with `gen` and followed by
+/// the parameter name, taking a single `uint256` seed and returning a value of the parameter type.
+/// For example, `function gen_amount(uint256 seed) public returns (uint256)` generates values for
+/// the `amount` parameter of fuzz tests.
+#[derive(Clone)]
+pub struct FuzzGenerator {
+    /// The type of the generated values.
+    ty: DynSolType,
+    /// Calls the generator with the given seed, returning `None` if the call reverted.
+    generate: Arc<dyn Fn(U256) -> Option<DynSolValue> + Send + Sync>,
+}
+
+impl fmt::Debug for FuzzGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FuzzGenerator").field("ty", &self.ty).finish_non_exhaustive()
+    }
+}
+
+impl FuzzGenerator {
+    /// Creates a new generator producing values of type `ty`.
+    pub fn new(
+        ty: DynSolType,
+        generate: impl Fn(U256) -> Option<DynSolValue> + Send + Sync + 'static,
+    ) -> Self {
+        Self { ty, generate: Arc::new(generate) }
+    }
+
+    /// Returns the type of the generated values.
+    pub fn ty(&self) -> &DynSolType {
+        &self.ty
+    }
+
+    /// Generates a value from the given seed.
+    pub fn generate(&self, seed: U256) -> Option<DynSolValue> {
+        (self.generate)(seed)
+    }
 }
 
 /// Extracts fixture name from a function name.
@@ -338,6 +399,12 @@ pub fn fixture_name(function_name: String) -> String {
     normalize_fixture(function_name.strip_prefix("fixture").unwrap())
 }
 
+/// Extracts generator name from a function name.
+/// For example: values generated by `gen_Owner` function will be used for `owner` parameter.
+pub fn generator_name(function_name: &str) -> String {
+    normalize_fixture(function_name.strip_prefix("gen").unwrap())
+}
+
 /// Normalize fixture parameter name, for example `_Owner` to `owner`.
 fn normalize_fixture(param_name: &str) -> String {
     param_name.trim_matches('_').to_ascii_lowercase()
@@ -1,5 +1,7 @@
 use crate::{
-    strategies::{fuzz_param_from_state, fuzz_param_with_fixtures, EvmFuzzState},
+    strategies::{
+        fuzz_param_from_state, fuzz_param_with_fixtures, fuzz_param_with_generator, EvmFuzzState,
+    },
     FuzzFixtures,
 };
 use alloy_dyn_abi::JsonAbiExt;
@@ -8,19 +10,22 @@
 use proptest::prelude::Strategy;
 
 /// Given a function, it returns a strategy which generates valid calldata
-/// for that function's input types, following declared test fixtures.
+/// for that function's input types, following declared test fixtures and generators.
 pub fn fuzz_calldata(func: Function, fuzz_fixtures: &FuzzFixtures) -> impl Strategy<Value = Bytes> {
     // We need to compose all the strategies generated for each parameter in all
-    // possible combinations, accounting any parameter declared fixture
+    // possible combinations, accounting any parameter declared fixture or generator
     let strats = func
         .inputs
         .iter()
         .map(|input| {
-            fuzz_param_with_fixtures(
-                &input.selector_type().parse().unwrap(),
-                fuzz_fixtures.param_fixtures(&input.name),
-                &input.name,
-            )
+            let param = input.selector_type().parse().unwrap();
+            let fixtures = fuzz_fixtures.param_fixtures(&input.name);
+            match fuzz_fixtures.param_generator(&input.name) {
+                Some(generator) => {
+                    fuzz_param_with_generator(&param, generator, fixtures, &input.name)
+                }
+                None => fuzz_param_with_fixtures(&param, fixtures, &input.name),
+            }
         })
         .collect::<Vec<_>>();
     strats.prop_map(move |values| {
@@ -37,14 +42,29 @@ pub fn fuzz_calldata(func: Function, fuzz_fixtures: &FuzzFixtures) -> impl Strat
 
 /// Given a function and some state, it returns a strategy which generated valid calldata for the
 /// given function's input types, based on state taken from the EVM.
+///
+/// Parameters with a declared generator are never taken from the state, so that their values
+/// always come from the generator.
 pub fn fuzz_calldata_from_state(
     func: Function,
     state: &EvmFuzzState,
+    fuzz_fixtures: &FuzzFixtures,
 ) -> impl Strategy<Value = Bytes> {
     let strats = func
         .inputs
         .iter()
-        .map(|input| fuzz_param_from_state(&input.selector_type().parse().unwrap(), state))
+        .map(|input| {
+            let param = input.selector_type().parse().unwrap();
+            match fuzz_fixtures.param_generator(&input.name) {
+                Some(generator) => fuzz_param_with_generator(
+                    &param,
+                    generator,
+                    fuzz_fixtures.param_fixtures(&input.name),
+                    &input.name,
+                ),
+                None => fuzz_param_from_state(&param, state),
+            }
+        })
         .collect::<Vec<_>>();
     strats
         .prop_map(move |values| {
@@ -62,11 +82,16 @@ pub fn fuzz_calldata_from_state(
 
 #[cfg(test)]
 mod tests {
-    use crate::{strategies::fuzz_calldata, FuzzFixtures};
-    use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+    use crate::{
+        strategies::{fuzz_calldata, fuzz_calldata_from_state, EvmFuzzState},
+        FuzzFixtures, FuzzGenerator,
+    };
+    use alloy_dyn_abi::{DynSolType, DynSolValue, JsonAbiExt};
     use alloy_json_abi::Function;
-    use alloy_primitives::{map::HashMap, Address};
-    use proptest::prelude::Strategy;
+    use alloy_primitives::{map::HashMap, Address, U256};
+    use foundry_config::FuzzDictionaryConfig;
+    use proptest::{prelude::Strategy, strategy::ValueTree, test_runner::TestRunner};
+    use revm::db::{CacheDB, EmptyDB};
 
     #[test]
     fn can_fuzz_with_fixtures() {
@@ -85,4 +110,32 @@ fn can_fuzz_with_fixtures() {
             assert_eq!(expected, fuzzed);
         });
     }
+
+    #[test]
+    fn can_fuzz_with_generators() {
+        let function = Function::parse("test_fuzzed_amount(uint256 _amount)").unwrap();
+
+        // Generate even amounts below 2000, rejecting odd seeds as a reverting generator would.
+        let generator = FuzzGenerator::new(DynSolType::Uint(256), |seed: U256| {
+            (!seed.bit(0)).then(|| DynSolValue::Uint(seed % U256::from(2000), 256))
+        });
+        let mut generators = HashMap::default();
+        generators.insert("amount".to_string(), generator);
+
+        let fixtures = FuzzFixtures::default().with_generators(generators);
+        let db = CacheDB::new(EmptyDB::default());
+        let state = EvmFuzzState::new(&db, FuzzDictionaryConfig::default(), &[]);
+        state.collect_values([U256::from(2001).into()]);
+        let strategy = proptest::prop_oneof![
+            fuzz_calldata(function.clone(), &fixtures),
+            fuzz_calldata_from_state(function.clone(), &state, &fixtures),
+        ];
+        let mut runner = TestRunner::default();
+        for _ in 0..32 {
+            let calldata = strategy.new_tree(&mut runner).unwrap().current();
+            let args = function.abi_decode_input(&calldata[4..], false).unwrap();
+            let amount = args[0].as_uint().unwrap().0;
+            assert!(amount < U256::from(2000) && !amount.bit(0));
+        }
+    }
 }
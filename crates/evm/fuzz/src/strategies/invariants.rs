@@ -5,11 +5,11 @@
     FuzzFixtures,
 };
 use alloy_json_abi::Function;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, Selector};
 use parking_lot::RwLock;
 use proptest::prelude::*;
 use rand::seq::IteratorRandom;
-use std::{rc::Rc, sync::Arc};
+use std::sync::Arc;
 
 /// Given a target address, we generate random calldata.
 pub fn override_call_strat(
@@ -55,21 +55,28 @@ pub fn override_call_strat(
 /// The fuzzed parameters can be filtered through different methods implemented in the test
 /// contract:
 ///
-/// `targetContracts()`, `targetSenders()`, `excludeContracts()`, `targetSelectors()`
+/// `targetContracts()`, `targetSenders()`, `excludeContracts()`, `targetSelectors()`,
+/// `targetRoles()`
 pub fn invariant_strat(
     fuzz_state: EvmFuzzState,
     senders: SenderFilters,
     contracts: FuzzRunIdentifiedContracts,
     dictionary_weight: u32,
     fuzz_fixtures: FuzzFixtures,
-) -> impl Strategy<Value = BasicTxDetails> {
-    let senders = Rc::new(senders);
+) -> impl Strategy<Value = BasicTxDetails> + Send + Sync {
+    let senders = Arc::new(senders);
     any::<prop::sample::Selector>()
         .prop_flat_map(move |selector| {
             let contracts = contracts.targets.lock();
             let functions = contracts.fuzzed_functions();
             let (target_address, target_function) = selector.select(functions);
-            let sender = select_random_sender(&fuzz_state, senders.clone(), dictionary_weight);
+            let sender = select_random_sender(
+                &fuzz_state,
+                senders.clone(),
+                dictionary_weight,
+                *target_address,
+                target_function.selector(),
+            );
             let call_details = fuzz_contract_with_calldata(
                 &fuzz_state,
                 &fuzz_fixtures,
@@ -81,15 +88,21 @@ pub fn invariant_strat(
         .prop_map(|(sender, call_details)| BasicTxDetails { sender, call_details })
 }
 
-/// Strategy to select a sender address:
+/// Strategy to select a sender address for the `selector` function of `target`:
+/// * If the function is restricted to roles, a random address is chosen from the role senders.
 /// * If `senders` is empty, then it's either a random address (10%) or from the dictionary (90%).
 /// * If `senders` is not empty, a random address is chosen from the list of senders.
 fn select_random_sender(
     fuzz_state: &EvmFuzzState,
-    senders: Rc<SenderFilters>,
+    senders: Arc<SenderFilters>,
     dictionary_weight: u32,
+    target: Address,
+    selector: Selector,
 ) -> impl Strategy<Value = Address> {
-    if !senders.targeted.is_empty() {
+    if let Some(role_senders) = senders.role_senders(target, selector) {
+        let role_senders = role_senders.to_vec();
+        any::<prop::sample::Index>().prop_map(move |index| *index.get(&role_senders)).boxed()
+    } else if !senders.targeted.is_empty() {
         any::<prop::sample::Index>().prop_map(move |index| *index.get(&senders.targeted)).boxed()
     } else {
         assert!(dictionary_weight <= 100, "dictionary_weight must be <= 100");
@@ -118,7 +131,7 @@ pub fn fuzz_contract_with_calldata(
     #[allow(clippy::arc_with_non_send_sync)]
     prop_oneof![
         60 => fuzz_calldata(func.clone(), fuzz_fixtures),
-        40 => fuzz_calldata_from_state(func, fuzz_state),
+        40 => fuzz_calldata_from_state(func, fuzz_state, fuzz_fixtures),
     ]
     .prop_map(move |calldata| {
         trace!(input=?calldata);
@@ -5,7 +5,9 @@
 pub use uint::UintStrategy;
 
 mod param;
-pub use param::{fuzz_param, fuzz_param_from_state, fuzz_param_with_fixtures};
+pub use param::{
+    fuzz_param, fuzz_param_from_state, fuzz_param_with_fixtures, fuzz_param_with_generator,
+};
 
 mod calldata;
 pub use calldata::{fuzz_calldata, fuzz_calldata_from_state};
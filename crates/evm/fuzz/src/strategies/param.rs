@@ -1,4 +1,5 @@
 use super::state::EvmFuzzState;
+use crate::FuzzGenerator;
 use alloy_dyn_abi::{DynSolType, DynSolValue};
 use alloy_primitives::{Address, B256, I256, U256};
 use proptest::prelude::*;
@@ -36,6 +37,33 @@ pub fn fuzz_param_with_fixtures(
     fuzz_param_inner(param, fixtures.map(|f| (f, name)))
 }
 
+/// Given a parameter type and a user-defined generator for param name, returns a strategy which
+/// generates values for that type by calling the generator with random seeds.
+///
+/// Generators are defined for parameter name, in a function with a
+/// `function gen_amount(uint256 seed) public returns (uint256)` signature, and allow producing
+/// domain-valid values such as sorted arrays or valid signatures. Seeds for which the generator
+/// reverts are rejected.
+///
+/// Falls back to [`fuzz_param_with_fixtures`] if the generated type doesn't match the input
+/// parameter.
+pub fn fuzz_param_with_generator(
+    param: &DynSolType,
+    generator: &FuzzGenerator,
+    fixtures: Option<&[DynSolValue]>,
+    name: &str,
+) -> BoxedStrategy<DynSolValue> {
+    if generator.ty() != param {
+        error!("generator for {name:?} returns {} instead of {param}", generator.ty());
+        return fuzz_param_with_fixtures(param, fixtures, name);
+    }
+
+    let generator = generator.clone();
+    any::<U256>()
+        .prop_filter_map("generator reverted", move |seed| generator.generate(seed))
+        .boxed()
+}
+
 fn fuzz_param_inner(
     param: &DynSolType,
     mut fuzz_fixtures: Option<(&[DynSolValue], &str)>,
@@ -232,7 +260,7 @@ fn can_fuzz_array() {
         let state = EvmFuzzState::new(&db, FuzzDictionaryConfig::default(), &[]);
         let strategy = proptest::prop_oneof![
             60 => fuzz_calldata(func.clone(), &FuzzFixtures::default()),
-            40 => fuzz_calldata_from_state(func, &state),
+            40 => fuzz_calldata_from_state(func, &state, &FuzzFixtures::default()),
         ];
         let cfg = proptest::test_runner::Config { failure_persistence: None, ..Default::default() };
         let mut runner = proptest::test_runner::TestRunner::new(cfg);
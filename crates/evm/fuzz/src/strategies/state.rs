@@ -54,6 +54,21 @@ pub fn collect_values(&self, values: impl IntoIterator<Item = B256>) {
         }
     }
 
+    /// Collects values that are kept across runs, see
+    /// [FuzzDictionary::insert_persistent_value].
+    pub fn collect_persistent_values(&self, values: impl IntoIterator<Item = B256>) {
+        let mut dict = self.inner.write();
+        for value in values {
+            dict.insert_persistent_value(value);
+        }
+    }
+
+    /// Collects sample values that are reused across runs, see
+    /// [FuzzDictionary::insert_sample_values].
+    pub fn collect_sample_values(&self, values: impl IntoIterator<Item = DynSolValue>, limit: u32) {
+        self.inner.write().insert_sample_values(values, limit);
+    }
+
     /// Collects state changes from a [StateChangeset] and logs into an [EvmFuzzState] according to
     /// the given [FuzzDictionaryConfig].
     pub fn collect_values_from_call(
@@ -103,7 +118,7 @@ pub struct FuzzDictionary {
     addresses: AIndexSet<Address>,
     /// Configuration for the dictionary.
     config: FuzzDictionaryConfig,
-    /// Number of state values initially collected from db.
+    /// Number of state values initially collected from db or persisted across runs.
     /// Used to revert new collected values at the end of each run.
     db_state_values: usize,
     /// Number of address values initially collected from db.
@@ -321,6 +336,20 @@ fn insert_value(&mut self, value: B256) {
         }
     }
 
+    /// Insert raw value into fuzz dictionary, keeping it at the end of current run.
+    /// If value was collected during current run then it is made persistent.
+    fn insert_persistent_value(&mut self, value: B256) {
+        if self.state_values.len() < self.config.max_fuzz_dictionary_values {
+            let (index, new_value) = self.state_values.insert_full(value);
+            let counter = if new_value { &mut self.misses } else { &mut self.hits };
+            *counter += 1;
+            if index >= self.db_state_values {
+                self.state_values.move_index(index, self.db_state_values);
+                self.db_state_values += 1;
+            }
+        }
+    }
+
     /// Insert sample values that are reused across multiple runs.
     /// The number of samples is limited to invariant run depth.
     /// If collected samples limit is reached then values are inserted as regular values.
@@ -1,8 +1,7 @@
-use super::eip712::Resolver;
 use clap::{Parser, ValueHint};
 use eyre::Result;
 use foundry_cli::{opts::BuildOpts, utils::LoadConfig};
-use foundry_common::{compile::with_compilation_reporter, fs};
+use foundry_common::{compile::with_compilation_reporter, eip712::Resolver, fs};
 use foundry_compilers::{
     artifacts::{
         output_selection::OutputSelection, ContractDefinitionPart, Source, SourceUnit,
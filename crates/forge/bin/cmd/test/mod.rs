@@ -567,6 +567,11 @@ pub async fn run_tests(
                 if !silent {
                     sh_println!("{}", result.short_result(name))?;
 
+                    // Display campaign statistics if the test ran as a fuzz campaign.
+                    if let Some(campaign) = &result.campaign {
+                        sh_println!("  Campaign {campaign}")?;
+                    }
+
                     // Display invariant metrics if invariant kind.
                     if let TestKind::Invariant { metrics, .. } = &result.kind {
                         print_invariant_metrics(metrics);
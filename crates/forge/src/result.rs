@@ -14,7 +14,7 @@
     coverage::HitMaps,
     decode::SkipReason,
     executors::{invariant::InvariantMetrics, RawCallResult},
-    fuzz::{CounterExample, FuzzCase, FuzzFixtures, FuzzTestResult},
+    fuzz::{CounterExample, FuzzCampaignStats, FuzzCase, FuzzFixtures, FuzzTestResult},
     traces::{CallTraceArena, CallTraceDecoder, TraceKind, Traces},
 };
 use serde::{Deserialize, Serialize};
@@ -420,6 +420,10 @@ pub struct TestResult {
     /// Deprecated cheatcodes (mapped to their replacements, if any) used in current test.
     #[serde(skip)]
     pub deprecated_cheatcodes: HashMap<&'static str, Option<&'static str>>,
+
+    /// Statistics of the fuzz campaign, if the test ran as a time-budgeted campaign.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub campaign: Option<FuzzCampaignStats>,
 }
 
 impl fmt::Display for TestResult {
@@ -568,6 +572,7 @@ pub fn fuzz_result(&mut self, result: FuzzTestResult) {
         self.gas_report_traces = result.gas_report_traces.into_iter().map(|t| vec![t]).collect();
         self.breakpoints = result.breakpoints.unwrap_or_default();
         self.deprecated_cheatcodes = result.deprecated_cheatcodes;
+        self.campaign = result.campaign;
     }
 
     /// Returns the skipped result for invariant test.
@@ -615,6 +620,7 @@ pub fn invariant_result(
         cases: Vec<FuzzedCases>,
         reverts: usize,
         metrics: Map<String, InvariantMetrics>,
+        campaign: Option<FuzzCampaignStats>,
     ) {
         self.kind = TestKind::Invariant {
             runs: cases.len(),
@@ -629,6 +635,7 @@ pub fn invariant_result(
         self.reason = reason;
         self.counterexample = counterexample;
         self.gas_report_traces = gas_report_traces;
+        self.campaign = campaign;
     }
 
     /// Returns `true` if this is the result of a fuzz test
@@ -8,7 +8,7 @@
     MultiContractRunner,
     TestFilter,
 };
-use alloy_dyn_abi::DynSolValue;
+use alloy_dyn_abi::{ DynSolType, DynSolValue };
 use alloy_json_abi::Function;
 use alloy_primitives::{ address, map::HashMap, Address, U256 };
 use alloy_provider::Provider;
@@ -26,7 +26,7 @@
     constants::CALLER,
     decode::RevertDecoder,
     executors::{
-        fuzz::FuzzedExecutor,
+        fuzz::{ DifferentialTargets, FuzzedExecutor },
         invariant::{
             check_sequence,
             replay_error,
@@ -42,9 +42,11 @@
     },
     fuzz::{
         fixture_name,
+        generator_name,
         invariant::{ CallDetails, InvariantContract },
         CounterExample,
         FuzzFixtures,
+        FuzzGenerator,
     },
     traces::{ load_contracts, TraceKind, TraceMode },
 };
@@ -252,6 +254,11 @@ fn inline_config(&self, func: Option<&Function>) -> Result<Config> {
     /// `function fixture_owner() public returns (address[] memory){}`
     /// returns an array of addresses to be used for fuzzing `owner` named parameter in scope of the
     /// current test.
+    ///
+    /// Generators can be defined as functions prefixed with `gen`, followed by parameter name and
+    /// taking a single `uint256` seed:
+    /// `function gen_amount(uint256 seed) public returns (uint256)`
+    /// is called with random seeds to produce values for fuzzing `amount` named parameter.
     fn fuzz_fixtures(&mut self, address: Address) -> FuzzFixtures {
         let mut fixtures = HashMap::default();
         let fixture_functions = self.contract.abi.functions().filter(|func| func.is_fixture());
@@ -297,7 +304,44 @@ fn fuzz_fixtures(&mut self, address: Address) -> FuzzFixtures {
                 fixtures.insert(fixture_name(func.name.clone()), DynSolValue::Array(vals));
             }
         }
-        FuzzFixtures::new(fixtures)
+        FuzzFixtures::new(fixtures).with_generators(self.fuzz_generators(address))
+    }
+
+    /// Collect generators from test contract.
+    ///
+    /// Generators are called on a snapshot of the executor taken after `setUp`, so they can rely
+    /// on deployed state but state changes made while generating values are discarded.
+    fn fuzz_generators(&self, address: Address) -> HashMap<String, FuzzGenerator> {
+        let mut generators = HashMap::default();
+        let mut executor = None;
+        for func in self.contract.abi.functions().filter(|func| func.is_generator()) {
+            let ([input], [output]) = (&func.inputs[..], &func.outputs[..]) else {
+                warn!("generator {} must take a single seed and return a single value", func.name);
+                continue;
+            };
+            if input.ty != "uint256" {
+                warn!("generator {} must take a uint256 seed", func.name);
+                continue;
+            }
+            let Ok(ty) = output.selector_type().parse::<DynSolType>() else {
+                warn!("generator {} returns an unsupported type", func.name);
+                continue;
+            };
+
+            let executor = Arc::clone(
+                executor.get_or_insert_with(|| Arc::new(self.executor.clone()))
+            );
+            let name = generator_name(&func.name);
+            let func = func.clone();
+            let generator = FuzzGenerator::new(ty, move |seed| {
+                executor
+                    .call(CALLER, address, &func, &[DynSolValue::Uint(seed, 256)], U256::ZERO, None)
+                    .ok()
+                    .map(|CallResult { decoded_result, .. }| decoded_result)
+            });
+            generators.insert(name, generator);
+        }
+        generators
     }
 
     /// Runs all tests for a contract whose names match the provided regular expression
@@ -368,12 +412,16 @@ pub fn run_tests(mut self, filter: &dyn TestFilter) -> SuiteResult {
             match_sig
         });
 
-        // Invariant testing requires tracing to figure out what contracts were created.
-        // We also want to disable `debug` for setup since we won't be using those traces.
+        // Invariant and differential testing require tracing to figure out what contracts were
+        // created. We also want to disable `debug` for setup since we won't be using those traces.
         let has_invariants = self.contract.abi.functions().any(|func| func.is_invariant_test());
+        let has_differentials = self.contract.abi
+            .functions()
+            .any(|func| func.is_differential_test());
+        let identify_contracts = has_invariants || has_differentials;
 
         let prev_tracer = self.executor.inspector_mut().tracer.take();
-        if prev_tracer.is_some() || has_invariants {
+        if prev_tracer.is_some() || identify_contracts {
             self.executor.set_tracing(TraceMode::Call);
         }
 
@@ -406,7 +454,7 @@ pub fn run_tests(mut self, filter: &dyn TestFilter) -> SuiteResult {
             find_timer.elapsed()
         );
 
-        let identified_contracts = has_invariants.then(|| {
+        let identified_contracts = identify_contracts.then(|| {
             load_contracts(
                 setup.traces.iter().map(|(_, t)| &t.arena),
                 &self.mcr.known_contracts
@@ -541,6 +589,9 @@ fn run(
             TestFunctionKind::InvariantTest => {
                 self.run_invariant_test(func, call_after_invariant, identified_contracts.unwrap())
             }
+            TestFunctionKind::DifferentialTest => {
+                self.run_differential_test(func, identified_contracts.unwrap())
+            }
             TestFunctionKind::IterateBlocks => todo!(),
             TestFunctionKind::IterateEventLogs => todo!(),
             _ => unreachable!(),
@@ -796,7 +847,8 @@ fn run_invariant_test(
             counterexample,
             invariant_result.cases,
             invariant_result.reverts,
-            invariant_result.metrics
+            invariant_result.metrics,
+            invariant_result.campaign
         );
         self.result
     }
@@ -827,7 +879,7 @@ fn run_fuzz_test(mut self, func: &Function, should_fail: bool) -> TestResult {
         );
 
         // Run fuzz test.
-        let fuzzed_executor = FuzzedExecutor::new(
+        let mut fuzzed_executor = FuzzedExecutor::new(
             self.executor.into_owned(),
             runner,
             self.tcfg.sender,
@@ -846,6 +898,101 @@ fn run_fuzz_test(mut self, func: &Function, should_fail: bool) -> TestResult {
         self.result
     }
 
+    /// Runs a differential test.
+    ///
+    /// The test function returns the addresses of a reference and a candidate implementation,
+    /// which are fuzzed side by side with the functions of the reference implementation that the
+    /// candidate also exposes. Fails on the first diverging input, after shrinking it.
+    fn run_differential_test(
+        mut self,
+        func: &Function,
+        identified_contracts: &ContractsByAddress
+    ) -> TestResult {
+        // Prepare differential test execution.
+        if self.prepare_test(func).is_err() {
+            return self.result;
+        }
+
+        let targets = match self.differential_targets(func, identified_contracts) {
+            Ok(targets) => targets,
+            Err(err) => {
+                match err.downcast::<EvmError>() {
+                    Ok(EvmError::Skip(reason)) => self.result.single_skip(reason),
+                    Ok(err) => self.result.single_fail(Some(err.to_string())),
+                    Err(err) => self.result.single_fail(Some(err.to_string())),
+                }
+                return self.result;
+            }
+        };
+
+        let runner = self.differential_runner();
+        let fuzz_config = self.config.fuzz.clone();
+
+        let progress = start_fuzz_progress(
+            self.cr.progress,
+            self.cr.name,
+            &func.name,
+            fuzz_config.runs
+        );
+
+        // Run differential test.
+        let fuzzed_executor = FuzzedExecutor::new(
+            self.executor.into_owned(),
+            runner,
+            self.tcfg.sender,
+            fuzz_config
+        );
+        let result = fuzzed_executor.fuzz_differential(
+            &targets,
+            &self.setup.fuzz_fixtures,
+            &self.setup.deployed_libs,
+            progress.as_ref()
+        );
+        self.result.fuzz_result(result);
+        self.result
+    }
+
+    /// Calls the differential test function and returns the implementations to compare.
+    ///
+    /// Implementations must be deployed in `setUp` to be identified.
+    fn differential_targets(
+        &self,
+        func: &Function,
+        identified_contracts: &ContractsByAddress
+    ) -> Result<DifferentialTargets> {
+        let CallResult { decoded_result, .. } = self.executor.call(
+            self.sender,
+            self.address,
+            func,
+            &[],
+            U256::ZERO,
+            Some(self.revert_decoder())
+        )?;
+        let Some(&[DynSolValue::Address(reference), DynSolValue::Address(candidate)]) =
+            decoded_result.as_tuple() else {
+            eyre::bail!("differential test must return (address reference, address candidate)");
+        };
+
+        let Some((name, abi)) = identified_contracts.get(&reference) else {
+            eyre::bail!("could not identify reference implementation at {reference}");
+        };
+        let candidate_abi = identified_contracts.get(&candidate).map(|(_, abi)| abi);
+        let functions = abi
+            .functions()
+            .filter(|func| {
+                candidate_abi.is_none_or(|abi| {
+                    abi.functions().any(|f| f.selector() == func.selector())
+                })
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if functions.is_empty() {
+            eyre::bail!("no functions shared by reference and candidate implementations");
+        }
+
+        Ok(DifferentialTargets { reference, candidate, name: Some(name.clone()), functions })
+    }
+
     fn run_iterate_block(mut self, func: &Function) -> TestResult {
         if self.prepare_test(func).is_err() {
             return self.result;
@@ -1050,6 +1197,16 @@ fn fuzz_runner(&self) -> TestRunner {
         )
     }
 
+    fn differential_runner(&self) -> TestRunner {
+        // Unlike fuzz tests, differential tests rely on proptest to shrink diverging inputs.
+        let mut runner = self.fuzz_runner();
+        let config = proptest::test_runner::Config {
+            max_shrink_iters: proptest::test_runner::Config::default().max_shrink_iters,
+            ..runner.config().clone()
+        };
+        TestRunner::new_with_rng(config, runner.new_rng())
+    }
+
     fn invariant_runner(&self) -> TestRunner {
         let config = &self.config.invariant;
         fuzzer_with_cases(self.config.fuzz.seed, config.runs, config.max_assume_rejects, None)
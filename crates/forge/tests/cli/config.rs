@@ -136,6 +136,7 @@
         allow_paths: vec![],
         include_paths: vec![],
         rpc_endpoints: Default::default(),
+        db_endpoints: Default::default(),
         build_info: false,
         build_info_path: None,
         fmt: Default::default(),
@@ -278,3 +278,179 @@ macro_rules! run_fail {
 
 "#]]);
 });
+
+// Tests that fuzz campaigns keep running until the budget is spent and report their statistics.
+forgetest_init!(test_fuzz_campaign, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "Contract.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract FuzzCampaignTest is Test {
+    /// forge-config: default.fuzz.campaign-budget = 1
+    /// forge-config: default.fuzz.campaign-workers = 2
+    function test_fuzz_campaign(uint256 a) public pure {
+        assertTrue(a != 0 || a == 0);
+    }
+
+    /// forge-config: default.fuzz.campaign-budget = 10
+    /// forge-config: default.fuzz.campaign-workers = 2
+    function test_fuzz_campaign_fail(uint256 a) public pure {
+        require(a < type(uint256).max / 2);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+[PASS] test_fuzz_campaign(uint256) (runs: [..], [AVG_GAS])
+  Campaign (workers: 2, runs: [..], elapsed: [..], runs/s: [..], corpus: [..])
+[FAIL: EvmError: Revert; counterexample: [..]] test_fuzz_campaign_fail(uint256) (runs: [..], [AVG_GAS])
+  Campaign (workers: 2, runs: [..], elapsed: [..], runs/s: [..], corpus: [..])
+...
+"#]]);
+});
+
+// Tests that fuzzed parameters are produced by the generators declared in the test contract.
+forgetest_init!(test_fuzz_generators, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "Contract.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract FuzzGeneratorsTest is Test {
+    function gen_amount(uint256 seed) public pure returns (uint256) {
+        return seed % 100 * 2;
+    }
+
+    function gen_values(uint256 seed) public pure returns (uint256[] memory values) {
+        values = new uint256[](seed % 8);
+        for (uint256 i; i < values.length; i++) {
+            values[i] = i * (seed >> 8 & 0xff);
+        }
+    }
+
+    function test_fuzz_generated_amount(uint256 amount) public pure {
+        assertLt(amount, 200);
+        assertEq(amount % 2, 0);
+    }
+
+    function test_fuzz_generated_sorted(uint256[] memory values) public pure {
+        for (uint256 i = 1; i < values.length; i++) {
+            assertLe(values[i - 1], values[i]);
+        }
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_success().stdout_eq(str![[r#"
+...
+[PASS] test_fuzz_generated_amount(uint256) (runs: 256, [AVG_GAS])
+[PASS] test_fuzz_generated_sorted(uint256[]) (runs: 256, [AVG_GAS])
+...
+"#]]);
+});
+
+// Tests that differential tests fuzz two implementations and report the shrunk diverging input.
+forgetest_init!(test_fuzz_differential, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "Contract.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract Math {
+    uint256 public last;
+
+    function average(uint256 a, uint256 b) public returns (uint256) {
+        last = (a & b) + (a ^ b) / 2;
+        return last;
+    }
+}
+
+contract MathOpt {
+    uint256 public last;
+
+    function average(uint256 a, uint256 b) public returns (uint256 r) {
+        assembly {
+            r := add(and(a, b), shr(1, xor(a, b)))
+            sstore(0, r)
+        }
+    }
+}
+
+contract MathNaive {
+    uint256 public last;
+
+    function average(uint256 a, uint256 b) public returns (uint256) {
+        last = (a + b) / 2;
+        return last;
+    }
+}
+
+contract DifferentialTest is Test {
+    Math math;
+    MathOpt mathOpt;
+    MathNaive mathNaive;
+
+    function setUp() public {
+        math = new Math();
+        mathOpt = new MathOpt();
+        mathNaive = new MathNaive();
+    }
+
+    function differential_opt() public view returns (address, address) {
+        return (address(math), address(mathOpt));
+    }
+
+    function differential_naive() public view returns (address, address) {
+        return (address(math), address(mathNaive));
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: revert status diverged: reference succeeded, candidate reverted; counterexample: [Math][..] calldata=average(uint256,uint256) args=[..]] differential_naive() (runs: [..], [AVG_GAS])
+[PASS] differential_opt() (runs: 256, [AVG_GAS])
+...
+"#]]);
+});
+
+// Tests that values compared against fuzzed inputs are fed back into the dictionary.
+forgetest_init!(test_fuzz_include_comparisons, |prj, cmd| {
+    prj.wipe_contracts();
+
+    prj.add_test(
+        "Contract.t.sol",
+        r#"
+import {Test} from "forge-std/Test.sol";
+
+contract FuzzComparisonsTest is Test {
+    /// forge-config: default.fuzz.runs = 5000
+    /// forge-config: default.fuzz.include-comparisons = true
+    function test_fuzz_runtime_hash(uint256 x) public view {
+        assertTrue(x != uint256(keccak256(abi.encode(block.timestamp))));
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.args(["test"]).assert_failure().stdout_eq(str![[r#"
+...
+[FAIL: assertion failed; counterexample: [..]] test_fuzz_runtime_hash(uint256) (runs: [..], [AVG_GAS])
+...
+"#]]);
+});
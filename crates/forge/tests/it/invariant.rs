@@ -657,6 +657,23 @@ async fn test_invariant_excluded_senders() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invariant_target_roles() {
+    // Reverts of calls made by senders outside of the selected function role fail the test.
+    let filter = Filter::new(".*", ".*", ".*fuzz/invariant/target/TargetRoles.t.sol");
+    let mut runner = TEST_DATA_DEFAULT.runner_with(|config| {
+        config.invariant.fail_on_revert = true;
+    });
+    let results = runner.test_collect(&filter);
+    assert_multiple(
+        &results,
+        BTreeMap::from([(
+            "default/fuzz/invariant/target/TargetRoles.t.sol:TargetRoles",
+            vec![("invariantOnlyUsersDeposit()", true, None, None, None)],
+        )]),
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_invariant_after_invariant() {
     // Check failure on passing invariant and failed `afterInvariant` condition
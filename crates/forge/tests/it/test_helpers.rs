@@ -10,8 +10,9 @@
     Project, ProjectCompileOutput, SolcConfig, Vyper,
 };
 use foundry_config::{
-    fs_permissions::PathPermission, Config, FsPermissions, FuzzConfig, FuzzDictionaryConfig,
-    InvariantConfig, RpcEndpointUrl, RpcEndpoints,
+    fs_permissions::PathPermission, Config, DbEndpoint, DbEndpoints, FsPermissions,
+    FuzzCampaignConfig, FuzzConfig, FuzzDictionaryConfig, InvariantConfig, RpcEndpointUrl,
+    RpcEndpoints,
 };
 use foundry_evm::{constants::CALLER, opts::EvmOpts};
 use foundry_test_utils::{fd_lock, init_tracing, rpc::next_rpc_endpoint};
@@ -115,6 +116,7 @@ pub fn config(&self) -> Config {
             dictionary: FuzzDictionaryConfig {
                 include_storage: true,
                 include_push_bytes: true,
+                include_comparisons: false,
                 dictionary_weight: 40,
                 max_fuzz_dictionary_addresses: 10_000,
                 max_fuzz_dictionary_values: 10_000,
@@ -124,6 +126,8 @@ pub fn config(&self) -> Config {
             failure_persist_file: Some("testfailure".to_string()),
             show_logs: false,
             timeout: None,
+            differential_storage: true,
+            campaign: FuzzCampaignConfig::default(),
         };
         config.invariant = InvariantConfig {
             runs: 256,
@@ -134,6 +138,7 @@ pub fn config(&self) -> Config {
                 dictionary_weight: 80,
                 include_storage: true,
                 include_push_bytes: true,
+                include_comparisons: false,
                 max_fuzz_dictionary_addresses: 10_000,
                 max_fuzz_dictionary_values: 10_000,
             },
@@ -149,6 +154,7 @@ pub fn config(&self) -> Config {
             ),
             show_metrics: false,
             timeout: None,
+            campaign: FuzzCampaignConfig::default(),
         };
 
         config.sanitized()
@@ -200,6 +206,7 @@ pub fn runner_with(&self, modify: impl FnOnce(&mut Config)) -> MultiContractRunn
 
     fn runner_with_config(&self, mut config: Config) -> MultiContractRunner {
         config.rpc_endpoints = rpc_endpoints();
+        config.db_endpoints = db_endpoints();
         config.allow_paths.push(manifest_root().to_path_buf());
 
         if config.fs_permissions.is_empty() {
@@ -354,6 +361,42 @@ pub fn rpc_endpoints() -> RpcEndpoints {
     ])
 }
 
+/// the database endpoints used during tests
+pub fn db_endpoints() -> DbEndpoints {
+    let endpoint = "sqlite:fixtures/Database/endpoint.db";
+    DbEndpoints::new([
+        ("memory", DbEndpoint::new("sqlite::memory:")),
+        (
+            "isolated",
+            DbEndpoint {
+                url: "sqlite::memory:".into(),
+                isolate: true,
+                fixtures: vec![
+                    "fixtures/Database/schema.sql".into(),
+                    "fixtures/Database/accounts.csv".into(),
+                ],
+                ..Default::default()
+            },
+        ),
+        (
+            "writable",
+            DbEndpoint {
+                url: endpoint.into(),
+                writers: Some(vec!["Sqlite*Test".into()]),
+                ..Default::default()
+            },
+        ),
+        (
+            "readonly",
+            DbEndpoint {
+                url: endpoint.into(),
+                writers: Some(vec!["Deploy*".into()]),
+                ..Default::default()
+            },
+        ),
+    ])
+}
+
 fn config_evm_opts(config: &Config) -> EvmOpts {
     config.to_figment(foundry_config::FigmentProviders::None).extract().unwrap()
 }
@@ -1,6 +1,10 @@
 use crate::transaction::TransactionWithMetadata;
 use alloy_network::AnyTransactionReceipt;
-use alloy_primitives::{hex, map::HashMap, TxHash};
+use alloy_primitives::{
+    hex,
+    map::{AddressHashMap, HashMap},
+    TxHash,
+};
 use eyre::{ContextCompat, Result, WrapErr};
 use foundry_common::{fs, shell, TransactionMaybeSigned, SELECTOR_LEN};
 use foundry_compilers::ArtifactId;
@@ -34,6 +38,9 @@ pub struct ScriptSequence {
     /// None if sequence should not be saved to disk (e.g. part of a multi-chain sequence)
     pub paths: Option<(PathBuf, PathBuf)>,
     pub returns: HashMap<String, NestedValue>,
+    /// Labels of the addresses of the script, set with `vm.label`.
+    #[serde(default, skip_serializing_if = "AddressHashMap::is_empty")]
+    pub labels: AddressHashMap<String>,
     pub timestamp: u64,
     pub chain: u64,
     pub commit: Option<String>,
@@ -0,0 +1,366 @@
+use crate::{broadcast::BundledState, verify::BroadcastedState, ScriptArgs};
+use alloy_chains::Chain;
+use alloy_primitives::{Address, TxHash};
+use eyre::{Context, Result};
+use forge_script_sequence::ScriptSequence;
+use foundry_cheatcodes::{connect_db_endpoint, DatabaseClient, SqlValue};
+use foundry_config::{Config, ResolvedDbEndpoint};
+use itertools::Itertools;
+use revm_inspectors::tracing::types::CallKind;
+use uuid::Uuid;
+
+/// The tables the broadcast results are recorded into, created if they don't exist.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS broadcast_chains (
+    chain_id BIGINT PRIMARY KEY,
+    name TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS broadcast_transactions (
+    chain_id BIGINT NOT NULL,
+    hash TEXT NOT NULL,
+    tx_index BIGINT NOT NULL,
+    kind TEXT NOT NULL,
+    sender TEXT,
+    recipient TEXT,
+    nonce BIGINT,
+    contract_name TEXT,
+    contract_address TEXT,
+    signature TEXT,
+    arguments TEXT,
+    commit_hash TEXT,
+    PRIMARY KEY (chain_id, hash)
+);
+CREATE TABLE IF NOT EXISTS broadcast_receipts (
+    chain_id BIGINT NOT NULL,
+    hash TEXT NOT NULL,
+    block_number BIGINT,
+    block_hash TEXT,
+    status BOOLEAN NOT NULL,
+    gas_used BIGINT NOT NULL,
+    effective_gas_price NUMERIC NOT NULL,
+    contract_address TEXT,
+    PRIMARY KEY (chain_id, hash)
+);
+CREATE TABLE IF NOT EXISTS broadcast_contracts (
+    chain_id BIGINT NOT NULL,
+    address TEXT NOT NULL,
+    contract_name TEXT,
+    hash TEXT NOT NULL,
+    PRIMARY KEY (chain_id, address)
+);
+CREATE TABLE IF NOT EXISTS broadcast_labels (
+    chain_id BIGINT NOT NULL,
+    address TEXT NOT NULL,
+    label TEXT NOT NULL,
+    PRIMARY KEY (chain_id, address)
+);
+";
+
+impl BundledState {
+    /// Checks that the broadcast results can be recorded into the `--broadcast-db` database, if
+    /// any, before broadcasting.
+    pub fn broadcast_db_preflight_check(&self) -> Result<()> {
+        let contract = &self.build_data.build_data.target.name;
+        broadcast_db_endpoint(&self.args, &self.script_config.config, contract).map(drop)
+    }
+}
+
+impl BroadcastedState {
+    /// Records the broadcast results into the `--broadcast-db` database, if any.
+    pub fn record(&self) -> Result<()> {
+        let config = &self.script_config.config;
+        let contract = &self.build_data.build_data.target.name;
+        let Some((alias, endpoint)) = broadcast_db_endpoint(&self.args, config, contract)? else {
+            return Ok(())
+        };
+        let client = connect_db_endpoint(&endpoint, alias, &config.root, false)
+            .map_err(|err| eyre::eyre!("failed to connect to `{alias}`: {err}"))?;
+        for sequence in self.sequence.sequences() {
+            record_sequence(&*client, sequence).wrap_err_with(|| {
+                format!("failed to record the broadcast on chain {} into `{alias}`", sequence.chain)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the alias and the endpoint of the `--broadcast-db` database, if any.
+///
+/// Fails if the endpoint is isolated, as its changes are never committed, or if the script
+/// contract is not one of its writers.
+fn broadcast_db_endpoint<'a>(
+    args: &'a ScriptArgs,
+    config: &Config,
+    contract: &str,
+) -> Result<Option<(&'a str, ResolvedDbEndpoint)>> {
+    let Some(alias) = &args.broadcast_db else { return Ok(None) };
+    let Some(endpoint) = config.db_endpoints.get(alias) else {
+        eyre::bail!("database endpoint `{alias}` not found in `[db_endpoints]`")
+    };
+    let endpoint = endpoint.clone().resolve();
+    if endpoint.isolate {
+        eyre::bail!(
+            "database endpoint `{alias}` is isolated, so broadcasts can't be recorded into it"
+        )
+    }
+    if !endpoint.is_writer(contract) {
+        eyre::bail!("`{contract}` is not one of the `writers` of database endpoint `{alias}`")
+    }
+    Ok(Some((alias, endpoint)))
+}
+
+/// Records the sent transactions, receipts, deployed contracts and labels of the sequence.
+///
+/// Rows are upserted by chain and transaction hash, or address, so that recording a resumed
+/// sequence again updates the rows of the previous run.
+pub fn record_sequence(client: &dyn DatabaseClient, sequence: &ScriptSequence) -> Result<()> {
+    client.batch_execute(SCHEMA)?;
+    let id = client.build_transaction()?;
+    match upsert_sequence(client, id, sequence) {
+        Ok(()) => Ok(client.commit(id)?),
+        Err(err) => {
+            let _ = client.rollback(id);
+            Err(err)
+        }
+    }
+}
+
+fn upsert_sequence(client: &dyn DatabaseClient, id: Uuid, sequence: &ScriptSequence) -> Result<()> {
+    let chain_id = SqlValue::Integer(sequence.chain as i64);
+    upsert(
+        client,
+        id,
+        "broadcast_chains",
+        &[("chain_id", chain_id.clone())],
+        &[("name", SqlValue::Text(Chain::from(sequence.chain).to_string()))],
+    )?;
+
+    for (index, tx) in sequence.transactions.iter().enumerate() {
+        let Some(hash) = tx.hash else { continue };
+        let key = [("chain_id", chain_id.clone()), ("hash", hash_value(hash))];
+        let arguments = tx.arguments.as_ref().map(serde_json::to_string).transpose()?;
+        upsert(
+            client,
+            id,
+            "broadcast_transactions",
+            &key,
+            &[
+                ("tx_index", SqlValue::Integer(index as i64)),
+                ("kind", SqlValue::Text(tx.opcode.to_string())),
+                ("sender", opt_address(tx.transaction.from())),
+                ("recipient", opt_address(tx.transaction.to().and_then(|to| to.to().copied()))),
+                (
+                    "nonce",
+                    tx.transaction.nonce().map_or(SqlValue::Null, |n| SqlValue::Integer(n as i64)),
+                ),
+                ("contract_name", opt_text(tx.contract_name.clone())),
+                ("contract_address", opt_address(tx.contract_address)),
+                ("signature", opt_text(tx.function.clone())),
+                ("arguments", opt_text(arguments)),
+                ("commit_hash", opt_text(sequence.commit.clone())),
+            ],
+        )?;
+
+        let created = matches!(tx.opcode, CallKind::Create | CallKind::Create2)
+            .then_some(tx.contract_address)
+            .flatten()
+            .map(|address| (address, tx.contract_name.clone()));
+        let additional = tx.additional_contracts.iter().map(|contract| (contract.address, None));
+        for (address, name) in created.into_iter().chain(additional) {
+            upsert(
+                client,
+                id,
+                "broadcast_contracts",
+                &[("chain_id", chain_id.clone()), ("address", address_value(address))],
+                &[("contract_name", opt_text(name)), ("hash", hash_value(hash))],
+            )?;
+        }
+    }
+
+    for receipt in &sequence.receipts {
+        let status = receipt.inner.inner.inner.receipt.status.coerce_status();
+        upsert(
+            client,
+            id,
+            "broadcast_receipts",
+            &[("chain_id", chain_id.clone()), ("hash", hash_value(receipt.transaction_hash))],
+            &[
+                (
+                    "block_number",
+                    receipt.block_number.map_or(SqlValue::Null, |n| SqlValue::Integer(n as i64)),
+                ),
+                ("block_hash", receipt.block_hash.map_or(SqlValue::Null, hash_value)),
+                ("status", SqlValue::Bool(status)),
+                ("gas_used", SqlValue::Integer(receipt.gas_used as i64)),
+                ("effective_gas_price", SqlValue::Numeric(receipt.effective_gas_price.to_string())),
+                ("contract_address", opt_address(receipt.contract_address)),
+            ],
+        )?;
+    }
+
+    for (address, label) in &sequence.labels {
+        upsert(
+            client,
+            id,
+            "broadcast_labels",
+            &[("chain_id", chain_id.clone()), ("address", address_value(*address))],
+            &[("label", SqlValue::Text(label.clone()))],
+        )?;
+    }
+    Ok(())
+}
+
+/// Inserts a row, or updates the columns of the row with the same key.
+fn upsert(
+    client: &dyn DatabaseClient,
+    id: Uuid,
+    table: &str,
+    key: &[(&str, SqlValue)],
+    columns: &[(&str, SqlValue)],
+) -> Result<()> {
+    let names = key.iter().chain(columns).map(|(name, _)| *name).join(", ");
+    let placeholders = (1..=key.len() + columns.len()).map(|i| format!("${i}")).join(", ");
+    let key_names = key.iter().map(|(name, _)| *name).join(", ");
+    let updates = columns.iter().map(|(name, _)| format!("{name} = excluded.{name}")).join(", ");
+    let query = format!(
+        "INSERT INTO {table} ({names}) VALUES ({placeholders}) \
+         ON CONFLICT ({key_names}) DO UPDATE SET {updates}"
+    );
+    let params = key.iter().chain(columns).map(|(_, value)| value.clone()).collect::<Vec<_>>();
+    client.execute_in_transaction(id, &query, &params)?;
+    Ok(())
+}
+
+fn hash_value(hash: TxHash) -> SqlValue {
+    SqlValue::Text(hash.to_string())
+}
+
+fn address_value(address: Address) -> SqlValue {
+    SqlValue::Text(address.to_checksum(None))
+}
+
+fn opt_address(address: Option<Address>) -> SqlValue {
+    address.map_or(SqlValue::Null, address_value)
+}
+
+fn opt_text(text: Option<String>) -> SqlValue {
+    text.filter(|text| !text.is_empty()).map_or(SqlValue::Null, SqlValue::Text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use alloy_rpc_types::TransactionRequest;
+    use alloy_serde::WithOtherFields;
+    use forge_script_sequence::TransactionWithMetadata;
+    use foundry_cheatcodes::SqliteClient;
+    use foundry_common::TransactionMaybeSigned;
+    use foundry_config::{DbEndpoint, DbEndpoints};
+    use std::path::Path;
+
+    fn count(client: &dyn DatabaseClient, table: &str) -> usize {
+        client.query(&format!("SELECT * FROM {table}"), &[]).unwrap().len()
+    }
+
+    #[test]
+    fn records_sequences() {
+        let sender = address!("f39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+        let deployed = address!("5fbdb2315678afecb367f032d93f642f64180aa3");
+        let hash = TxHash::repeat_byte(1);
+
+        let mut tx = TransactionWithMetadata::from_tx_request(TransactionMaybeSigned::new(
+            WithOtherFields::new(TransactionRequest::default().from(sender).nonce(0)),
+        ));
+        tx.hash = Some(hash);
+        tx.opcode = CallKind::Create;
+        tx.contract_name = Some("Counter".to_string());
+        tx.contract_address = Some(deployed);
+        let mut unsent = tx.clone();
+        unsent.hash = None;
+
+        let receipt = serde_json::json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x1",
+            "logs": [],
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": hash,
+            "transactionIndex": "0x0",
+            "blockHash": TxHash::repeat_byte(2),
+            "blockNumber": "0x1",
+            "gasUsed": "0x5208",
+            "effectiveGasPrice": "0x3b9aca00",
+            "from": sender,
+            "to": null,
+            "contractAddress": deployed,
+        });
+
+        let mut sequence = ScriptSequence {
+            chain: 31337,
+            transactions: [tx, unsent].into(),
+            receipts: vec![serde_json::from_value(receipt).unwrap()],
+            ..Default::default()
+        };
+        sequence.labels.insert(deployed, "counter".to_string());
+
+        let client = SqliteClient::open(Path::new(":memory:"), false).unwrap();
+        // Recording the sequence again, as when resuming a script, updates the rows.
+        for _ in 0..2 {
+            record_sequence(&client, &sequence).unwrap();
+        }
+
+        assert_eq!(count(&client, "broadcast_chains"), 1);
+        assert_eq!(count(&client, "broadcast_transactions"), 1);
+        assert_eq!(count(&client, "broadcast_receipts"), 1);
+        assert_eq!(count(&client, "broadcast_labels"), 1);
+        let contracts = client
+            .query("SELECT address, contract_name, hash FROM broadcast_contracts", &[])
+            .unwrap();
+        assert_eq!(contracts.len(), 1);
+        assert_eq!(contracts[0].get("address").unwrap(), &address_value(deployed));
+        assert_eq!(contracts[0].get("contract_name").unwrap(), &SqlValue::Text("Counter".into()));
+        assert_eq!(contracts[0].get("hash").unwrap(), &hash_value(hash));
+    }
+
+    #[test]
+    fn checks_broadcast_db_endpoint() {
+        let config = Config {
+            db_endpoints: DbEndpoints::new([
+                (
+                    "deployments",
+                    DbEndpoint {
+                        writers: Some(vec!["Deploy*".to_string()]),
+                        ..DbEndpoint::new("sqlite::memory:")
+                    },
+                ),
+                ("test", DbEndpoint { isolate: true, ..DbEndpoint::new("sqlite::memory:") }),
+            ]),
+            ..Default::default()
+        };
+        let args = |alias: &str| ScriptArgs {
+            broadcast_db: Some(alias.to_string()),
+            ..Default::default()
+        };
+
+        assert!(broadcast_db_endpoint(&ScriptArgs::default(), &config, "Deploy")
+            .unwrap()
+            .is_none());
+        let (_, endpoint) =
+            broadcast_db_endpoint(&args("deployments"), &config, "DeployCounter").unwrap().unwrap();
+        assert_eq!(endpoint.url().unwrap(), "sqlite::memory:");
+
+        let err = broadcast_db_endpoint(&args("deployments"), &config, "Upgrade").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Upgrade` is not one of the `writers` of database endpoint `deployments`"
+        );
+        let err = broadcast_db_endpoint(&args("test"), &config, "DeployCounter").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "database endpoint `test` is isolated, so broadcasts can't be recorded into it"
+        );
+        let err = broadcast_db_endpoint(&args("missing"), &config, "DeployCounter").unwrap_err();
+        assert_eq!(err.to_string(), "database endpoint `missing` not found in `[db_endpoints]`");
+    }
+}
@@ -7,7 +7,7 @@
 use alloy_dyn_abi::FunctionExt;
 use alloy_json_abi::{Function, InternalType, JsonAbi};
 use alloy_primitives::{
-    map::{HashMap, HashSet},
+    map::{AddressHashMap, HashMap, HashSet},
     Address, Bytes,
 };
 use alloy_provider::Provider;
@@ -265,6 +265,8 @@ pub struct ExecutionArtifacts {
     pub decoder: CallTraceDecoder,
     /// Return values from the execution result.
     pub returns: HashMap<String, NestedValue>,
+    /// Labels of the addresses of the script.
+    pub labels: AddressHashMap<String>,
     /// Information about RPC endpoints used during script execution.
     pub rpc_data: RpcData,
 }
@@ -308,6 +310,8 @@ pub async fn prepare_simulation(self) -> Result<PreSimulationState> {
         }
         rpc_data.check_shanghai_support().await?;
 
+        let labels = self.execution_result.labeled_addresses.clone();
+
         Ok(PreSimulationState {
             args: self.args,
             script_config: self.script_config,
@@ -315,7 +319,7 @@ pub async fn prepare_simulation(self) -> Result<PreSimulationState> {
             build_data: self.build_data,
             execution_data: self.execution_data,
             execution_result: self.execution_result,
-            execution_artifacts: ExecutionArtifacts { decoder, returns, rpc_data },
+            execution_artifacts: ExecutionArtifacts { decoder, returns, labels, rpc_data },
         })
     }
 
@@ -60,6 +60,7 @@
 
 mod broadcast;
 mod build;
+mod database;
 mod execute;
 mod multi_sequence;
 mod progress;
@@ -186,6 +187,13 @@ pub struct ScriptArgs {
     #[arg(long)]
     pub verify: bool,
 
+    /// Records the broadcast transactions, receipts, deployed contracts and labels into the
+    /// `[db_endpoints]` database with the given alias.
+    ///
+    /// Rows are upserted, so resuming the script doesn't duplicate them.
+    #[arg(long, value_name = "ALIAS")]
+    pub broadcast_db: Option<String>,
+
     /// Gas price for legacy transactions, or max fee per gas for EIP1559 transactions, either
     /// specified in wei, or as a string with a unit type.
     ///
@@ -313,9 +321,17 @@ pub async fn run_script(self) -> Result<()> {
             bundled.verify_preflight_check()?;
         }
 
+        // Exit early if the broadcast can't be recorded into the database.
+        bundled.broadcast_db_preflight_check()?;
+
         // Wait for pending txes and broadcast others.
         let broadcasted = bundled.wait_for_pending().await?.broadcast().await?;
 
+        // The transactions are already sent, so failing to record them must not skip verification.
+        if let Err(err) = broadcasted.record() {
+            sh_warn!("{err:#}")?;
+        }
+
         if broadcasted.args.verify {
             broadcasted.verify().await?;
         }
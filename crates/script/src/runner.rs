@@ -39,6 +39,12 @@ pub fn setup(
     ) -> Result<(Address, ScriptResult)> {
         trace!(target: "script", "executing setUP()");
 
+        // Scripts don't run in the isolation scopes of the database cheatcodes, which are for
+        // tests.
+        if let Some(cheatcodes) = self.executor.inspector_mut().cheatcodes.as_mut() {
+            cheatcodes.db_scoped = true;
+        }
+
         if !is_broadcast {
             if self.evm_opts.sender == Config::DEFAULT_SENDER {
                 // We max out their balance so that they can deploy and make calls.
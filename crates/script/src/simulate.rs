@@ -443,6 +443,7 @@ fn create_sequence(
         let sequence = ScriptSequence {
             transactions,
             returns: self.execution_artifacts.returns.clone(),
+            labels: self.execution_artifacts.labels.clone(),
             receipts: vec![],
             pending: vec![],
             paths,
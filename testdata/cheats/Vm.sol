@@ -13,6 +13,7 @@ interface Vm {
     struct Log { bytes32[] topics; bytes data; address emitter; }
     struct Rpc { string key; string url; }
     struct EthGetLogs { address emitter; bytes32[] topics; bytes data; bytes32 blockHash; uint64 blockNumber; bytes32 transactionHash; uint64 transactionIndex; uint256 logIndex; bool removed; }
+    struct EthDecodedLog { address emitter; bytes args; bytes32 blockHash; uint64 blockNumber; bytes32 transactionHash; uint64 transactionIndex; uint256 logIndex; bool removed; }
     struct DirEntry { string errorMessage; string path; uint64 depth; bool isDir; bool isSymlink; }
     struct FsMetadata { bool isDir; bool isSymlink; uint256 length; bool readOnly; uint256 modified; uint256 accessed; uint256 created; }
     struct Wallet { address addr; uint256 publicKeyX; uint256 publicKeyY; uint256 privateKey; }
@@ -24,6 +25,10 @@ interface Vm {
     struct DebugStep { uint256[] stack; bytes memoryInput; uint8 opcode; uint64 depth; bool isOutOfGas; address contractAddr; }
     struct BroadcastTxSummary { bytes32 txHash; BroadcastTxType txType; address contractAddress; uint64 blockNumber; bool success; }
     struct SignedDelegation { uint8 v; bytes32 r; bytes32 s; uint64 nonce; address implementation; }
+    struct BlockReceipt { uint64 transactionIndex; bool success; uint64 gasUsed; uint64 cumulativeGasUsed; address contractAddress; bytes output; Log[] logs; }
+    struct OpcodeCount { uint8 opcode; uint64 count; }
+    struct StorageSlot { address account; bytes32 slot; }
+    struct OpcodeRecord { OpcodeCount[] opcodes; uint64 externalCalls; uint64 coldAccesses; uint64 warmAccesses; StorageSlot[] storageWrites; }
     function _expectCheatcodeRevert() external;
     function _expectCheatcodeRevert(bytes4 revertData) external;
     function _expectCheatcodeRevert(bytes calldata revertData) external;
@@ -113,6 +118,9 @@ interface Vm {
     function assertLt(uint256 left, uint256 right, string calldata error) external pure;
     function assertLt(int256 left, int256 right) external pure;
     function assertLt(int256 left, int256 right, string calldata error) external pure;
+    function assertMaxExternalCalls(OpcodeRecord calldata record, uint256 maxCalls) external pure;
+    function assertNoOpcode(OpcodeRecord calldata record, uint8 opcode) external pure;
+    function assertNoStorageWrite(OpcodeRecord calldata record, address account, bytes32 slot) external pure;
     function assertNotEqDecimal(uint256 left, uint256 right, uint256 decimals) external pure;
     function assertNotEqDecimal(uint256 left, uint256 right, uint256 decimals, string calldata error) external pure;
     function assertNotEqDecimal(int256 left, int256 right, uint256 decimals) external pure;
@@ -152,6 +160,7 @@ interface Vm {
     function attachDelegation(SignedDelegation calldata signedDelegation) external;
     function blobBaseFee(uint256 newBlobBaseFee) external;
     function blobhashes(bytes32[] calldata hashes) external;
+    function blockTransact(address from, address to, uint256 value, bytes calldata data) external returns (BlockReceipt memory receipt);
     function breakpoint(string calldata char) external pure;
     function breakpoint(string calldata char, bool value) external pure;
     function broadcastRawTransaction(bytes calldata data) external;
@@ -169,6 +178,8 @@ interface Vm {
     function computeCreate2Address(bytes32 salt, bytes32 initCodeHash) external pure returns (address);
     function computeCreateAddress(address deployer, uint256 nonce) external pure returns (address);
     function connectDb(string calldata host, uint16 port, string calldata user, string calldata password, string calldata name) external;
+    function connectDb(string calldata alias) external;
+    function connectSqlite(string calldata path) external;
     function contains(string calldata subject, string calldata search) external returns (bool result);
     function cool(address target) external;
     function copyFile(string calldata from, string calldata to) external returns (uint64 copied);
@@ -196,6 +207,9 @@ interface Vm {
     function deriveKey(string calldata mnemonic, string calldata derivationPath, uint32 index, string calldata language) external pure returns (uint256 privateKey);
     function difficulty(uint256 newDifficulty) external;
     function dumpState(string calldata pathToStateJson) external;
+    function eip712HashStruct(string calldata typeNameOrDefinition, bytes calldata abiEncodedData) external view returns (bytes32 structHash);
+    function eip712HashTypedData(string calldata jsonData) external view returns (bytes32 digest);
+    function endBlock() external returns (bytes32 blockHash);
     function ensNamehash(string calldata name) external pure returns (bytes32);
     function envAddress(string calldata name) external view returns (address value);
     function envAddress(string calldata name, string calldata delim) external view returns (address[] memory value);
@@ -228,8 +242,12 @@ interface Vm {
     function envUint(string calldata name, string calldata delim) external view returns (uint256[] memory value);
     function etch(address target, bytes calldata newRuntimeBytecode) external;
     function eth_getLogs(uint256 fromBlock, uint256 toBlock, address target, bytes32[] calldata topics) external returns (EthGetLogs[] memory logs);
+    function eth_getLogs(uint256 fromBlock, uint256 toBlock, address[] calldata targets, bytes32[][] calldata topics) external returns (EthGetLogs[] memory logs);
+    function eth_getLogs(uint256 fromBlock, uint256 toBlock, address[] calldata targets, string calldata eventSignature) external returns (EthDecodedLog[] memory logs);
     function execute(string calldata query, bytes[] calldata params) external returns (uint64);
     function executeInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params) external returns (uint64);
+    function executeTyped(string calldata query, string calldata paramTypes, bytes calldata params) external returns (uint64);
+    function executeTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params) external returns (uint64);
     function exists(string calldata path) external view returns (bool result);
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data) external;
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data, uint64 count) external;
@@ -388,6 +406,10 @@ interface Vm {
     function queryInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params, string[] calldata selects) external view returns (bytes[][] memory);
     function queryOpt(string calldata query, bytes[] calldata params, string[] calldata selects) external view returns (bytes[] memory);
     function queryOptInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params, string[] calldata selects) external view returns (bytes[] memory);
+    function queryOptTyped(string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes memory row);
+    function queryOptTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes memory row);
+    function queryTyped(string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes[] memory rows);
+    function queryTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bytes[] memory rows);
     function randomAddress() external returns (address);
     function randomBool() external view returns (bool);
     function randomBytes(uint256 len) external view returns (bytes memory);
@@ -413,6 +435,7 @@ interface Vm {
     function rememberKeys(string calldata mnemonic, string calldata derivationPath, string calldata language, uint32 count) external returns (address[] memory keyAddrs);
     function removeDir(string calldata path, bool recursive) external;
     function removeFile(string calldata path) external;
+    function reorg(uint256 depth) external returns (bytes32[] memory blockHashes);
     function replace(string calldata input, string calldata from, string calldata to) external pure returns (string memory output);
     function resetGasMetering() external;
     function resetNonce(address account) external;
@@ -436,6 +459,7 @@ interface Vm {
     function rpcUrls() external view returns (string[2][] memory urls);
     function rpc(string calldata method, string calldata params) external returns (bytes memory data);
     function rpc(string calldata urlOrAlias, string calldata method, string calldata params) external returns (bytes memory data);
+    function selectDb(string calldata alias) external;
     function selectFork(uint256 forkId) external;
     function serializeAddress(string calldata objectKey, string calldata valueKey, address value) external returns (string memory json);
     function serializeAddress(string calldata objectKey, string calldata valueKey, address[] calldata values) external returns (string memory json);
@@ -467,6 +491,7 @@ interface Vm {
     function signCompact(address signer, bytes32 digest) external pure returns (bytes32 r, bytes32 vs);
     function signDelegation(address implementation, uint256 privateKey) external returns (SignedDelegation memory signedDelegation);
     function signP256(uint256 privateKey, bytes32 digest) external pure returns (bytes32 r, bytes32 s);
+    function signTypedData(uint256 privateKey, string calldata jsonData) external view returns (uint8 v, bytes32 r, bytes32 s);
     function sign(Wallet calldata wallet, bytes32 digest) external returns (uint8 v, bytes32 r, bytes32 s);
     function sign(uint256 privateKey, bytes32 digest) external pure returns (uint8 v, bytes32 r, bytes32 s);
     function sign(bytes32 digest) external pure returns (uint8 v, bytes32 r, bytes32 s);
@@ -481,11 +506,13 @@ interface Vm {
     function snapshotValue(string calldata name, uint256 value) external;
     function snapshotValue(string calldata group, string calldata name, uint256 value) external;
     function split(string calldata input, string calldata delimiter) external pure returns (string[] memory outputs);
+    function startBlock() external returns (uint256 blockNumber);
     function startBroadcast() external;
     function startBroadcast(address signer) external;
     function startBroadcast(uint256 privateKey) external;
     function startDebugTraceRecording() external;
     function startMappingRecording() external;
+    function startOpcodeRecording() external;
     function startPrank(address msgSender) external;
     function startPrank(address msgSender, address txOrigin) external;
     function startPrank(address msgSender, bool delegateCall) external;
@@ -494,6 +521,7 @@ interface Vm {
     function startSnapshotGas(string calldata group, string calldata name) external;
     function startStateDiffRecording() external;
     function stopAndReturnDebugTraceRecording() external returns (DebugStep[] memory step);
+    function stopAndReturnOpcodes() external returns (OpcodeRecord memory record);
     function stopAndReturnStateDiff() external returns (AccountAccess[] memory accountAccesses);
     function stopBroadcast() external;
     function stopExpectSafeMemory() external;
@@ -518,7 +546,15 @@ interface Vm {
     function transact(bytes32 txHash) external;
     function transact(uint256 forkId, bytes32 txHash) external;
     function trim(string calldata input) external pure returns (string memory output);
+    function tryExecute(string calldata query, bytes[] calldata params) external returns (bool ok, bytes memory data);
+    function tryExecuteInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params) external returns (bool ok, bytes memory data);
+    function tryExecuteTyped(string calldata query, string calldata paramTypes, bytes calldata params) external returns (bool ok, bytes memory data);
+    function tryExecuteTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params) external returns (bool ok, bytes memory data);
     function tryFfi(string[] calldata commandInput) external returns (FfiResult memory result);
+    function tryQuery(string calldata query, bytes[] calldata params, string[] calldata selects) external view returns (bool ok, bytes memory data);
+    function tryQueryInTransaction(uint128 transactionId, string calldata query, bytes[] calldata params, string[] calldata selects) external view returns (bool ok, bytes memory data);
+    function tryQueryTyped(string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bool ok, bytes memory data);
+    function tryQueryTypedInTransaction(uint128 transactionId, string calldata query, string calldata paramTypes, bytes calldata params, string calldata rowType) external view returns (bool ok, bytes memory data);
     function txGasPrice(uint256 newGasPrice) external;
     function unixTime() external view returns (uint256 milliseconds);
     function warp(uint256 newTimestamp) external;
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract Eip712Test is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    struct Person {
+        string name;
+        address wallet;
+    }
+
+    struct Mail {
+        Person from;
+        Person to;
+        string contents;
+    }
+
+    // The example of the EIP-712 specification.
+    string constant MAIL_DEFINITION = "Mail(Person from,Person to,string contents)Person(string name,address wallet)";
+    bytes32 constant MAIL_HASH = 0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e;
+    bytes32 constant MAIL_DIGEST = 0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2;
+
+    string constant DOMAIN =
+        '"domain":{"name":"Ether Mail","version":"1","chainId":1,"verifyingContract":"0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"}';
+    string constant MESSAGE =
+        '"message":{"from":{"name":"Cow","wallet":"0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},"to":{"name":"Bob","wallet":"0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},"contents":"Hello, Bob!"}';
+    string constant TYPES =
+        '"types":{"EIP712Domain":[{"name":"name","type":"string"},{"name":"version","type":"string"},{"name":"chainId","type":"uint256"},{"name":"verifyingContract","type":"address"}],"Person":[{"name":"name","type":"string"},{"name":"wallet","type":"address"}],"Mail":[{"name":"from","type":"Person"},{"name":"to","type":"Person"},{"name":"contents","type":"string"}]}';
+
+    function mail() internal pure returns (Mail memory) {
+        return Mail({
+            from: Person({name: "Cow", wallet: 0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826}),
+            to: Person({name: "Bob", wallet: 0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB}),
+            contents: "Hello, Bob!"
+        });
+    }
+
+    function testHashStructWithDefinition() public {
+        assertEq(vm.eip712HashStruct(MAIL_DEFINITION, abi.encode(mail())), MAIL_HASH);
+    }
+
+    function testHashStructWithTypeName() public {
+        assertEq(vm.eip712HashStruct("Mail", abi.encode(mail())), MAIL_HASH);
+        assertEq(vm.eip712HashStruct("Eip712Test.Mail", abi.encode(mail())), MAIL_HASH);
+    }
+
+    function testHashStructUnknownTypeName() public {
+        vm._expectCheatcodeRevert("no struct `Letter` supported by EIP-712 found in the project");
+        vm.eip712HashStruct("Letter", abi.encode(mail()));
+    }
+
+    function testHashTypedData() public {
+        string memory json = string.concat("{", TYPES, ',"primaryType":"Mail",', DOMAIN, ",", MESSAGE, "}");
+        assertEq(vm.eip712HashTypedData(json), MAIL_DIGEST);
+    }
+
+    function testHashTypedDataWithTypeName() public {
+        // Without `types`, the definition of `Mail` is resolved from the project sources.
+        string memory json = string.concat('{"primaryType":"Mail",', DOMAIN, ",", MESSAGE, "}");
+        assertEq(vm.eip712HashTypedData(json), MAIL_DIGEST);
+    }
+
+    function testSignTypedData() public {
+        string memory json = string.concat("{", TYPES, ',"primaryType":"Mail",', DOMAIN, ",", MESSAGE, "}");
+        uint256 pk = uint256(keccak256("cow"));
+
+        (uint8 v, bytes32 r, bytes32 s) = vm.signTypedData(pk, json);
+        assertEq(v, 28);
+        assertEq(r, 0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d);
+        assertEq(s, 0x07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562);
+        assertEq(ecrecover(MAIL_DIGEST, v, r, s), vm.addr(pk));
+    }
+}
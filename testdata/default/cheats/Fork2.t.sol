@@ -220,6 +220,53 @@ contract ForkTest is DSTest {
         }
     }
 
+    function testEthGetLogsMultipleTopics() public {
+        vm.selectFork(mainnetFork);
+        address weth = address(0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2);
+        bytes32 withdrawalTopic = 0x7fcf532c15f0a6db0bd6d0e038bea71d30d808c7d98cb3bf7268a95bf5081b65;
+        bytes32 depositTopic = 0xe1fffcc4923d04b559f4d29a8bfc6cda04eb5b0d3c460751c2402c5c5cc9109c;
+        uint256 blockNumber = 17623835;
+
+        address[] memory targets = new address[](1);
+        targets[0] = weth;
+        bytes32[][] memory topics = new bytes32[][](1);
+        topics[0] = new bytes32[](2);
+        topics[0][0] = withdrawalTopic;
+        topics[0][1] = depositTopic;
+        Vm.EthGetLogs[] memory logs = vm.eth_getLogs(blockNumber, blockNumber, targets, topics);
+
+        uint256 withdrawals;
+        for (uint256 i = 0; i < logs.length; i++) {
+            assertEq(logs[i].emitter, weth);
+            assertTrue(logs[i].topics[0] == withdrawalTopic || logs[i].topics[0] == depositTopic);
+            if (logs[i].topics[0] == withdrawalTopic) withdrawals++;
+        }
+        assertEq(withdrawals, 3);
+    }
+
+    function testEthGetLogsDecoded() public {
+        vm.selectFork(mainnetFork);
+        address weth = address(0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2);
+        uint256 blockNumber = 17623835;
+
+        string memory file = vm.readFile("fixtures/Rpc/eth_getLogs.json");
+        EthGetLogsJsonParseable[] memory fixtureLogs = abi.decode(vm.parseJson(file), (EthGetLogsJsonParseable[]));
+
+        address[] memory targets = new address[](1);
+        targets[0] = weth;
+        Vm.EthDecodedLog[] memory logs =
+            vm.eth_getLogs(blockNumber, blockNumber, targets, "Withdrawal(address indexed src, uint256 wad)");
+        assertEq(logs.length, 3);
+
+        for (uint256 i = 0; i < logs.length; i++) {
+            (address src, uint256 wad) = abi.decode(logs[i].args, (address, uint256));
+            assertEq(logs[i].emitter, weth);
+            assertEq(logs[i].transactionHash, fixtureLogs[i].transactionHash);
+            assertEq(src, address(uint160(uint256(fixtureLogs[i].topics[1]))));
+            assertEq(wad, uint256(fixtureLogs[i].data));
+        }
+    }
+
     function testRpc() public {
         // balance at block <https://etherscan.io/block/18332681>
         vm.selectFork(mainnetFork);
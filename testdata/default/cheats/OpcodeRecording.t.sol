@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract Store {
+    uint256 public value;
+
+    function set(uint256 newValue) external {
+        value = newValue;
+    }
+}
+
+contract Caller {
+    function setTwice(Store store, uint256 newValue) external {
+        store.set(newValue);
+        store.set(newValue + 1);
+    }
+
+    function delegateSet(Store store, uint256 newValue) external {
+        (bool success,) = address(store).delegatecall(abi.encodeCall(Store.set, (newValue)));
+        require(success);
+    }
+}
+
+contract OpcodeRecordingTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    uint8 constant SLOAD = 0x54;
+    uint8 constant SSTORE = 0x55;
+    uint8 constant DELEGATECALL = 0xf4;
+
+    Store store;
+    Caller caller;
+
+    function setUp() public {
+        store = new Store();
+        caller = new Caller();
+    }
+
+    function count(Vm.OpcodeRecord memory record, uint8 opcode) internal pure returns (uint64) {
+        for (uint256 i = 0; i < record.opcodes.length; i++) {
+            if (record.opcodes[i].opcode == opcode) {
+                return record.opcodes[i].count;
+            }
+        }
+        return 0;
+    }
+
+    function testRecordsOpcodes() public {
+        vm.startOpcodeRecording();
+        caller.setTwice(store, 1);
+        Vm.OpcodeRecord memory record = vm.stopAndReturnOpcodes();
+
+        assertEq(count(record, SSTORE), 2);
+        assertEq(record.externalCalls, 2);
+        assertEq(record.storageWrites.length, 2);
+        assertEq(record.storageWrites[0].account, address(store));
+        assertEq(record.storageWrites[0].slot, bytes32(0));
+
+        vm.assertNoOpcode(record, DELEGATECALL);
+        vm.assertMaxExternalCalls(record, 2);
+        vm.assertNoStorageWrite(record, address(store), bytes32(uint256(1)));
+    }
+
+    function testDoesNotRecordCurrentFrame() public {
+        vm.startOpcodeRecording();
+        store.value();
+        Vm.OpcodeRecord memory record = vm.stopAndReturnOpcodes();
+
+        // Only the call made by the test is recorded, not the test's own `CALL`.
+        assertEq(count(record, SLOAD), 1);
+        assertEq(record.externalCalls, 0);
+        assertEq(record.storageWrites.length, 0);
+    }
+
+    function testColdAndWarmAccesses() public {
+        vm.startOpcodeRecording();
+        caller.setTwice(store, 1);
+        Vm.OpcodeRecord memory record = vm.stopAndReturnOpcodes();
+
+        // The first call to the store and its first access to the slot are cold; the second call
+        // and the later accesses to the slot are warm.
+        assertEq(record.coldAccesses, 2);
+        assertTrue(record.warmAccesses >= 2);
+    }
+
+    function testAssertNoOpcodeFails() public {
+        vm.startOpcodeRecording();
+        caller.delegateSet(store, 1);
+        Vm.OpcodeRecord memory record = vm.stopAndReturnOpcodes();
+
+        vm._expectCheatcodeRevert("DELEGATECALL was executed 1 times");
+        vm.assertNoOpcode(record, DELEGATECALL);
+    }
+
+    function testAssertMaxExternalCallsFails() public {
+        vm.startOpcodeRecording();
+        caller.setTwice(store, 1);
+        Vm.OpcodeRecord memory record = vm.stopAndReturnOpcodes();
+
+        vm._expectCheatcodeRevert("2 external calls were made, more than the maximum of 1");
+        vm.assertMaxExternalCalls(record, 1);
+    }
+
+    function testAssertNoStorageWriteFails() public {
+        vm.startOpcodeRecording();
+        caller.delegateSet(store, 1);
+        Vm.OpcodeRecord memory record = vm.stopAndReturnOpcodes();
+
+        // The delegate call writes to the storage of the caller, not the store.
+        vm.assertNoStorageWrite(record, address(store), bytes32(0));
+        vm._expectCheatcodeRevert(
+            "slot 0x0000000000000000000000000000000000000000000000000000000000000000 of 0x2e234DAe75C793f67A35089C9d99245E1C58470b was written 1 times"
+        );
+        vm.assertNoStorageWrite(record, address(caller), bytes32(0));
+    }
+
+    function testRevertsWithoutRecording() public {
+        vm._expectCheatcodeRevert("opcode recording was not started; call `startOpcodeRecording` first");
+        vm.stopAndReturnOpcodes();
+    }
+}
@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract Counter {
+    event Incremented(address origin, uint256 count);
+
+    uint256 public count;
+
+    function increment() external {
+        count++;
+        emit Incremented(tx.origin, count);
+    }
+
+    function fail() external pure {
+        revert("failed");
+    }
+}
+
+contract SimulatedBlockTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    Counter counter;
+    address alice = address(0xa11ce);
+    address bob = address(0xb0b);
+
+    function setUp() public {
+        counter = new Counter();
+    }
+
+    function increment(address from) internal returns (Vm.BlockReceipt memory) {
+        return vm.blockTransact(from, address(counter), 0, abi.encodeCall(Counter.increment, ()));
+    }
+
+    function testBlockTransactions() public {
+        uint256 timestamp = vm.getBlockTimestamp();
+        uint256 number = vm.startBlock();
+        assertEq(vm.getBlockNumber(), number);
+        assertEq(vm.getBlockTimestamp(), timestamp + 12);
+
+        Vm.BlockReceipt memory first = increment(alice);
+        Vm.BlockReceipt memory second = increment(bob);
+        Vm.BlockReceipt memory failed =
+            vm.blockTransact(alice, address(counter), 0, abi.encodeCall(Counter.fail, ()));
+
+        assertEq(first.transactionIndex, 0);
+        assertTrue(first.success);
+        assertEq(first.cumulativeGasUsed, first.gasUsed);
+        assertEq(first.logs.length, 1);
+        assertEq(first.logs[0].emitter, address(counter));
+        (address origin, uint256 count) = abi.decode(first.logs[0].data, (address, uint256));
+        assertEq(origin, alice);
+        assertEq(count, 1);
+
+        assertEq(second.transactionIndex, 1);
+        assertEq(second.cumulativeGasUsed, first.gasUsed + second.gasUsed);
+        (origin, count) = abi.decode(second.logs[0].data, (address, uint256));
+        assertEq(origin, bob);
+        assertEq(count, 2);
+
+        assertEq(failed.transactionIndex, 2);
+        assertTrue(!failed.success);
+        assertEq(failed.output, abi.encodeWithSignature("Error(string)", "failed"));
+        assertEq(failed.logs.length, 0);
+
+        bytes32 blockHash = vm.endBlock();
+        assertEq(vm.getBlockNumber(), number + 1);
+        assertEq(blockhash(number), blockHash);
+        assertEq(counter.count(), 2);
+    }
+
+    function testCreateTransaction() public {
+        vm.startBlock();
+        Vm.BlockReceipt memory receipt = vm.blockTransact(alice, address(0), 0, type(Counter).creationCode);
+        vm.endBlock();
+
+        assertTrue(receipt.success);
+        assertTrue(receipt.contractAddress != address(0));
+        assertEq(receipt.contractAddress.code, receipt.output);
+    }
+
+    function testReorg() public {
+        uint256 start = vm.getBlockNumber();
+        uint256 startTimestamp = vm.getBlockTimestamp();
+
+        uint256 first = vm.startBlock();
+        increment(alice);
+        bytes32 firstHash = vm.endBlock();
+        uint256 second = vm.startBlock();
+        increment(bob);
+        bytes32 secondHash = vm.endBlock();
+        assertEq(first, start + 1);
+        assertEq(second, first + 1);
+        assertEq(counter.count(), 2);
+
+        bytes32[] memory reorged = vm.reorg(2);
+        assertEq(reorged.length, 2);
+        assertEq(reorged[0], firstHash);
+        assertEq(reorged[1], secondHash);
+        assertEq(vm.getBlockNumber(), start);
+        assertEq(vm.getBlockTimestamp(), startTimestamp);
+        assertEq(counter.count(), 0);
+
+        // The block simulated again at the same height gets a different hash.
+        uint256 number = vm.startBlock();
+        increment(bob);
+        bytes32 newHash = vm.endBlock();
+        assertEq(number, start + 1);
+        assertTrue(newHash != firstHash);
+        assertEq(blockhash(number), newHash);
+        assertEq(counter.count(), 1);
+    }
+
+    function testReorgOpenBlock() public {
+        vm.startBlock();
+        increment(alice);
+
+        bytes32[] memory reorged = vm.reorg(1);
+        assertEq(reorged[0], bytes32(0));
+        assertEq(counter.count(), 0);
+    }
+
+    function testRevertsWithoutOpenBlock() public {
+        vm._expectCheatcodeRevert("no open simulated block; call `startBlock` first");
+        vm.endBlock();
+    }
+
+    function testRevertsOnNestedBlock() public {
+        vm.startBlock();
+        vm._expectCheatcodeRevert("a simulated block is already open; call `endBlock` first");
+        vm.startBlock();
+    }
+
+    function testRevertsOnDeepReorg() public {
+        vm.startBlock();
+        vm.endBlock();
+        vm._expectCheatcodeRevert("cannot reorg 2 blocks; there are 1 simulated blocks");
+        vm.reorg(2);
+    }
+}
@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract SqliteTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function selectNames() internal view returns (bytes[][] memory) {
+        string[] memory selects = new string[](1);
+        selects[0] = "name";
+        return vm.query("SELECT name FROM users ORDER BY id", new bytes[](0), selects);
+    }
+
+    function insertUser(bytes memory name) internal returns (uint64) {
+        bytes[] memory params = new bytes[](1);
+        params[0] = name;
+        return vm.execute("INSERT INTO users (name) VALUES (?1)", params);
+    }
+
+    function testQueryInMemory() public {
+        vm.connectSqlite(":memory:");
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+
+        assertEq(insertUser("alice"), 1);
+        assertEq(insertUser("bob"), 1);
+
+        bytes[][] memory rows = selectNames();
+        assertEq(rows.length, 2);
+        assertEq(rows[0][0], bytes("alice"));
+        assertEq(rows[1][0], bytes("bob"));
+
+        bytes[] memory params = new bytes[](1);
+        params[0] = "bob";
+        string[] memory selects = new string[](1);
+        selects[0] = "name";
+        bytes[] memory row = vm.queryOpt("SELECT name FROM users WHERE name = ?1", params, selects);
+        assertEq(row[0], bytes("bob"));
+    }
+
+    function testTransactions() public {
+        vm.connectSqlite(":memory:");
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+
+        bytes[] memory params = new bytes[](1);
+        params[0] = "alice";
+        uint128 committed = vm.openTransaction();
+        vm.executeInTransaction(committed, "INSERT INTO users (name) VALUES (?1)", params);
+        vm.commitTransaction(committed);
+
+        params[0] = "bob";
+        uint128 rolledBack = vm.openTransaction();
+        vm.executeInTransaction(rolledBack, "INSERT INTO users (name) VALUES (?1)", params);
+        string[] memory selects = new string[](1);
+        selects[0] = "name";
+        bytes[][] memory rows =
+            vm.queryInTransaction(rolledBack, "SELECT name FROM users ORDER BY id", new bytes[](0), selects);
+        assertEq(rows.length, 2);
+        vm.rollbackTransaction(rolledBack);
+
+        rows = selectNames();
+        assertEq(rows.length, 1);
+        assertEq(rows[0][0], bytes("alice"));
+    }
+
+    function testTyped() public {
+        vm.connectSqlite(":memory:");
+        vm.execute(
+            "CREATE TABLE balances (owner TEXT, amount TEXT, active BOOL, updated TIMESTAMP, note TEXT)",
+            new bytes[](0)
+        );
+
+        address owner = address(0xBEEF);
+        uint256 amount = type(uint256).max;
+        uint64 inserted = vm.executeTyped(
+            "INSERT INTO balances VALUES (?1, ?2, ?3, ?4, ?5)",
+            "(address,uint256,bool,string,null)",
+            abi.encode(owner, amount, true, "2024-01-02 03:04:05")
+        );
+        assertEq(inserted, 1);
+
+        bytes[] memory rows = vm.queryTyped(
+            "SELECT owner, amount, active, updated, note FROM balances WHERE owner = ?1",
+            "(address)",
+            abi.encode(owner),
+            "(address,uint256,bool,uint64,string)"
+        );
+        assertEq(rows.length, 1);
+        (address o, uint256 a, bool active, uint64 updated, string memory note) =
+            abi.decode(rows[0], (address, uint256, bool, uint64, string));
+        assertEq(o, owner);
+        assertEq(a, amount);
+        assertTrue(active);
+        assertEq(updated, 1704164645);
+        assertEq(note, "");
+
+        bytes memory row = vm.queryOptTyped(
+            "SELECT amount FROM balances WHERE owner = ?1", "(address)", abi.encode(address(0)), "(uint256)"
+        );
+        assertEq(row.length, 0);
+    }
+
+    function testTypedInTransaction() public {
+        vm.connectSqlite(":memory:");
+        vm.execute("CREATE TABLE counters (value INTEGER)", new bytes[](0));
+
+        uint128 transaction = vm.openTransaction();
+        vm.executeTypedInTransaction(transaction, "INSERT INTO counters VALUES (?1)", "(int256)", abi.encode(int256(-7)));
+        bytes memory row =
+            vm.queryOptTypedInTransaction(transaction, "SELECT value FROM counters", "()", "", "(int256)");
+        assertEq(abi.decode(row, (int256)), -7);
+        bytes[] memory rows =
+            vm.queryTypedInTransaction(transaction, "SELECT value FROM counters", "()", "", "(int8)");
+        assertEq(abi.decode(rows[0], (int8)), -7);
+        vm.rollbackTransaction(transaction);
+
+        rows = vm.queryTyped("SELECT value FROM counters", "()", "", "(int256)");
+        assertEq(rows.length, 0);
+    }
+
+    function testRevertsWithoutConnection() public {
+        vm._expectCheatcodeRevert("no database connection; call `connectDb` or `connectSqlite` first");
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)", new bytes[](0));
+    }
+
+    function testRevertsOnInvalidQuery() public {
+        vm.connectSqlite(":memory:");
+        vm._expectCheatcodeRevert("SQLite error: no such table: missing");
+        vm.query("SELECT name FROM missing", new bytes[](0), new string[](0));
+    }
+
+    function testTry() public {
+        vm.connectSqlite(":memory:");
+        (bool ok, bytes memory data) =
+            vm.tryExecute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+        assertTrue(ok);
+        assertEq(abi.decode(data, (uint64)), 0);
+
+        (ok, data) = vm.tryExecuteTyped("INSERT INTO users (name) VALUES (?1)", "(null)", "");
+        assertTrue(!ok);
+        assertEq(string(data), "SQLite error: NOT NULL constraint failed: users.name");
+
+        (ok, data) = vm.tryQueryTyped("SELECT id FROM users", "()", "", "(uint256");
+        assertTrue(!ok);
+
+        insertUser("alice");
+        (ok, data) = vm.tryQueryTyped("SELECT id FROM users", "()", "", "(uint256)");
+        assertTrue(ok);
+        bytes[] memory rows = abi.decode(data, (bytes[]));
+        assertEq(abi.decode(rows[0], (uint256)), 1);
+    }
+
+    function openAndRevert() external {
+        uint128 transaction = vm.openTransaction();
+        vm.executeInTransaction(transaction, "INSERT INTO users (name) VALUES ('bob')", new bytes[](0));
+        revert("reverted");
+    }
+
+    function testRollbackOnRevert() public {
+        vm.connectSqlite(":memory:");
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+        insertUser("alice");
+
+        // The transaction opened by the call is rolled back when it reverts.
+        try this.openAndRevert() {} catch {}
+        assertEq(selectNames().length, 1);
+    }
+
+    function testEndpoints() public {
+        vm.createDir("fixtures/Database", true);
+        vm.connectDb("writable");
+        vm.execute("DROP TABLE IF EXISTS users", new bytes[](0));
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+        insertUser("alice");
+
+        // Both connections stay open, and the current one is switched with `selectDb`.
+        vm.connectDb("memory");
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+        assertEq(selectNames().length, 0);
+        vm.selectDb("writable");
+        assertEq(selectNames().length, 1);
+
+        // Only the writers of an endpoint may write to it.
+        vm.connectDb("readonly");
+        assertEq(selectNames().length, 1);
+        vm._expectCheatcodeRevert("SQLite error: attempt to write a readonly database");
+        insertUser("bob");
+
+        vm.removeFile("fixtures/Database/endpoint.db");
+    }
+
+    function testEndpointNotFound() public {
+        vm._expectCheatcodeRevert("database endpoint `missing` not found in `[db_endpoints]`");
+        vm.connectDb("missing");
+    }
+
+    function testSelectNotConnected() public {
+        vm._expectCheatcodeRevert("database `memory` is not connected; call `connectDb(\"memory\")` first");
+        vm.selectDb("memory");
+    }
+
+    function testFile() public {
+        vm.createDir("fixtures/Database", true);
+        string memory path = "fixtures/Database/sqlite.db";
+        if (vm.exists(path)) vm.removeFile(path);
+        vm.connectSqlite(path);
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+        insertUser("alice");
+
+        // A new connection to the same file sees the rows.
+        vm.connectSqlite(path);
+        bytes[][] memory rows = selectNames();
+        assertEq(rows.length, 1);
+        assertEq(rows[0][0], bytes("alice"));
+
+        vm.removeFile(path);
+    }
+
+    function testFilePermissions() public {
+        vm._expectCheatcodeRevert("the path /etc/foundry.db is not allowed to be accessed for read operations");
+        vm.connectSqlite("/etc/foundry.db");
+    }
+}
+
+contract SqliteSetUpTransactionTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    uint128 transaction;
+
+    function setUp() public {
+        vm.connectSqlite(":memory:");
+        vm.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name BLOB NOT NULL)", new bytes[](0));
+        transaction = vm.openTransaction();
+        vm.executeInTransaction(transaction, "INSERT INTO users (name) VALUES ('alice')", new bytes[](0));
+    }
+
+    // The transactions left open by `setUp()` are rolled back when it ends.
+    function testSetUpTransactionIsRolledBack() public {
+        string[] memory selects = new string[](1);
+        selects[0] = "name";
+        assertEq(vm.query("SELECT name FROM users", new bytes[](0), selects).length, 0);
+
+        vm._expectCheatcodeRevert("Not found: Transaction not found");
+        vm.commitTransaction(transaction);
+    }
+}
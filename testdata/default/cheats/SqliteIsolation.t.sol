@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+import "cheats/Vm.sol";
+
+contract SqliteIsolationTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function setUp() public {
+        // The fixtures are loaded when the database is connected.
+        vm.connectDb("isolated");
+        vm.executeTyped("INSERT INTO accounts VALUES (?1, ?2)", "(string,uint256)", abi.encode("carol", 10));
+    }
+
+    function balances() internal view returns (bytes[] memory) {
+        return vm.queryTyped("SELECT balance FROM accounts ORDER BY owner", "()", "", "(uint256)");
+    }
+
+    function testFixturesAndSetUp() public {
+        bytes[] memory rows = balances();
+        assertEq(rows.length, 3);
+        assertEq(abi.decode(rows[0], (uint256)), 100);
+        assertEq(abi.decode(rows[1], (uint256)), 50);
+        assertEq(abi.decode(rows[2], (uint256)), 10);
+    }
+
+    // Each test sees the rows of the fixtures and `setUp()` only, whichever runs first.
+    function testDeleteIsRolledBack() public {
+        vm.execute("DELETE FROM accounts", new bytes[](0));
+        assertEq(balances().length, 0);
+    }
+
+    function testInsertIsRolledBack() public {
+        vm.executeTyped("INSERT INTO accounts VALUES (?1, ?2)", "(string,uint256)", abi.encode("dave", 1));
+        assertEq(balances().length, 4);
+    }
+
+    // Each fuzz run is rolled back too.
+    function testFuzzInsertIsRolledBack(uint64 balance) public {
+        assertEq(balances().length, 3);
+        vm.executeTyped("INSERT INTO accounts VALUES (?1, ?2)", "(string,uint256)", abi.encode("erin", balance));
+        assertEq(balances().length, 4);
+    }
+
+    function testTransactionsUnsupported() public {
+        vm._expectCheatcodeRevert(
+            "Unsupported: transactions of isolated databases, whose tests already run in a transaction"
+        );
+        vm.openTransaction();
+    }
+}
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.18;
+
+import "ds-test/test.sol";
+
+contract Vault {
+    address public admin = address(0xad);
+    mapping(address => uint256) public deposits;
+    bool public paused;
+
+    function pause(bool value) public {
+        require(msg.sender == admin, "only admin");
+        paused = value;
+    }
+
+    function deposit(uint256 amount) public {
+        require(msg.sender == address(0xa1) || msg.sender == address(0xa2), "only users");
+        if (!paused) {
+            deposits[msg.sender] += amount % 1 ether;
+        }
+    }
+}
+
+contract TargetRoles is DSTest {
+    struct FuzzSelector {
+        address addr;
+        bytes4[] selectors;
+    }
+
+    struct FuzzRole {
+        string name;
+        address[] senders;
+        FuzzSelector[] selectors;
+    }
+
+    Vault vault;
+
+    function setUp() public {
+        vault = new Vault();
+    }
+
+    function targetRoles() public view returns (FuzzRole[] memory roles) {
+        roles = new FuzzRole[](2);
+
+        address[] memory admins = new address[](1);
+        admins[0] = address(0xad);
+        FuzzSelector[] memory adminSelectors = new FuzzSelector[](1);
+        adminSelectors[0] = FuzzSelector(address(vault), new bytes4[](1));
+        adminSelectors[0].selectors[0] = Vault.pause.selector;
+        roles[0] = FuzzRole("admin", admins, adminSelectors);
+
+        address[] memory users = new address[](2);
+        users[0] = address(0xa1);
+        users[1] = address(0xa2);
+        FuzzSelector[] memory userSelectors = new FuzzSelector[](1);
+        userSelectors[0] = FuzzSelector(address(vault), new bytes4[](1));
+        userSelectors[0].selectors[0] = Vault.deposit.selector;
+        roles[1] = FuzzRole("user", users, userSelectors);
+    }
+
+    function targetSelectors() public view returns (FuzzSelector[] memory selectors) {
+        selectors = new FuzzSelector[](1);
+        selectors[0] = FuzzSelector(address(vault), new bytes4[](2));
+        selectors[0].selectors[0] = Vault.pause.selector;
+        selectors[0].selectors[1] = Vault.deposit.selector;
+    }
+
+    function invariantOnlyUsersDeposit() public {
+        require(vault.deposits(address(0xad)) == 0, "admin deposited");
+    }
+}
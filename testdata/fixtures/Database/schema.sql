@@ -0,0 +1 @@
+CREATE TABLE accounts (owner TEXT NOT NULL, balance INTEGER NOT NULL);